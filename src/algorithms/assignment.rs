@@ -0,0 +1,448 @@
+//! Задача о назначениях: венгерский алгоритм для паросочетания
+//! минимального или максимального веса в двудольном графе.
+
+use std::{collections::HashSet, error::Error};
+
+use crate::{
+    algorithms::bipartite::bipartition,
+    graph::{
+        core::{GraphError, GraphKindError, Index},
+        view::GraphView,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Что оптимизировать: суммарную стоимость или суммарный вес назначения
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Objective {
+    #[default]
+    Minimize,
+    Maximize,
+}
+
+/// Найденное назначение: пары `(левая, правая, вес)` по возрастанию левой
+/// вершины и их суммарный вес.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment<W> {
+    pairs: Vec<(Index, Index, W)>,
+    total: W,
+    unassigned: Vec<Index>,
+}
+
+impl<W: Weight> Assignment<W> {
+    /// Пары `(вершина левой доли, вершина правой доли, вес ребра)`
+    pub fn pairs(&self) -> &[(Index, Index, W)] {
+        &self.pairs
+    }
+
+    /// Сумма весов ребер назначения
+    pub fn total(&self) -> W {
+        self.total
+    }
+
+    /// Вершины большей доли, которым не хватило пары (при равных долях
+    /// пусто)
+    pub fn unassigned(&self) -> &[Index] {
+        &self.unassigned
+    }
+}
+
+fn overflow(description: &str) -> Box<dyn Error> {
+    Box::new(GraphError::new(GraphKindError::WeightOverflow, description))
+}
+
+/// Матрица стоимостей: строки --- меньшая доля, столбцы --- большая.
+/// Стоимости сдвинуты так, чтобы быть неотрицательными; отсутствующее
+/// ребро --- [`None`].
+struct CostMatrix<W> {
+    rows: Vec<Index>,
+    columns: Vec<Index>,
+    // Исходный вес и сдвинутая стоимость
+    cells: Vec<Vec<Option<(W, W)>>>,
+    // Строки --- левая доля
+    rows_are_left: bool,
+}
+
+impl<W: Weight> CostMatrix<W> {
+    fn new<G: GraphView<Weight = W>>(
+        graph: &G,
+        left: Vec<Index>,
+        right: Vec<Index>,
+        objective: Objective,
+    ) -> Result<Self> {
+        let rows_are_left = left.len() <= right.len();
+        let (rows, columns) = if rows_are_left {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        let row_of = |index: Index| rows.binary_search(&index).ok();
+        let column_of = |index: Index| columns.binary_search(&index).ok();
+
+        // Из параллельных ребер берем лучшее; направление ребер не важно
+        let mut weights: Vec<Vec<Option<W>>> = vec![vec![None; columns.len()]; rows.len()];
+        for from in graph.node_indices() {
+            for (to, weight) in graph.neighbors(from) {
+                let cell = match (row_of(from), column_of(to), row_of(to), column_of(from)) {
+                    (Some(row), Some(column), _, _) | (_, _, Some(row), Some(column)) => {
+                        &mut weights[row][column]
+                    }
+                    _ => continue,
+                };
+                let better = cell.is_none_or(|old| match objective {
+                    Objective::Minimize => weight < old,
+                    Objective::Maximize => weight > old,
+                });
+                if better {
+                    *cell = Some(weight);
+                }
+            }
+        }
+
+        // Каждая строка получает ровно один столбец, поэтому сдвиг всех
+        // стоимостей на одно число не меняет оптимального назначения. Для
+        // максимума стоимость --- недостача до наибольшего веса
+        let present = weights.iter().flatten().flatten().copied();
+        let (shift, is_max) = match objective {
+            Objective::Minimize => (present.min(), false),
+            Objective::Maximize => (present.max(), true),
+        };
+        let shift = shift.unwrap_or(W::ZERO);
+        let cells = weights
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|weight| {
+                        weight
+                            .map(|weight| {
+                                let cost = if is_max {
+                                    shift.checked_sub(weight)
+                                } else {
+                                    weight.checked_sub(shift)
+                                };
+                                cost.map(|cost| (weight, cost))
+                                    .ok_or_else(|| overflow(&format!("{weight} и {shift}")))
+                            })
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            rows,
+            columns,
+            cells,
+            rows_are_left,
+        })
+    }
+
+    /// Венгерский алгоритм в форме кратчайших увеличивающих путей: строки
+    /// добавляются по одной, для каждой Дейкстрой по приведенным стоимостям
+    /// `c(i, j) + u(i) - v(j) >= 0` ищется путь до свободного столбца.
+    /// Потенциалы `u`, `v` только растут, поэтому остаются неотрицательными.
+    /// Возвращает столбец каждой строки.
+    fn solve(&self) -> Result<Vec<usize>> {
+        let (n, m) = (self.rows.len(), self.columns.len());
+        let mut row_potential = vec![W::ZERO; n];
+        let mut column_potential = vec![W::ZERO; m];
+        let mut row_of_column: Vec<Option<usize>> = vec![None; m];
+        let mut column_of_row: Vec<Option<usize>> = vec![None; n];
+
+        for root in 0..n {
+            let mut dist: Vec<Option<W>> = vec![None; m];
+            // Строка, из которой пришли в столбец
+            let mut parent = vec![root; m];
+            let mut done = vec![false; m];
+            // Расстояние до каждой строки дерева (до корня --- ноль)
+            let mut row_dist: Vec<(usize, W)> = vec![(root, W::ZERO)];
+
+            let mut row = root;
+            let mut row_start = W::ZERO;
+            let (free, delta) = loop {
+                for (column, cell) in self.cells[row].iter().enumerate() {
+                    let Some((_, cost)) = cell else {
+                        continue;
+                    };
+                    if done[column] {
+                        continue;
+                    }
+                    let reduced = cost
+                        .try_add(row_potential[row])?
+                        .checked_sub(column_potential[column])
+                        .ok_or_else(|| overflow("приведенная стоимость"))?;
+                    let candidate = row_start.try_add(reduced)?;
+                    if dist[column].is_none_or(|old| candidate < old) {
+                        dist[column] = Some(candidate);
+                        parent[column] = row;
+                    }
+                }
+
+                let Some((column, found)) = (0..m)
+                    .filter(|&column| !done[column])
+                    .filter_map(|column| dist[column].map(|found| (column, found)))
+                    .min_by_key(|&(column, found)| (found, column))
+                else {
+                    return Err(Box::new(GraphError::new(
+                        GraphKindError::EdgeNotFound,
+                        &format!(
+                            "вершине {} не найти пару: в графе не хватает ребер между долями",
+                            self.rows[root]
+                        ),
+                    )));
+                };
+                done[column] = true;
+                match row_of_column[column] {
+                    None => break (column, found),
+                    // По ребру паросочетания приведенная стоимость нулевая
+                    Some(next) => {
+                        row = next;
+                        row_start = found;
+                        row_dist.push((next, found));
+                    }
+                }
+            };
+
+            // Сдвиг потенциалов на min(d, delta) сохраняет неотрицательность
+            // приведенных стоимостей и обнуляет их вдоль кратчайшего пути
+            for (row, potential) in row_potential.iter_mut().enumerate() {
+                let found = row_dist
+                    .iter()
+                    .find(|&&(other, _)| other == row)
+                    .map_or(delta, |&(_, found)| found);
+                *potential = potential.try_add(found)?;
+            }
+            for (column, potential) in column_potential.iter_mut().enumerate() {
+                let found = match dist[column] {
+                    Some(found) if done[column] => found,
+                    _ => delta,
+                };
+                *potential = potential.try_add(found)?;
+            }
+
+            // Чередуем паросочетание вдоль пути от свободного столбца
+            let mut column = free;
+            loop {
+                let row = parent[column];
+                let previous = column_of_row[row];
+                row_of_column[column] = Some(row);
+                column_of_row[row] = Some(column);
+                match previous {
+                    Some(previous) if row != root => column = previous,
+                    _ => break,
+                }
+            }
+        }
+
+        Ok(column_of_row
+            .into_iter()
+            .map(|column| column.expect("каждой строке назначен столбец"))
+            .collect())
+    }
+}
+
+/// Назначение минимальной стоимости или максимального веса венгерским
+/// алгоритмом за `O(n² m)`, где `n` --- меньшая доля, `m` --- большая.
+/// Каждая вершина меньшей доли получает ровно одну пару; при равных
+/// долях это совершенное паросочетание. Вес пары --- вес ребра между
+/// вершинами (направление не важно, из параллельных ребер берется
+/// лучшее).
+///
+/// Доли задаются вершинами левой доли `left` (правая --- все остальные,
+/// ребра внутри долей не используются) или, если `left` не задана,
+/// определяются по графу ([`bipartition`]).
+///
+/// # Errors
+/// Вернет [`GraphError`], если вершины из `left` нет, граф не двудольный,
+/// какой-то вершине меньшей доли не найти пары или веса переполнились.
+pub fn hungarian<G: GraphView>(
+    graph: &G,
+    left: Option<&[Index]>,
+    objective: Objective,
+) -> Result<Assignment<G::Weight>> {
+    let (mut left, mut right) = match left {
+        Some(left) => {
+            let left_set: HashSet<Index> = left.iter().copied().collect();
+            if let Some(&missing) = left.iter().find(|&&index| !graph.contains_node(index)) {
+                return Err(Box::new(GraphError::new(
+                    GraphKindError::NodeNotFound,
+                    &format!("вершина {missing}"),
+                )));
+            }
+            let right: Vec<Index> = graph
+                .node_indices()
+                .filter(|index| !left_set.contains(index))
+                .collect();
+            (left_set.into_iter().collect::<Vec<_>>(), right)
+        }
+        None => bipartition(graph).into_parts()?,
+    };
+    left.sort();
+    right.sort();
+
+    let matrix = CostMatrix::new(graph, left, right, objective)?;
+    let column_of_row = matrix.solve()?;
+
+    let mut total = <G::Weight as Weight>::ZERO;
+    let mut pairs = Vec::new();
+    for (row, &column) in column_of_row.iter().enumerate() {
+        let (weight, _) = matrix.cells[row][column].expect("назначено по ребру");
+        total = total.try_add(weight)?;
+        let (row, column) = (matrix.rows[row], matrix.columns[column]);
+        pairs.push(if matrix.rows_are_left {
+            (row, column, weight)
+        } else {
+            (column, row, weight)
+        });
+    }
+    pairs.sort();
+
+    let assigned: HashSet<usize> = column_of_row.into_iter().collect();
+    let unassigned = (0..matrix.columns.len())
+        .filter(|column| !assigned.contains(column))
+        .map(|column| matrix.columns[column])
+        .collect();
+
+    Ok(Assignment {
+        pairs,
+        total,
+        unassigned,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::test_support::{Rng, assert_kind, graph};
+
+    /// Лучшая сумма назначения строк `row..` в свободные столбцы перебором
+    /// (`None`, если какой-то строке не хватает столбца)
+    fn brute_force(
+        weights: &[Vec<Option<i64>>],
+        row: usize,
+        used: &mut [bool],
+        objective: Objective,
+    ) -> Option<i64> {
+        let Some(cells) = weights.get(row) else {
+            return Some(0);
+        };
+        let mut best: Option<i64> = None;
+        for (column, &weight) in cells.iter().enumerate() {
+            let Some(weight) = weight else {
+                continue;
+            };
+            if used[column] {
+                continue;
+            }
+            used[column] = true;
+            if let Some(rest) = brute_force(weights, row + 1, used, objective) {
+                let total = rest + weight;
+                best = Some(match (best, objective) {
+                    (None, _) => total,
+                    (Some(old), Objective::Minimize) => old.min(total),
+                    (Some(old), Objective::Maximize) => old.max(total),
+                });
+            }
+            used[column] = false;
+        }
+        best
+    }
+
+    #[test]
+    fn random_assignments_match_brute_force() {
+        let mut rng = Rng::new(0x1405_7b7e_f767_814f);
+        for _ in 0..300 {
+            // Левая доля 1..=a, правая a+1..=a+b
+            let (a, b) = (1 + rng.below(4) as u32, 1 + rng.below(4) as u32);
+            let mut edges = Vec::new();
+            for left in 1..=a {
+                for right in a + 1..=a + b {
+                    if rng.below(100) < 80 {
+                        edges.push((left, right, rng.below(21) as i64 - 5));
+                    }
+                    // Параллельное ребро
+                    if rng.below(100) < 15 {
+                        edges.push((right, left, rng.below(21) as i64 - 5));
+                    }
+                }
+            }
+            let graph = graph(a + b, false, &edges);
+            let left: Vec<Index> = (1..=a).map(Index).collect();
+
+            for objective in [Objective::Minimize, Objective::Maximize] {
+                // Строки --- меньшая доля
+                let (rows, columns) = if a <= b {
+                    (
+                        (1..=a).collect::<Vec<_>>(),
+                        (a + 1..=a + b).collect::<Vec<_>>(),
+                    )
+                } else {
+                    ((a + 1..=a + b).collect(), (1..=a).collect())
+                };
+                let weights: Vec<Vec<Option<i64>>> = rows
+                    .iter()
+                    .map(|&row| {
+                        columns
+                            .iter()
+                            .map(|&column| {
+                                let parallel = edges.iter().filter(|&&(x, y, _)| {
+                                    (x, y) == (row, column) || (y, x) == (row, column)
+                                });
+                                let weights = parallel.map(|&(_, _, weight)| weight);
+                                match objective {
+                                    Objective::Minimize => weights.min(),
+                                    Objective::Maximize => weights.max(),
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let expected = brute_force(&weights, 0, &mut vec![false; columns.len()], objective);
+
+                let found = hungarian(&graph, Some(&left), objective);
+                let Some(expected) = expected else {
+                    assert_kind(found, GraphKindError::EdgeNotFound);
+                    continue;
+                };
+                let assignment = found.unwrap();
+                assert_eq!(assignment.total(), expected, "ребра {edges:?}");
+                assert_eq!(assignment.pairs().len(), a.min(b) as usize);
+                assert_eq!(assignment.unassigned().len(), a.abs_diff(b) as usize);
+
+                let mut seen = HashSet::new();
+                let mut total = 0;
+                for &(left, right, weight) in assignment.pairs() {
+                    assert!(*left <= a && *right > a, "пара ({left}, {right})");
+                    assert!(seen.insert(left) && seen.insert(right));
+                    total += weight;
+                }
+                assert_eq!(total, assignment.total());
+            }
+        }
+    }
+
+    #[test]
+    fn parts_from_bipartition() {
+        // Путь 1 - 2 - 3 - 4: доли {1, 3} и {2, 4}
+        let path = graph(4, false, &[(1, 2, 4), (2, 3, 1), (3, 4, 4)]);
+        let assignment = hungarian(&path, None, Objective::Minimize).unwrap();
+        assert_eq!(
+            assignment.pairs(),
+            &[(Index(1), Index(2), 4), (Index(3), Index(4), 4)]
+        );
+        let assignment = hungarian(&path, None, Objective::Maximize).unwrap();
+        assert_eq!(assignment.total(), 8);
+
+        let triangle = graph(3, false, &[(1, 2, 1), (2, 3, 1), (1, 3, 1)]);
+        assert_kind(
+            hungarian(&triangle, None, Objective::Minimize),
+            GraphKindError::GraphMustBeBipartite,
+        );
+        assert_kind(
+            hungarian(&path, Some(&[Index(7)]), Objective::Minimize),
+            GraphKindError::NodeNotFound,
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Поиск кратчайшего пути A* с эвристикой.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt::Debug,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::graph::{
+    core::{Graph, GraphError, GraphKindError, Index},
+    view::GraphView,
+    weight::{Weight, ensure_non_negative},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Результат A*: путь (если найден) и сколько вершин было раскрыто.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<W> {
+    /// Длина найденного пути
+    pub distance: Option<W>,
+    /// Вершины пути от начала до цели включительно
+    pub path: Option<Vec<Index>>,
+    /// Число раскрытых (извлеченных из очереди) вершин
+    pub expanded: usize,
+}
+
+/// Поиск кратчайшего пути из `source` в `target` алгоритмом A*.
+///
+/// `heuristic(v)` --- оценка расстояния от `v` до `target`. Если она не
+/// превосходит настоящего расстояния, найденный путь кратчайший. С нулевой
+/// эвристикой (`|_| W::ZERO`) это алгоритм Дейкстры, что удобно для
+/// сравнения числа раскрытых вершин.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет, в графе есть ребро
+/// отрицательного веса или длина пути переполнилась.
+pub fn astar<G, H>(
+    graph: &G,
+    source: Index,
+    target: Index,
+    heuristic: H,
+) -> Result<SearchResult<G::Weight>>
+where
+    G: GraphView,
+    H: Fn(Index) -> G::Weight,
+{
+    for index in [source, target] {
+        if !graph.contains_node(index) {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::NodeNotFound,
+                &format!("вершина {index}"),
+            )));
+        }
+    }
+    ensure_non_negative(graph)?;
+
+    let mut dist: HashMap<Index, G::Weight> = HashMap::from([(source, G::Weight::ZERO)]);
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+    let mut expanded = 0;
+    // В куче (f, g, вершина), где f = g + h; Reverse --- чтобы первым
+    // доставать минимальное f
+    let mut heap = BinaryHeap::from([Reverse((heuristic(source), G::Weight::ZERO, source))]);
+
+    while let Some(Reverse((_, weight, from))) = heap.pop() {
+        // Устаревшая запись в куче
+        if weight > dist[&from] {
+            continue;
+        }
+        expanded += 1;
+
+        if from == target {
+            let mut path = vec![target];
+            let mut current = target;
+            while let Some(&prev) = parent.get(&current) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Ok(SearchResult {
+                distance: Some(weight),
+                path: Some(path),
+                expanded,
+            });
+        }
+
+        for (to, edge_weight) in graph.neighbors(from) {
+            let new_weight = weight.try_add(edge_weight)?;
+            if dist.get(&to).is_none_or(|&old| new_weight < old) {
+                dist.insert(to, new_weight);
+                parent.insert(to, from);
+                let estimate = new_weight.try_add(heuristic(to))?;
+                heap.push(Reverse((estimate, new_weight, to)));
+            }
+        }
+    }
+
+    Ok(SearchResult {
+        distance: None,
+        path: None,
+        expanded,
+    })
+}
+
+/// Значение вершины, в котором есть координаты на плоскости
+pub trait Coordinates {
+    /// Координаты `(x, y)`; [`None`], если их нет
+    fn coordinates(&self) -> Option<(f64, f64)>;
+}
+
+impl Coordinates for (f64, f64) {
+    fn coordinates(&self) -> Option<(f64, f64)> {
+        Some(*self)
+    }
+}
+
+impl Coordinates for [f64; 2] {
+    fn coordinates(&self) -> Option<(f64, f64)> {
+        Some((self[0], self[1]))
+    }
+}
+
+impl Coordinates for (i32, i32) {
+    fn coordinates(&self) -> Option<(f64, f64)> {
+        Some((f64::from(self.0), f64::from(self.1)))
+    }
+}
+
+/// Координаты из JSON: `{"x": 1, "y": 2}` или `[1, 2]`
+impl Coordinates for Value {
+    fn coordinates(&self) -> Option<(f64, f64)> {
+        match self {
+            Value::Object(map) => Some((map.get("x")?.as_f64()?, map.get("y")?.as_f64()?)),
+            Value::Array(items) if items.len() == 2 => {
+                Some((items[0].as_f64()?, items[1].as_f64()?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Эвристика по расстоянию между координатами вершин; для вершин без
+/// координат оценка нулевая
+fn coordinate_heuristic<T, W>(
+    graph: &Graph<T, W>,
+    target: Index,
+    metric: fn(f64, f64) -> f64,
+) -> impl Fn(Index) -> W + '_
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default + Coordinates,
+    W: Weight,
+{
+    let goal = graph
+        .get_node(&target)
+        .and_then(|node| node.value.coordinates());
+    move |index| {
+        let here = graph
+            .get_node(&index)
+            .and_then(|node| node.value.coordinates());
+        match (here, goal) {
+            (Some((x1, y1)), Some((x2, y2))) => W::from_f64(metric(x1 - x2, y1 - y2)),
+            _ => W::ZERO,
+        }
+    }
+}
+
+/// Евклидово расстояние до `target`. Допустима, если вес ребра не меньше
+/// расстояния между его концами.
+pub fn euclidean<T, W>(graph: &Graph<T, W>, target: Index) -> impl Fn(Index) -> W + '_
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default + Coordinates,
+    W: Weight,
+{
+    coordinate_heuristic(graph, target, f64::hypot)
+}
+
+/// Манхэттенское расстояние до `target`. Допустима на решетке, где ребра
+/// идут только по осям и вес ребра не меньше его длины.
+pub fn manhattan<T, W>(graph: &Graph<T, W>, target: Index) -> impl Fn(Index) -> W + '_
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default + Coordinates,
+    W: Weight,
+{
+    coordinate_heuristic(graph, target, |dx, dy| dx.abs() + dy.abs())
+}
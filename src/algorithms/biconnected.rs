@@ -0,0 +1,252 @@
+//! Мосты, точки сочленения и компоненты двусвязности неориентированного
+//! графа (алгоритм Тарьяна с low-link).
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Debug,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::components::sorted_nodes,
+    graph::{
+        core::{EdgeId, Graph, Index},
+        view::ensure_undirected,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Слабые места неориентированного графа.
+///
+/// Мост --- ребро, после удаления которого число компонент связности
+/// растет; точка сочленения --- такая же вершина. Компонента двусвязности
+/// (блок) --- максимальный подграф без точек сочленения; каждое ребро
+/// лежит ровно в одном блоке, а соседние блоки делят точку сочленения.
+/// Изолированная вершина --- отдельный блок.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Biconnectivity {
+    bridges: Vec<(Index, Index)>,
+    articulation_points: Vec<Index>,
+    components: Vec<Vec<Index>>,
+}
+
+impl Biconnectivity {
+    /// Мосты как пары концов `(меньший, больший)`, по возрастанию
+    pub fn bridges(&self) -> &[(Index, Index)] {
+        &self.bridges
+    }
+
+    /// Точки сочленения по возрастанию номера
+    pub fn articulation_points(&self) -> &[Index] {
+        &self.articulation_points
+    }
+
+    /// Вершины каждой компоненты двусвязности (отсортированы, компоненты
+    /// упорядочены по первой вершине)
+    pub fn components(&self) -> &[Vec<Index>] {
+        &self.components
+    }
+
+    /// Компоненты двусвязности как подграфы исходного графа. Подграф,
+    /// порожденный вершинами блока, содержит ровно ребра блока.
+    pub fn subgraphs<T, W>(&self, graph: &Graph<T, W>) -> Result<Vec<Graph<T, W>>>
+    where
+        T: Clone + Serialize + DeserializeOwned + Debug + Default,
+        W: Weight,
+    {
+        self.components
+            .iter()
+            .map(|members| graph.create_subgraph(members.clone(), false))
+            .collect()
+    }
+}
+
+/// Стек вызовов обхода: вершина, id ребра, по которому в нее пришли,
+/// ее ребра и сколько из них уже просмотрено
+type EdgeCallStack = Vec<(Index, EdgeId, Vec<(Index, EdgeId)>, usize)>;
+
+/// Состояние обхода в глубину
+#[derive(Default)]
+struct LowLink {
+    order: HashMap<Index, usize>,
+    low: HashMap<Index, usize>,
+    // Ребра текущего, еще не выделенного блока
+    edges: Vec<(Index, Index)>,
+    bridges: Vec<(Index, Index)>,
+    articulation_points: HashSet<Index>,
+    components: Vec<Vec<Index>>,
+}
+
+impl LowLink {
+    fn enter<T, W>(
+        &mut self,
+        graph: &Graph<T, W>,
+        index: Index,
+        via: EdgeId,
+        calls: &mut EdgeCallStack,
+    ) where
+        T: Clone + Serialize + DeserializeOwned + Debug + Default,
+        W: Weight,
+    {
+        let time = self.order.len();
+        self.order.insert(index, time);
+        self.low.insert(index, time);
+        let edges = graph
+            .get_adjacency(&index)
+            .into_iter()
+            .flatten()
+            // Петли на двусвязность не влияют
+            .filter(|edge| edge.node.number != index)
+            .map(|edge| (edge.node.number, edge.id))
+            .collect();
+        calls.push((index, via, edges, 0));
+    }
+
+    fn run<T, W>(&mut self, graph: &Graph<T, W>, root: Index)
+    where
+        T: Clone + Serialize + DeserializeOwned + Debug + Default,
+        W: Weight,
+    {
+        let mut calls = EdgeCallStack::new();
+        self.enter(graph, root, EdgeId::default(), &mut calls);
+        let mut root_children = 0;
+
+        while let Some((node, via, edges, next)) = calls.last_mut() {
+            let (node, via) = (*node, *via);
+            if let Some(&(to, id)) = edges.get(*next) {
+                *next += 1;
+                // Вторая половина ребра, по которому пришли; параллельное
+                // ему ребро id отличается и считается обратным
+                if id == via {
+                    continue;
+                }
+                match self.order.get(&to) {
+                    None => {
+                        self.edges.push((node, to));
+                        if node == root {
+                            root_children += 1;
+                        }
+                        self.enter(graph, to, id, &mut calls);
+                    }
+                    // Обратное ребро к предку; ребро к потомку уже
+                    // учтено с другой стороны
+                    Some(&time) if time < self.order[&node] => {
+                        self.edges.push((node, to));
+                        let low = self.low.get_mut(&node).expect("вершина посещена");
+                        *low = (*low).min(time);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            let Some(&(parent, _, _, _)) = calls.last() else {
+                break;
+            };
+            let low = self.low[&node];
+            let parent_low = self.low.get_mut(&parent).expect("вершина посещена");
+            *parent_low = (*parent_low).min(low);
+
+            let parent_order = self.order[&parent];
+            if low > parent_order {
+                self.bridges.push((parent.min(node), parent.max(node)));
+            }
+            if low >= parent_order {
+                // parent отделяет поддерево node: снимаем блок со стека
+                if parent != root {
+                    self.articulation_points.insert(parent);
+                }
+                self.pop_component(parent, node);
+            }
+        }
+
+        // Корень --- точка сочленения, только если у него несколько детей
+        if root_children >= 2 {
+            self.articulation_points.insert(root);
+        }
+        if root_children == 0 {
+            self.components.push(vec![root]);
+        }
+    }
+
+    fn pop_component(&mut self, parent: Index, child: Index) {
+        let mut members: HashSet<Index> = HashSet::new();
+        while let Some((from, to)) = self.edges.pop() {
+            members.insert(from);
+            members.insert(to);
+            if (from, to) == (parent, child) {
+                break;
+            }
+        }
+        let mut members: Vec<Index> = members.into_iter().collect();
+        members.sort();
+        self.components.push(members);
+    }
+}
+
+/// Мосты, точки сочленения и компоненты двусвязности за один обход в
+/// глубину (без рекурсии). Параллельные ребра мультиграфа мостами не
+/// являются.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный.
+pub fn biconnectivity<T, W>(graph: &Graph<T, W>) -> Result<Biconnectivity>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    ensure_undirected(
+        graph,
+        "мосты и точки сочленения ищутся в неориентированном графе",
+    )?;
+
+    let mut state = LowLink::default();
+    for root in sorted_nodes(graph) {
+        if !state.order.contains_key(&root) {
+            state.run(graph, root);
+        }
+    }
+
+    let mut articulation_points: Vec<Index> = state.articulation_points.into_iter().collect();
+    articulation_points.sort();
+    state.bridges.sort();
+    state.components.sort();
+
+    Ok(Biconnectivity {
+        bridges: state.bridges,
+        articulation_points,
+        components: state.components,
+    })
+}
+
+/// Мосты неориентированного графа
+pub fn bridges<T, W>(graph: &Graph<T, W>) -> Result<Vec<(Index, Index)>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    Ok(biconnectivity(graph)?.bridges)
+}
+
+/// Точки сочленения неориентированного графа
+pub fn articulation_points<T, W>(graph: &Graph<T, W>) -> Result<Vec<Index>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    Ok(biconnectivity(graph)?.articulation_points)
+}
+
+/// Компоненты двусвязности неориентированного графа как подграфы
+pub fn biconnected_components<T, W>(graph: &Graph<T, W>) -> Result<Vec<Graph<T, W>>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    biconnectivity(graph)?.subgraphs(graph)
+}
@@ -0,0 +1,136 @@
+//! Проверка двудольности: разбиение на доли или нечетный цикл,
+//! доказывающий, что разбиения нет.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    algorithms::components::{sorted_nodes, successors},
+    graph::{
+        core::{GraphError, GraphKindError, Index},
+        view::GraphView,
+    },
+};
+
+/// Результат проверки двудольности: две доли или нечетный цикл.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bipartition {
+    /// Доли по возрастанию номера; каждое ребро соединяет вершины разных
+    /// долей. Наименьшая вершина каждой компоненты лежит в первой доле
+    Parts(Vec<Index>, Vec<Index>),
+    /// Вершины цикла нечетной длины по ходу ребер, первая не повторяется
+    /// в конце. Петля --- цикл из одной вершины
+    OddCycle(Vec<Index>),
+}
+
+impl Bipartition {
+    pub fn is_bipartite(&self) -> bool {
+        matches!(self, Bipartition::Parts(..))
+    }
+
+    /// Доли, если граф двудольный; иначе ошибка
+    /// [`GraphKindError::GraphMustBeBipartite`] с вершинами цикла
+    pub fn into_parts(self) -> std::result::Result<(Vec<Index>, Vec<Index>), GraphError> {
+        match self {
+            Bipartition::Parts(left, right) => Ok((left, right)),
+            Bipartition::OddCycle(cycle) => Err(GraphError::new(
+                GraphKindError::GraphMustBeBipartite,
+                &format!(
+                    "нечетный цикл {}",
+                    cycle
+                        .iter()
+                        .chain(cycle.first())
+                        .map(|index| index.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" - ")
+                ),
+            )),
+        }
+    }
+}
+
+/// Путь по дереву обхода от вершины до корня
+fn path_to_root(parent: &HashMap<Index, Index>, mut index: Index) -> Vec<Index> {
+    let mut path = vec![index];
+    while let Some(&next) = parent.get(&index) {
+        path.push(next);
+        index = next;
+    }
+    path
+}
+
+/// Нечетный цикл из ребра `from - to` между вершинами одного цвета: пути
+/// от них до общего предка в дереве обхода в ширину
+fn odd_cycle(parent: &HashMap<Index, Index>, from: Index, to: Index) -> Vec<Index> {
+    if from == to {
+        return vec![from];
+    }
+    // Концы ребра одного цвета лежат на одной глубине, поэтому пути до
+    // корня одной длины
+    let mut up = path_to_root(parent, from);
+    let mut down = path_to_root(parent, to);
+    // Снимаем общую часть путей, оставляя ближайшего общего предка
+    while up.len() >= 2 && down.len() >= 2 && up[up.len() - 2] == down[down.len() - 2] {
+        up.pop();
+        down.pop();
+    }
+    down.pop();
+    down.reverse();
+    up.extend(down);
+    up
+}
+
+fn color<G: GraphView>(graph: &G) -> Bipartition {
+    let mut side: HashMap<Index, bool> = HashMap::new();
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+
+    for root in sorted_nodes(graph) {
+        if side.contains_key(&root) {
+            continue;
+        }
+        side.insert(root, false);
+        let mut queue = VecDeque::from([root]);
+        while let Some(from) = queue.pop_front() {
+            let from_side = side[&from];
+            for to in successors(graph, from) {
+                match side.get(&to) {
+                    None => {
+                        side.insert(to, !from_side);
+                        parent.insert(to, from);
+                        queue.push_back(to);
+                    }
+                    Some(&to_side) if to_side == from_side => {
+                        return Bipartition::OddCycle(odd_cycle(&parent, from, to));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    let (mut left, mut right): (Vec<Index>, Vec<Index>) = (Vec::new(), Vec::new());
+    for (index, is_right) in side {
+        if is_right {
+            right.push(index);
+        } else {
+            left.push(index);
+        }
+    }
+    left.sort();
+    right.sort();
+    Bipartition::Parts(left, right)
+}
+
+/// Разбивает вершины графа на две доли обходом в ширину. Направление
+/// ребер орграфа не учитывается.
+pub fn bipartition<G: GraphView>(graph: &G) -> Bipartition {
+    if graph.is_directed() {
+        color(&graph.undirected())
+    } else {
+        color(graph)
+    }
+}
+
+/// Является ли граф двудольным (направление ребер не учитывается)
+pub fn is_bipartite<G: GraphView>(graph: &G) -> bool {
+    bipartition(graph).is_bipartite()
+}
@@ -0,0 +1,1193 @@
+//! Паросочетания в произвольном неориентированном графе: алгоритм
+//! Эдмондса со сжатием цветков для наибольшего паросочетания и его
+//! взвешенный вариант для паросочетания максимального веса.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+};
+
+use crate::{
+    algorithms::{components::sorted_nodes, matching::Matching},
+    graph::{
+        core::{GraphError, GraphKindError, Index},
+        view::{GraphView, ensure_undirected},
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Граф на плотных номерах без петель и параллельных ребер
+struct DenseGraph {
+    indices: Vec<Index>,
+    positions: HashMap<Index, usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl DenseGraph {
+    fn new<G: GraphView>(graph: &G) -> Result<Self> {
+        ensure_undirected(graph, "паросочетание ищется в неориентированном графе")?;
+        let indices = sorted_nodes(graph);
+        let positions: HashMap<Index, usize> = indices
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+        let adjacency = indices
+            .iter()
+            .enumerate()
+            .map(|(from, &index)| {
+                let mut targets: Vec<usize> = graph
+                    .neighbors(index)
+                    .filter_map(|(to, _)| positions.get(&to).copied())
+                    .filter(|&to| to != from)
+                    .collect();
+                targets.sort();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        Ok(Self {
+            indices,
+            positions,
+            adjacency,
+        })
+    }
+}
+
+/// Состояние алгоритма Эдмондса: поиск увеличивающего пути обходом в
+/// ширину, цветки сжимаются через общую базу `base`
+struct Edmonds<'a> {
+    graph: &'a DenseGraph,
+    mate: Vec<Option<usize>>,
+    // Откуда пришли в нечетную вершину дерева
+    parent: Vec<Option<usize>>,
+    base: Vec<usize>,
+    // Четные вершины дерева (уже в очереди)
+    used: Vec<bool>,
+    blossom: Vec<bool>,
+}
+
+impl<'a> Edmonds<'a> {
+    fn new(graph: &'a DenseGraph) -> Self {
+        let n = graph.indices.len();
+        Self {
+            graph,
+            mate: vec![None; n],
+            parent: vec![None; n],
+            base: (0..n).collect(),
+            used: vec![false; n],
+            blossom: vec![false; n],
+        }
+    }
+
+    /// Ближайший общий предок баз `a` и `b` в дереве чередующихся путей
+    fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.mate.len()];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            let Some(mate) = self.mate[a] else {
+                break;
+            };
+            a = self.parent[mate].expect("нечетная вершина дерева");
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b;
+            }
+            let mate = self.mate[b].expect("база ниже корня сопоставлена");
+            b = self.parent[mate].expect("нечетная вершина дерева");
+        }
+    }
+
+    /// Отмечает цветок на пути от `v` до базы `base` и перенаправляет
+    /// родителей, чтобы путь можно было пройти в обратную сторону
+    fn mark_path(&mut self, mut v: usize, base: usize, mut child: usize) {
+        while self.base[v] != base {
+            let mate = self.mate[v].expect("вершина цветка сопоставлена");
+            self.blossom[self.base[v]] = true;
+            self.blossom[self.base[mate]] = true;
+            self.parent[v] = Some(child);
+            child = mate;
+            v = self.parent[mate].expect("нечетная вершина дерева");
+        }
+    }
+
+    /// Ищет увеличивающий путь из свободной вершины `root` и возвращает его
+    /// свободный конец
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.mate.len();
+        self.used.fill(false);
+        self.parent.fill(None);
+        for (vertex, base) in self.base.iter_mut().enumerate() {
+            *base = vertex;
+        }
+
+        self.used[root] = true;
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            for &to in &self.graph.adjacency[v] {
+                if self.base[v] == self.base[to] || self.mate[v] == Some(to) {
+                    continue;
+                }
+                let to_is_even =
+                    to == root || self.mate[to].is_some_and(|mate| self.parent[mate].is_some());
+                if to_is_even {
+                    // Ребро между четными вершинами замыкает нечетный цикл:
+                    // сжимаем его в цветок
+                    let base = self.lowest_common_ancestor(v, to);
+                    self.blossom.fill(false);
+                    self.mark_path(v, base, to);
+                    self.mark_path(to, base, v);
+                    for vertex in 0..n {
+                        if self.blossom[self.base[vertex]] {
+                            self.base[vertex] = base;
+                            if !self.used[vertex] {
+                                self.used[vertex] = true;
+                                queue.push_back(vertex);
+                            }
+                        }
+                    }
+                } else if self.parent[to].is_none() {
+                    self.parent[to] = Some(v);
+                    let Some(mate) = self.mate[to] else {
+                        return Some(to);
+                    };
+                    self.used[mate] = true;
+                    queue.push_back(mate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Увеличивающий путь от корня до свободного конца `end`
+    fn path(&self, end: usize) -> Vec<usize> {
+        let mut path = vec![end];
+        let mut current = end;
+        loop {
+            let previous = self.parent[current].expect("вершина пути");
+            path.push(previous);
+            let Some(next) = self.mate[previous] else {
+                break;
+            };
+            path.push(next);
+            current = next;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Чередует паросочетание вдоль пути
+    fn augment(&mut self, path: &[usize]) {
+        for pair in path.chunks(2) {
+            self.mate[pair[0]] = Some(pair[1]);
+            self.mate[pair[1]] = Some(pair[0]);
+        }
+    }
+
+    fn matching(&self) -> Matching {
+        Matching::new(
+            self.mate
+                .iter()
+                .enumerate()
+                .filter_map(|(v, mate)| {
+                    mate.filter(|&w| v < w)
+                        .map(|w| (self.graph.indices[v], self.graph.indices[w]))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Наибольшее паросочетание неориентированного графа алгоритмом Эдмондса
+/// за `O(V³)`. Пары --- `(меньшая вершина, большая)`; петли не учитываются.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный.
+pub fn maximum_matching<G: GraphView>(graph: &G) -> Result<Matching> {
+    let graph = DenseGraph::new(graph)?;
+    let mut state = Edmonds::new(&graph);
+    for root in 0..graph.indices.len() {
+        if state.mate[root].is_none()
+            && let Some(end) = state.find_path(root)
+        {
+            let path = state.path(end);
+            state.augment(&path);
+        }
+    }
+    Ok(state.matching())
+}
+
+/// Увеличивающий путь относительно паросочетания `matching`: путь между
+/// двумя свободными вершинами, ребра которого попеременно не лежат и лежат
+/// в паросочетании. По теореме Бержа паросочетание наибольшее, только
+/// если такого пути нет.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный, пара паросочетания
+/// не является ребром графа или вершина входит в две пары.
+pub fn augmenting_path<G: GraphView>(graph: &G, matching: &Matching) -> Result<Option<Vec<Index>>> {
+    let graph = DenseGraph::new(graph)?;
+    let mut state = Edmonds::new(&graph);
+    for &(a, b) in matching.pairs() {
+        let edge = graph.positions.get(&a).zip(graph.positions.get(&b));
+        let Some((&a_pos, &b_pos)) = edge.filter(|(a, b)| graph.adjacency[**a].contains(b)) else {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::EdgeNotFound,
+                &format!("пара ({a}, {b}) не является ребром графа"),
+            )));
+        };
+        for (vertex, index, other) in [(a_pos, a, b_pos), (b_pos, b, a_pos)] {
+            if state.mate[vertex].is_some() {
+                return Err(Box::new(GraphError::new(
+                    GraphKindError::NodeAlreadyExist,
+                    &format!("вершина {index} входит в две пары"),
+                )));
+            }
+            state.mate[vertex] = Some(other);
+        }
+    }
+
+    for root in 0..graph.indices.len() {
+        if state.mate[root].is_none()
+            && let Some(end) = state.find_path(root)
+        {
+            let path = state.path(end);
+            return Ok(Some(
+                path.into_iter()
+                    .map(|vertex| graph.indices[vertex])
+                    .collect(),
+            ));
+        }
+    }
+    Ok(None)
+}
+
+/// Является ли `matching` наибольшим паросочетанием графа (проверка
+/// отсутствия увеличивающего пути).
+///
+/// # Errors
+/// Те же, что у [`augmenting_path`].
+pub fn is_maximum_matching<G: GraphView>(graph: &G, matching: &Matching) -> Result<bool> {
+    Ok(augmenting_path(graph, matching)?.is_none())
+}
+
+/// Метки вершин и цветков во взвешенном алгоритме
+const FREE: u8 = 0;
+const OUTER: u8 = 1;
+const INNER: u8 = 2;
+// Временная отметка при поиске общего предка
+const VISITED: u8 = 4;
+
+/// Элемент списка по индексу, отсчитываемому с конца при отрицательном
+/// значении
+fn cyclic<T: Copy>(list: &[T], index: isize) -> T {
+    list[index.rem_euclid(list.len() as isize) as usize]
+}
+
+/// Шаг изменения двойственных переменных: конец этапа, новое допустимое
+/// ребро или раскрытие внутреннего цветка
+enum Step {
+    Finish,
+    Edge(usize),
+    Expand(usize),
+}
+
+/// Взвешенный алгоритм Эдмондса (прямо-двойственный, `O(V³)`).
+///
+/// Ребро `k` с концами `i`, `j` имеет «концы-дуги» `2k` (вершина `i`) и
+/// `2k + 1` (вершина `j`); `p ^ 1` --- противоположный конец. Вершины ---
+/// номера `0..n`, цветки --- `n..2n`. Двойственные переменные вершин
+/// начинаются с наибольшего веса и не опускаются ниже нуля, поэтому
+/// хватает вычитания без знака; при целых весах запас ребра между двумя
+/// внешними цветками четный и делится пополам без остатка.
+struct WeightedBlossom<W> {
+    n: usize,
+    edges: Vec<(usize, usize, W)>,
+    endpoint: Vec<usize>,
+    // Для каждой вершины --- дальние концы ее ребер
+    neighbor_ends: Vec<Vec<usize>>,
+    // Дальний конец ребра паросочетания
+    mate: Vec<Option<usize>>,
+    label: Vec<u8>,
+    // Конец, через который вершина или цветок получили метку
+    label_end: Vec<Option<usize>>,
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<Option<usize>>,
+    // Подцветки по циклу, начиная с содержащего базу
+    blossom_children: Vec<Vec<usize>>,
+    blossom_base: Vec<Option<usize>>,
+    // blossom_ends[b][i] --- конец ребра между подцветками i и i + 1
+    blossom_ends: Vec<Vec<usize>>,
+    // Ребро с наименьшим запасом до другого внешнего цветка
+    best_edge: Vec<Option<usize>>,
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused_blossoms: Vec<usize>,
+    dual: Vec<W>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<W: Weight> WeightedBlossom<W> {
+    fn new(n: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let max_weight = edges
+            .iter()
+            .map(|&(_, _, weight)| weight)
+            .max()
+            .unwrap_or(W::ZERO)
+            .max(W::ZERO);
+        let mut neighbor_ends = vec![Vec::new(); n];
+        let mut endpoint = Vec::with_capacity(2 * edges.len());
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            endpoint.push(i);
+            endpoint.push(j);
+            neighbor_ends[i].push(2 * k + 1);
+            neighbor_ends[j].push(2 * k);
+        }
+
+        Self {
+            n,
+            endpoint,
+            neighbor_ends,
+            mate: vec![None; n],
+            label: vec![FREE; 2 * n],
+            label_end: vec![None; 2 * n],
+            in_blossom: (0..n).collect(),
+            blossom_parent: vec![None; 2 * n],
+            blossom_children: vec![Vec::new(); 2 * n],
+            blossom_base: (0..n).map(Some).chain((0..n).map(|_| None)).collect(),
+            blossom_ends: vec![Vec::new(); 2 * n],
+            best_edge: vec![None; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused_blossoms: (n..2 * n).collect(),
+            dual: (0..2 * n)
+                .map(|b| if b < n { max_weight } else { W::ZERO })
+                .collect(),
+            allowed: vec![false; edges.len()],
+            queue: Vec::new(),
+            edges,
+        }
+    }
+
+    /// Запас ребра `k` в двойственных ограничениях: `u(i) + u(j) - 2w`
+    fn slack(&self, k: usize) -> Result<W> {
+        let (i, j, weight) = self.edges[k];
+        let duals = self.dual[i].try_add(self.dual[j])?;
+        duals.checked_sub(weight.try_add(weight)?).ok_or_else(|| {
+            Box::new(GraphError::new(
+                GraphKindError::WeightOverflow,
+                &format!("запас ребра {i} - {j}"),
+            )) as Box<dyn Error>
+        })
+    }
+
+    fn sub_dual(&mut self, b: usize, delta: W) -> Result<()> {
+        self.dual[b] = self.dual[b].checked_sub(delta).ok_or_else(|| {
+            GraphError::new(
+                GraphKindError::WeightOverflow,
+                &format!("{} - {delta}", self.dual[b]),
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Вершины, лежащие в цветке `b` на любой глубине
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(current) = stack.pop() {
+            if current < self.n {
+                leaves.push(current);
+            } else {
+                stack.extend(self.blossom_children[current].iter().rev());
+            }
+        }
+        leaves
+    }
+
+    /// Помечает вершину `w` и ее верхний цветок меткой `label`, полученной
+    /// через конец `end`. Внутренний цветок сразу делает внешним цветок,
+    /// сопоставленный с его базой
+    fn assign_label(&mut self, w: usize, label: u8, end: Option<usize>) {
+        let b = self.in_blossom[w];
+        self.label[w] = label;
+        self.label[b] = label;
+        self.label_end[w] = end;
+        self.label_end[b] = end;
+        self.best_edge[w] = None;
+        self.best_edge[b] = None;
+        if label == OUTER {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let base = self.blossom_base[b].expect("цветок используется");
+            let mate = self.mate[base].expect("база внутреннего цветка сопоставлена");
+            self.assign_label(self.endpoint[mate], OUTER, Some(mate ^ 1));
+        }
+    }
+
+    /// Идет от `v` и `w` к корням деревьев. Возвращает базу нового цветка,
+    /// если пути сходятся, или [`None`], если найден увеличивающий путь
+    fn scan_blossom(&mut self, v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let (mut v, mut w) = (Some(v), Some(w));
+        while let Some(current) = v {
+            let b = self.in_blossom[current];
+            if self.label[b] & VISITED != 0 {
+                base = self.blossom_base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = OUTER | VISITED;
+            v = self.label_end[b].map(|end| {
+                let inner = self.in_blossom[self.endpoint[end]];
+                self.endpoint[self.label_end[inner].expect("внутренний цветок помечен")]
+            });
+            if w.is_some() {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = OUTER;
+        }
+        base
+    }
+
+    /// Сжимает цикл, замкнутый ребром `k`, в новый внешний цветок с базой
+    /// `base`
+    fn add_blossom(&mut self, base: usize, k: usize) -> Result<()> {
+        let (v, w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self.unused_blossoms.pop().expect("цветков не больше n");
+        self.blossom_base[b] = Some(base);
+        self.blossom_parent[b] = None;
+        self.blossom_parent[bb] = Some(b);
+
+        let mut children = Vec::new();
+        let mut ends = Vec::new();
+        while bv != bb {
+            self.blossom_parent[bv] = Some(b);
+            children.push(bv);
+            let end = self.label_end[bv].expect("цветок на пути помечен");
+            ends.push(end);
+            bv = self.in_blossom[self.endpoint[end]];
+        }
+        children.push(bb);
+        children.reverse();
+        ends.reverse();
+        ends.push(2 * k);
+        while bw != bb {
+            self.blossom_parent[bw] = Some(b);
+            children.push(bw);
+            let end = self.label_end[bw].expect("цветок на пути помечен");
+            ends.push(end ^ 1);
+            bw = self.in_blossom[self.endpoint[end]];
+        }
+
+        self.label[b] = OUTER;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = W::ZERO;
+        self.blossom_children[b] = children.clone();
+        self.blossom_ends[b] = ends;
+        for leaf in self.leaves(b) {
+            if self.label[self.in_blossom[leaf]] == INNER {
+                // Бывшие внутренние вершины теперь внешние
+                self.queue.push(leaf);
+            }
+            self.in_blossom[leaf] = b;
+        }
+
+        // Лучшие ребра к соседним внешним цветкам
+        let mut best_to: Vec<Option<(W, usize)>> = vec![None; 2 * self.n];
+        for &child in &children {
+            let candidates: Vec<usize> = match self.blossom_best_edges[child].take() {
+                Some(list) => list,
+                None => self
+                    .leaves(child)
+                    .into_iter()
+                    .flat_map(|leaf| self.neighbor_ends[leaf].iter().map(|end| end / 2))
+                    .collect(),
+            };
+            for edge in candidates {
+                let (i, j, _) = self.edges[edge];
+                let j = if self.in_blossom[j] == b { i } else { j };
+                let bj = self.in_blossom[j];
+                if bj != b && self.label[bj] == OUTER {
+                    let slack = self.slack(edge)?;
+                    if best_to[bj].is_none_or(|(best, _)| slack < best) {
+                        best_to[bj] = Some((slack, edge));
+                    }
+                }
+            }
+            self.best_edge[child] = None;
+        }
+        let best: Vec<(W, usize)> = best_to.into_iter().flatten().collect();
+        self.best_edge[b] = best
+            .iter()
+            .fold(None, |found: Option<(W, usize)>, &(slack, edge)| {
+                if found.is_none_or(|(old, _)| slack < old) {
+                    Some((slack, edge))
+                } else {
+                    found
+                }
+            })
+            .map(|(_, edge)| edge);
+        self.blossom_best_edges[b] = Some(best.into_iter().map(|(_, edge)| edge).collect());
+        Ok(())
+    }
+
+    /// Раскрывает цветок `b`. В конце этапа раскрываются цветки с нулевой
+    /// двойственной переменной; посреди этапа --- внутренний цветок, и
+    /// метки расставляются его подцветкам
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        let children = self.blossom_children[b].clone();
+        let ends = self.blossom_ends[b].clone();
+        for &child in &children {
+            self.blossom_parent[child] = None;
+            if child < self.n {
+                self.in_blossom[child] = child;
+            } else if end_stage && self.dual[child] == W::ZERO {
+                self.expand_blossom(child, end_stage);
+            } else {
+                for leaf in self.leaves(child) {
+                    self.in_blossom[leaf] = child;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == INNER {
+            let entry_end = self.label_end[b].expect("внутренний цветок помечен");
+            let entry_child = self.in_blossom[self.endpoint[entry_end ^ 1]];
+            let len = children.len() as isize;
+            let mut j = children
+                .iter()
+                .position(|&child| child == entry_child)
+                .expect("подцветок цветка") as isize;
+            // Идем от входа к базе по четной стороне цикла
+            let (step, trick) = if j & 1 == 1 {
+                j -= len;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+
+            let mut end = entry_end;
+            while j != 0 {
+                self.label[self.endpoint[end ^ 1]] = FREE;
+                let inner = cyclic(&ends, j - trick as isize) ^ trick ^ 1;
+                self.label[self.endpoint[inner]] = FREE;
+                self.assign_label(self.endpoint[end ^ 1], INNER, Some(end));
+                self.allowed[cyclic(&ends, j - trick as isize) / 2] = true;
+                j += step;
+                end = cyclic(&ends, j - trick as isize) ^ trick;
+                self.allowed[end / 2] = true;
+                j += step;
+            }
+
+            // Подцветок с базой становится внутренним
+            let base_child = cyclic(&children, j);
+            self.label[self.endpoint[end ^ 1]] = INNER;
+            self.label[base_child] = INNER;
+            self.label_end[self.endpoint[end ^ 1]] = Some(end);
+            self.label_end[base_child] = Some(end);
+            self.best_edge[base_child] = None;
+            j += step;
+
+            // Остальные подцветки могли быть достигнуты отдельно
+            while cyclic(&children, j) != entry_child {
+                let child = cyclic(&children, j);
+                j += step;
+                if self.label[child] == OUTER {
+                    continue;
+                }
+                if let Some(leaf) = self
+                    .leaves(child)
+                    .into_iter()
+                    .find(|&leaf| self.label[leaf] != FREE)
+                {
+                    self.label[leaf] = FREE;
+                    let base = self.blossom_base[child].expect("цветок используется");
+                    let mate = self.mate[base].expect("база сопоставлена");
+                    self.label[self.endpoint[mate]] = FREE;
+                    self.assign_label(leaf, INNER, self.label_end[leaf]);
+                }
+            }
+        }
+
+        self.label[b] = FREE;
+        self.label_end[b] = None;
+        self.blossom_children[b].clear();
+        self.blossom_ends[b].clear();
+        self.blossom_base[b] = None;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = None;
+        self.unused_blossoms.push(b);
+    }
+
+    /// Чередует паросочетание внутри цветка `b` так, чтобы его базой стала
+    /// вершина `v`
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossom_parent[t] != Some(b) {
+            t = self.blossom_parent[t].expect("вершина лежит в цветке");
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+
+        let children = self.blossom_children[b].clone();
+        let ends = self.blossom_ends[b].clone();
+        let i = children
+            .iter()
+            .position(|&child| child == t)
+            .expect("подцветок цветка");
+        let mut j = i as isize;
+        let (step, trick) = if i & 1 == 1 {
+            j -= children.len() as isize;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+
+        while j != 0 {
+            j += step;
+            let child = cyclic(&children, j);
+            let end = cyclic(&ends, j - trick as isize) ^ trick;
+            if child >= self.n {
+                self.augment_blossom(child, self.endpoint[end]);
+            }
+            j += step;
+            let child = cyclic(&children, j);
+            if child >= self.n {
+                self.augment_blossom(child, self.endpoint[end ^ 1]);
+            }
+            self.mate[self.endpoint[end]] = Some(end ^ 1);
+            self.mate[self.endpoint[end ^ 1]] = Some(end);
+        }
+
+        self.blossom_children[b].rotate_left(i);
+        self.blossom_ends[b].rotate_left(i);
+        self.blossom_base[b] = self.blossom_base[self.blossom_children[b][0]];
+    }
+
+    /// Чередует паросочетание вдоль увеличивающего пути через ребро `k`
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut end) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = Some(end);
+                let Some(label_end) = self.label_end[bs] else {
+                    // Дошли до корня дерева
+                    break;
+                };
+                let bt = self.in_blossom[self.endpoint[label_end]];
+                let inner_end = self.label_end[bt].expect("внутренний цветок помечен");
+                s = self.endpoint[inner_end];
+                let j = self.endpoint[inner_end ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = Some(inner_end);
+                end = inner_end ^ 1;
+            }
+        }
+    }
+
+    /// Просматривает ребра внешних вершин из очереди. Возвращает `true`,
+    /// если паросочетание увеличено
+    fn scan(&mut self) -> Result<bool> {
+        while let Some(v) = self.queue.pop() {
+            for end in self.neighbor_ends[v].clone() {
+                let k = end / 2;
+                let w = self.endpoint[end];
+                if self.in_blossom[v] == self.in_blossom[w] {
+                    continue;
+                }
+                let mut slack = None;
+                if !self.allowed[k] {
+                    let found = self.slack(k)?;
+                    slack = Some(found);
+                    if found <= W::ZERO {
+                        self.allowed[k] = true;
+                    }
+                }
+
+                let bw = self.in_blossom[w];
+                if self.allowed[k] {
+                    if self.label[bw] == FREE {
+                        self.assign_label(w, INNER, Some(end ^ 1));
+                    } else if self.label[bw] == OUTER {
+                        match self.scan_blossom(v, w) {
+                            Some(base) => self.add_blossom(base, k)?,
+                            None => {
+                                self.augment_matching(k);
+                                return Ok(true);
+                            }
+                        }
+                    } else if self.label[w] == FREE {
+                        // Вершина внутреннего цветка достижима из внешней
+                        self.label[w] = INNER;
+                        self.label_end[w] = Some(end ^ 1);
+                    }
+                } else if let Some(slack) = slack {
+                    let target = if self.label[bw] == OUTER {
+                        self.in_blossom[v]
+                    } else if self.label[w] == FREE {
+                        w
+                    } else {
+                        continue;
+                    };
+                    let better = match self.best_edge[target] {
+                        None => true,
+                        Some(best) => slack < self.slack(best)?,
+                    };
+                    if better {
+                        self.best_edge[target] = Some(k);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Один этап: растит деревья и меняет двойственные переменные, пока не
+    /// найдется увеличивающий путь. Возвращает `false`, если паросочетание
+    /// больше не увеличить
+    fn stage(&mut self) -> Result<bool> {
+        self.label.fill(FREE);
+        self.best_edge.fill(None);
+        for best in &mut self.blossom_best_edges[self.n..] {
+            *best = None;
+        }
+        self.allowed.fill(false);
+        self.queue.clear();
+        for v in 0..self.n {
+            if self.mate[v].is_none() && self.label[self.in_blossom[v]] == FREE {
+                self.assign_label(v, OUTER, None);
+            }
+        }
+
+        loop {
+            if self.scan()? {
+                return Ok(true);
+            }
+
+            // Наименьшее изменение двойственных переменных, после которого
+            // появится новое допустимое ребро, обнулится переменная вершины
+            // или внутреннего цветка
+            let mut delta = self.dual[..self.n].iter().copied().min().unwrap_or(W::ZERO);
+            let mut step = Step::Finish;
+            for v in 0..self.n {
+                if self.label[self.in_blossom[v]] == FREE
+                    && let Some(edge) = self.best_edge[v]
+                {
+                    let slack = self.slack(edge)?;
+                    if slack < delta {
+                        delta = slack;
+                        step = Step::Edge(edge);
+                    }
+                }
+            }
+            for b in 0..2 * self.n {
+                if self.blossom_parent[b].is_none()
+                    && self.label[b] == OUTER
+                    && let Some(edge) = self.best_edge[b]
+                {
+                    let half = self.slack(edge)?.half();
+                    if half < delta {
+                        delta = half;
+                        step = Step::Edge(edge);
+                    }
+                }
+            }
+            for b in self.n..2 * self.n {
+                if self.blossom_base[b].is_some()
+                    && self.blossom_parent[b].is_none()
+                    && self.label[b] == INNER
+                    && self.dual[b] < delta
+                {
+                    delta = self.dual[b];
+                    step = Step::Expand(b);
+                }
+            }
+
+            for v in 0..self.n {
+                match self.label[self.in_blossom[v]] {
+                    OUTER => self.sub_dual(v, delta)?,
+                    INNER => self.dual[v] = self.dual[v].try_add(delta)?,
+                    _ => {}
+                }
+            }
+            for b in self.n..2 * self.n {
+                if self.blossom_base[b].is_some() && self.blossom_parent[b].is_none() {
+                    match self.label[b] {
+                        OUTER => self.dual[b] = self.dual[b].try_add(delta)?,
+                        INNER => self.sub_dual(b, delta)?,
+                        _ => {}
+                    }
+                }
+            }
+
+            match step {
+                // Двойственные переменные свободных вершин обнулились:
+                // паросочетание максимально
+                Step::Finish => return Ok(false),
+                Step::Edge(edge) => {
+                    self.allowed[edge] = true;
+                    let (i, j, _) = self.edges[edge];
+                    let outer = if self.label[self.in_blossom[i]] == FREE {
+                        j
+                    } else {
+                        i
+                    };
+                    self.queue.push(outer);
+                }
+                Step::Expand(b) => self.expand_blossom(b, false),
+            }
+        }
+    }
+
+    fn run(&mut self) -> Result<()> {
+        for _ in 0..self.n {
+            if !self.stage()? {
+                break;
+            }
+            // Внешние цветки с нулевой переменной больше не нужны
+            for b in self.n..2 * self.n {
+                if self.blossom_parent[b].is_none()
+                    && self.blossom_base[b].is_some()
+                    && self.label[b] == OUTER
+                    && self.dual[b] == W::ZERO
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Паросочетание максимального суммарного веса в неориентированном графе
+/// (взвешенный алгоритм Эдмондса, `O(V³)`). Ребра неположительного веса
+/// в паросочетание не попадают; из параллельных ребер берется самое
+/// тяжелое. Возвращает паросочетание (пары `(меньшая вершина, большая)`)
+/// и его вес.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный или веса
+/// переполнились.
+pub fn maximum_weight_matching<G: GraphView>(graph: &G) -> Result<(Matching, G::Weight)> {
+    ensure_undirected(graph, "паросочетание ищется в неориентированном графе")?;
+    let indices = sorted_nodes(graph);
+    let positions: HashMap<Index, usize> = indices
+        .iter()
+        .enumerate()
+        .map(|(pos, &index)| (index, pos))
+        .collect();
+
+    let mut heaviest: HashMap<(usize, usize), G::Weight> = HashMap::new();
+    for (from, &index) in indices.iter().enumerate() {
+        for (to, weight) in graph.neighbors(index) {
+            let Some(&to) = positions.get(&to) else {
+                continue;
+            };
+            if from < to {
+                let entry = heaviest.entry((from, to)).or_insert(weight);
+                *entry = (*entry).max(weight);
+            }
+        }
+    }
+    let mut edges: Vec<(usize, usize, G::Weight)> = heaviest
+        .into_iter()
+        .map(|((i, j), weight)| (i, j, weight))
+        .collect();
+    edges.sort();
+
+    let mut state = WeightedBlossom::new(indices.len(), edges);
+    state.run()?;
+
+    let mut pairs = Vec::new();
+    let mut total = <G::Weight as Weight>::ZERO;
+    let mut matched: HashSet<usize> = HashSet::new();
+    for v in 0..indices.len() {
+        let Some(end) = state.mate[v] else {
+            continue;
+        };
+        let edge = end / 2;
+        if matched.insert(edge) {
+            let (i, j, weight) = state.edges[edge];
+            total = total.try_add(weight)?;
+            pairs.push((indices[i], indices[j]));
+        }
+    }
+    Ok((Matching::new(pairs), total))
+}
+
+/// Совершенное паросочетание минимального веса в полном графе на
+/// вершинах `nodes` (их четное число), вес пары дает `weight`. Ищется
+/// паросочетание максимального веса `C - w`, где `C` больше всех весов:
+/// такие веса положительны, поэтому в полном графе оно совершенное, а
+/// `C` входит в вес любого совершенного паросочетания одинаково.
+///
+/// # Errors
+/// Вернет [`GraphError`], если для какой-то пары веса нет или веса
+/// переполнились.
+pub(super) fn minimum_weight_perfect_matching<W: Weight>(
+    nodes: &[Index],
+    weight: impl Fn(Index, Index) -> Option<W>,
+) -> Result<Matching> {
+    let mut edges = Vec::new();
+    for (i, &from) in nodes.iter().enumerate() {
+        for (j, &to) in nodes.iter().enumerate().skip(i + 1) {
+            let found = weight(from, to).ok_or_else(|| {
+                GraphError::new(
+                    GraphKindError::EdgeNotFound,
+                    &format!("нет ребра между {from} и {to}"),
+                )
+            })?;
+            edges.push((i, j, found));
+        }
+    }
+    let heaviest = edges.iter().map(|&(_, _, weight)| weight).max();
+    let ceiling = heaviest.unwrap_or(W::ZERO).try_add(W::from_f64(1.0))?;
+    let edges = edges
+        .into_iter()
+        .map(|(i, j, weight)| {
+            ceiling
+                .checked_sub(weight)
+                .map(|weight| (i, j, weight))
+                .ok_or_else(|| {
+                    Box::new(GraphError::new(
+                        GraphKindError::WeightOverflow,
+                        &format!("{ceiling} - {weight}"),
+                    )) as Box<dyn Error>
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut state = WeightedBlossom::new(nodes.len(), edges);
+    state.run()?;
+    let pairs = (0..nodes.len())
+        .filter_map(|v| state.mate[v].map(|end| (v, end / 2)))
+        .filter(|&(v, edge)| state.edges[edge].0 == v)
+        .map(|(_, edge)| (nodes[state.edges[edge].0], nodes[state.edges[edge].1]))
+        .collect();
+    Ok(Matching::new(pairs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::test_support::{Rng, graph};
+
+    /// Наибольшие размер и вес паросочетания перебором: наименьшая
+    /// свободная вершина либо остается свободной, либо берет соседа
+    fn brute_force(n: u32, edges: &[(u32, u32, i64)], used: &mut [bool]) -> (usize, i64) {
+        let Some(v) = (1..=n).find(|&v| !used[v as usize]) else {
+            return (0, 0);
+        };
+        used[v as usize] = true;
+        let (mut size, mut weight) = brute_force(n, edges, used);
+        for &(a, b, w) in edges {
+            let other = if a == v {
+                b
+            } else if b == v {
+                a
+            } else {
+                continue;
+            };
+            if used[other as usize] {
+                continue;
+            }
+            used[other as usize] = true;
+            let (rest_size, rest_weight) = brute_force(n, edges, used);
+            used[other as usize] = false;
+            size = size.max(rest_size + 1);
+            weight = weight.max(rest_weight + w);
+        }
+        used[v as usize] = false;
+        (size, weight)
+    }
+
+    /// Пары паросочетания --- ребра графа без общих вершин
+    fn assert_valid(edges: &[(u32, u32, i64)], matching: &Matching) {
+        let mut seen = HashSet::new();
+        for &(a, b) in matching.pairs() {
+            assert!(a < b);
+            assert!(seen.insert(a) && seen.insert(b), "вершина в двух парах");
+            assert!(
+                edges
+                    .iter()
+                    .any(|&(x, y, _)| (Index(x), Index(y)) == (a, b)
+                        || (Index(y), Index(x)) == (a, b)),
+                "пара ({a}, {b}) не ребро"
+            );
+        }
+    }
+
+    fn check(n: u32, edges: &[(u32, u32, i64)]) {
+        let graph = graph(n, false, edges);
+        let (size, weight) = brute_force(n, edges, &mut vec![false; n as usize + 1]);
+
+        let matching = maximum_matching(&graph).unwrap();
+        assert_valid(edges, &matching);
+        assert_eq!(matching.len(), size, "размер, ребра {edges:?}");
+        assert!(is_maximum_matching(&graph, &matching).unwrap());
+
+        let (weighted, total) = maximum_weight_matching(&graph).unwrap();
+        assert_valid(edges, &weighted);
+        assert_eq!(total, weight, "вес, ребра {edges:?}");
+    }
+
+    #[test]
+    fn odd_cycles() {
+        // Треугольник, пятиугольник и два треугольника на мосту
+        check(3, &[(1, 2, 1), (2, 3, 1), (1, 3, 1)]);
+        check(5, &[(1, 2, 1), (2, 3, 1), (3, 4, 1), (4, 5, 1), (5, 1, 1)]);
+        check(
+            6,
+            &[
+                (1, 2, 1),
+                (2, 3, 1),
+                (1, 3, 1),
+                (3, 4, 1),
+                (4, 5, 1),
+                (5, 6, 1),
+                (4, 6, 1),
+            ],
+        );
+        // Пятиугольник с хвостом: увеличение только через цветок
+        check(
+            7,
+            &[
+                (1, 2, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (4, 5, 1),
+                (5, 1, 1),
+                (1, 6, 1),
+                (6, 7, 1),
+            ],
+        );
+    }
+
+    #[test]
+    fn nested_blossoms() {
+        // S-цветок, используемый для увеличения
+        check(4, &[(1, 2, 8), (1, 3, 9), (2, 3, 10), (3, 4, 7)]);
+        // Вложенный S-цветок
+        check(
+            6,
+            &[
+                (1, 2, 9),
+                (1, 3, 9),
+                (2, 3, 10),
+                (2, 4, 8),
+                (3, 5, 8),
+                (4, 5, 10),
+                (5, 6, 6),
+            ],
+        );
+        // Вложенный S-цветок, рекурсивно раскрываемый после увеличения
+        check(
+            8,
+            &[
+                (1, 2, 8),
+                (1, 3, 8),
+                (2, 3, 10),
+                (2, 4, 12),
+                (3, 5, 12),
+                (4, 5, 14),
+                (4, 6, 12),
+                (5, 7, 12),
+                (6, 7, 14),
+                (7, 8, 12),
+            ],
+        );
+        // Раскрытие T-цветка
+        check(
+            8,
+            &[
+                (1, 2, 23),
+                (1, 5, 22),
+                (1, 6, 15),
+                (2, 3, 25),
+                (3, 4, 22),
+                (4, 5, 25),
+                (4, 8, 14),
+                (5, 7, 13),
+            ],
+        );
+        // Вложенный S-цветок становится T-цветком
+        check(
+            8,
+            &[
+                (1, 2, 19),
+                (1, 3, 20),
+                (1, 8, 8),
+                (2, 3, 25),
+                (2, 4, 18),
+                (3, 5, 18),
+                (4, 5, 13),
+                (4, 7, 7),
+                (5, 6, 7),
+            ],
+        );
+        // Отрицательные веса в паросочетание не попадают
+        check(4, &[(1, 2, -5), (2, 3, 4), (3, 4, -1), (1, 4, 2)]);
+    }
+
+    #[test]
+    fn random_graphs_match_brute_force() {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+        for _ in 0..300 {
+            let n = 1 + rng.below(9) as u32;
+            let edges = rng.edges(n, 45, 20);
+            check(n, &edges);
+        }
+    }
+
+    #[test]
+    fn minimum_perfect_matching_on_complete_graph() {
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..100 {
+            let n = 2 * (1 + rng.below(4) as u32);
+            let edges = rng.edges(n, 100, 30);
+            let weight = |a: Index, b: Index| {
+                edges
+                    .iter()
+                    .find(|&&(x, y, _)| (Index(x), Index(y)) == (a.min(b), a.max(b)))
+                    .map(|&(_, _, w)| w)
+            };
+            let nodes: Vec<Index> = (1..=n).map(Index).collect();
+            let matching = minimum_weight_perfect_matching(&nodes, weight).unwrap();
+            assert_eq!(matching.len(), n as usize / 2);
+            assert_valid(&edges, &matching);
+            let total: i64 = matching
+                .pairs()
+                .iter()
+                .map(|&(a, b)| weight(a, b).unwrap())
+                .sum();
+
+            // Наименьший вес совершенного паросочетания = вес
+            // максимального по весам C - w
+            let ceiling = 30;
+            let flipped: Vec<(u32, u32, i64)> =
+                edges.iter().map(|&(a, b, w)| (a, b, ceiling - w)).collect();
+            let (_, best) = brute_force(n, &flipped, &mut vec![false; n as usize + 1]);
+            assert_eq!(total, ceiling * (n as i64 / 2) - best);
+        }
+    }
+}
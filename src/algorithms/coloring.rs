@@ -0,0 +1,664 @@
+//! Раскраска графа: жадная с разными порядками вершин, DSatur, точное
+//! хроматическое число перебором, раскраска ребер двудольного графа и
+//! проверка раскраски.
+//!
+//! Раскраска вершин не учитывает направление ребер и петли.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt::{Debug, Display},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::{bipartite::bipartition, components::sorted_nodes, euler::graph_edges},
+    graph::{
+        core::{EdgeId, Graph, Index},
+        view::GraphView,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Раскраска вершин: номер цвета каждой вершины (цвета с нуля).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Coloring {
+    colors: HashMap<Index, usize>,
+}
+
+impl Coloring {
+    pub fn new(colors: HashMap<Index, usize>) -> Self {
+        Self { colors }
+    }
+
+    pub fn color_of(&self, index: Index) -> Option<usize> {
+        self.colors.get(&index).copied()
+    }
+
+    pub fn colors(&self) -> &HashMap<Index, usize> {
+        &self.colors
+    }
+
+    /// Число различных цветов
+    pub fn color_count(&self) -> usize {
+        self.classes().len()
+    }
+
+    /// Цветовые классы по возрастанию цвета, вершины в классе по
+    /// возрастанию
+    pub fn classes(&self) -> Vec<Vec<Index>> {
+        let mut classes: HashMap<usize, Vec<Index>> = HashMap::new();
+        for (&index, &color) in &self.colors {
+            classes.entry(color).or_default().push(index);
+        }
+        let mut classes: Vec<(usize, Vec<Index>)> = classes.into_iter().collect();
+        classes.sort();
+        classes
+            .into_iter()
+            .map(|(_, mut class)| {
+                class.sort();
+                class
+            })
+            .collect()
+    }
+}
+
+/// Раскраска ребер: номер цвета каждого ребра по id.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EdgeColoring {
+    colors: HashMap<EdgeId, usize>,
+    count: usize,
+}
+
+impl EdgeColoring {
+    pub fn color_of(&self, id: EdgeId) -> Option<usize> {
+        self.colors.get(&id).copied()
+    }
+
+    pub fn colors(&self) -> &HashMap<EdgeId, usize> {
+        &self.colors
+    }
+
+    /// Число цветов (для двудольного графа --- наибольшая степень)
+    pub fn color_count(&self) -> usize {
+        self.count
+    }
+
+    /// Ребра каждого цвета по возрастанию id
+    pub fn classes(&self) -> Vec<Vec<EdgeId>> {
+        let mut classes = vec![Vec::new(); self.count];
+        for (&id, &color) in &self.colors {
+            classes[color].push(id);
+        }
+        for class in &mut classes {
+            class.sort();
+        }
+        classes
+    }
+}
+
+/// Порядок вершин для жадной раскраски
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GreedyOrder {
+    /// По возрастанию номера
+    #[default]
+    Natural,
+    /// По убыванию степени (Уэлш–Пауэлл)
+    LargestFirst,
+    /// Вершина наименьшей степени --- в конец, затем она удаляется из
+    /// графа, и так далее. Для планарного графа дает не больше шести
+    /// цветов
+    SmallestLast,
+}
+
+/// Соседи вершин на плотных номерах: без петель и повторов, в обе
+/// стороны
+struct Neighborhoods {
+    nodes: Vec<Index>,
+    adjacent: Vec<Vec<usize>>,
+}
+
+impl Neighborhoods {
+    fn new<G: GraphView>(graph: &G) -> Self {
+        let nodes = sorted_nodes(graph);
+        let positions: HashMap<Index, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+        let mut adjacent = vec![Vec::new(); nodes.len()];
+        for (from, &index) in nodes.iter().enumerate() {
+            for (to, _) in graph.neighbors(index) {
+                if let Some(&to) = positions.get(&to)
+                    && to != from
+                {
+                    adjacent[from].push(to);
+                    adjacent[to].push(from);
+                }
+            }
+        }
+        for list in &mut adjacent {
+            list.sort_unstable();
+            list.dedup();
+        }
+        Self { nodes, adjacent }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn coloring(&self, colors: &[usize]) -> Coloring {
+        Coloring::new(
+            self.nodes
+                .iter()
+                .copied()
+                .zip(colors.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// Наименьший цвет, которого нет у раскрашенных соседей
+    fn smallest_free(&self, node: usize, colors: &[Option<usize>]) -> usize {
+        let mut taken: Vec<usize> = self.adjacent[node]
+            .iter()
+            .filter_map(|&next| colors[next])
+            .collect();
+        taken.sort_unstable();
+        taken.dedup();
+        taken
+            .iter()
+            .enumerate()
+            .find(|&(color, &used)| color != used)
+            .map_or(taken.len(), |(color, _)| color)
+    }
+
+    fn greedy(&self, order: impl IntoIterator<Item = usize>) -> Coloring {
+        let mut colors = vec![None; self.len()];
+        for node in order {
+            colors[node] = Some(self.smallest_free(node, &colors));
+        }
+        let colors: Vec<usize> = colors
+            .into_iter()
+            .map(|color| color.expect("порядок содержит все вершины"))
+            .collect();
+        self.coloring(&colors)
+    }
+
+    fn smallest_last_order(&self) -> Vec<usize> {
+        let mut degree: Vec<usize> = self.adjacent.iter().map(Vec::len).collect();
+        let mut removed = vec![false; self.len()];
+        let mut heap: BinaryHeap<Reverse<(usize, usize)>> = degree
+            .iter()
+            .enumerate()
+            .map(|(node, &degree)| Reverse((degree, node)))
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse((current, node))) = heap.pop() {
+            // Устаревшая запись кучи
+            if removed[node] || current != degree[node] {
+                continue;
+            }
+            removed[node] = true;
+            order.push(node);
+            for &next in &self.adjacent[node] {
+                if !removed[next] {
+                    degree[next] -= 1;
+                    heap.push(Reverse((degree[next], next)));
+                }
+            }
+        }
+        order.reverse();
+        order
+    }
+
+    /// Насыщенность: число различных цветов у соседей
+    fn saturation(&self, node: usize, colors: &[Option<usize>]) -> usize {
+        let mut seen: Vec<usize> = self.adjacent[node]
+            .iter()
+            .filter_map(|&next| colors[next])
+            .collect();
+        seen.sort_unstable();
+        seen.dedup();
+        seen.len()
+    }
+
+    /// Неокрашенная вершина наибольшей насыщенности, при равенстве ---
+    /// наибольшей степени, затем наименьшая
+    fn most_saturated(&self, colors: &[Option<usize>]) -> Option<usize> {
+        (0..self.len())
+            .filter(|&node| colors[node].is_none())
+            .max_by_key(|&node| {
+                (
+                    self.saturation(node, colors),
+                    self.adjacent[node].len(),
+                    Reverse(node),
+                )
+            })
+    }
+
+    /// Клика, собранная жадно от вершин большей степени; ее размер ---
+    /// нижняя оценка числа цветов
+    fn greedy_clique(&self) -> usize {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|&node| (Reverse(self.adjacent[node].len()), node));
+        let mut clique: Vec<usize> = Vec::new();
+        for node in order {
+            if clique
+                .iter()
+                .all(|member| self.adjacent[node].binary_search(member).is_ok())
+            {
+                clique.push(node);
+            }
+        }
+        clique.len()
+    }
+}
+
+/// Жадная раскраска: вершины по порядку `order` получают наименьший цвет,
+/// которого нет у соседей. `O(V + E log E)`.
+pub fn greedy_coloring<G: GraphView>(graph: &G, order: GreedyOrder) -> Coloring {
+    let neighborhoods = Neighborhoods::new(graph);
+    let n = neighborhoods.len();
+    match order {
+        GreedyOrder::Natural => neighborhoods.greedy(0..n),
+        GreedyOrder::LargestFirst => {
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by_key(|&node| (Reverse(neighborhoods.adjacent[node].len()), node));
+            neighborhoods.greedy(order)
+        }
+        GreedyOrder::SmallestLast => neighborhoods.greedy(neighborhoods.smallest_last_order()),
+    }
+}
+
+/// Жадная раскраска в заданном порядке вершин; вершины, которых нет в
+/// `order`, красятся после них по возрастанию номера.
+pub fn greedy_coloring_in_order<G: GraphView>(graph: &G, order: &[Index]) -> Coloring {
+    let neighborhoods = Neighborhoods::new(graph);
+    let mut queued = vec![false; neighborhoods.len()];
+    let mut positions: Vec<usize> = order
+        .iter()
+        .filter_map(|index| neighborhoods.nodes.binary_search(index).ok())
+        .filter(|&node| !std::mem::replace(&mut queued[node], true))
+        .collect();
+    positions.extend((0..neighborhoods.len()).filter(|&node| !queued[node]));
+    neighborhoods.greedy(positions)
+}
+
+/// Раскраска DSatur (Брелаз): каждый раз красится вершина, у соседей
+/// которой больше всего различных цветов, в наименьший свободный цвет.
+/// Двудольный граф красит в два цвета. `O(V² + VE)`.
+pub fn dsatur<G: GraphView>(graph: &G) -> Coloring {
+    let neighborhoods = Neighborhoods::new(graph);
+    let mut colors = vec![None; neighborhoods.len()];
+    while let Some(node) = neighborhoods.most_saturated(&colors) {
+        colors[node] = Some(neighborhoods.smallest_free(node, &colors));
+    }
+    let colors: Vec<usize> = colors.into_iter().flatten().collect();
+    neighborhoods.coloring(&colors)
+}
+
+/// Перебор с возвратом: вершины в порядке DSatur, цвета не больше
+/// использованных плюс один, ветка отбрасывается, если не даст меньше
+/// цветов, чем уже найдено
+struct ExactColoring<'a> {
+    neighborhoods: &'a Neighborhoods,
+    colors: Vec<Option<usize>>,
+    best: Vec<usize>,
+    best_count: usize,
+    lower_bound: usize,
+}
+
+impl ExactColoring<'_> {
+    fn search(&mut self, used: usize) {
+        if used >= self.best_count {
+            return;
+        }
+        let Some(node) = self.neighborhoods.most_saturated(&self.colors) else {
+            self.best = self.colors.iter().flatten().copied().collect();
+            self.best_count = used;
+            return;
+        };
+        for color in 0..=used {
+            // Оптимум уже найден или цвет не даст меньше цветов
+            if self.best_count <= self.lower_bound || used.max(color + 1) >= self.best_count {
+                break;
+            }
+            let taken = self.neighborhoods.adjacent[node]
+                .iter()
+                .any(|&next| self.colors[next] == Some(color));
+            if taken {
+                continue;
+            }
+            self.colors[node] = Some(color);
+            self.search(used.max(color + 1));
+            self.colors[node] = None;
+        }
+    }
+}
+
+/// Раскраска в наименьшее число цветов (хроматическое число) перебором с
+/// возвратом. Начальная раскраска --- [`dsatur`], нижняя оценка --- жадно
+/// найденная клика. Время экспоненциальное, подходит для небольших
+/// графов (несколько десятков вершин).
+pub fn exact_coloring<G: GraphView>(graph: &G) -> Coloring {
+    let neighborhoods = Neighborhoods::new(graph);
+    let start = dsatur(graph);
+    let best: Vec<usize> = neighborhoods
+        .nodes
+        .iter()
+        .map(|&index| start.color_of(index).expect("DSatur красит все вершины"))
+        .collect();
+    let mut search = ExactColoring {
+        neighborhoods: &neighborhoods,
+        colors: vec![None; neighborhoods.len()],
+        best_count: start.color_count(),
+        best,
+        lower_bound: neighborhoods.greedy_clique(),
+    };
+    search.search(0);
+    neighborhoods.coloring(&search.best)
+}
+
+/// Хроматическое число графа (см. [`exact_coloring`])
+pub fn chromatic_number<G: GraphView>(graph: &G) -> usize {
+    exact_coloring(graph).color_count()
+}
+
+/// Раскраска ребер двудольного графа в `Δ` цветов (наибольшая степень,
+/// теорема Кёнига). Ребра красятся по одному; если свободный у одного
+/// конца цвет `a` занят у другого, цвета `a` и `b` меняются местами вдоль
+/// чередующегося пути, который в двудольном графе не возвращается в
+/// первый конец. `O(E V)`. Параллельные ребра красятся в разные цвета,
+/// направление не учитывается; ребра в вершины, которых нет в графе,
+/// пропускаются.
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError) с
+/// [`GraphKindError::GraphMustBeBipartite`](crate::GraphKindError::GraphMustBeBipartite),
+/// если граф не двудольный.
+pub fn bipartite_edge_coloring<T, W>(graph: &Graph<T, W>) -> Result<EdgeColoring>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    bipartition(graph).into_parts()?;
+    let nodes = sorted_nodes(graph);
+    // Ребра орграфа в несуществующие вершины не красим
+    let (edges, ends): (Vec<_>, Vec<(usize, usize)>) = graph_edges(graph)
+        .into_iter()
+        .filter_map(|edge| {
+            let from = nodes.binary_search(&edge.from).ok()?;
+            let to = nodes.binary_search(&edge.to).ok()?;
+            Some((edge, (from, to)))
+        })
+        .unzip();
+
+    let mut degree = vec![0; nodes.len()];
+    for &(from, to) in &ends {
+        degree[from] += 1;
+        degree[to] += 1;
+    }
+    let count = degree.into_iter().max().unwrap_or(0);
+
+    // Ребро цвета c у вершины v
+    let mut at: Vec<Vec<Option<usize>>> = vec![vec![None; count]; nodes.len()];
+    let mut color_of: Vec<Option<usize>> = vec![None; edges.len()];
+    let free = |at: &[Vec<Option<usize>>], node: usize| {
+        at[node]
+            .iter()
+            .position(Option::is_none)
+            .expect("у вершины степени не больше Δ есть свободный цвет")
+    };
+
+    for (edge, &(from, to)) in ends.iter().enumerate() {
+        let a = free(&at, from);
+        let b = free(&at, to);
+        if at[to][a].is_some() {
+            // Чередующийся a/b-путь из to; меняем на нем цвета местами
+            let mut path = Vec::new();
+            let (mut node, mut color) = (to, a);
+            while let Some(next) = at[node][color] {
+                path.push(next);
+                let (x, y) = ends[next];
+                node = if x == node { y } else { x };
+                color = if color == a { b } else { a };
+            }
+            for &next in &path {
+                let (x, y) = ends[next];
+                let old = color_of[next].expect("ребро пути покрашено");
+                at[x][old] = None;
+                at[y][old] = None;
+            }
+            for &next in &path {
+                let (x, y) = ends[next];
+                let new = if color_of[next] == Some(a) { b } else { a };
+                color_of[next] = Some(new);
+                at[x][new] = Some(next);
+                at[y][new] = Some(next);
+            }
+        }
+        color_of[edge] = Some(a);
+        at[from][a] = Some(edge);
+        at[to][a] = Some(edge);
+    }
+
+    Ok(EdgeColoring {
+        colors: edges
+            .iter()
+            .zip(color_of)
+            .map(|(edge, color)| (edge.id, color.expect("все ребра покрашены")))
+            .collect(),
+        count,
+    })
+}
+
+/// Раскраска не правильная: есть непокрашенные вершины или смежные
+/// вершины одного цвета
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidColoring {
+    uncolored: Vec<Index>,
+    conflicts: Vec<(Index, Index)>,
+}
+
+impl InvalidColoring {
+    /// Вершины графа без цвета
+    pub fn uncolored(&self) -> &[Index] {
+        &self.uncolored
+    }
+
+    /// Ребра `(меньшая вершина, большая)` между вершинами одного цвета
+    pub fn conflicts(&self) -> &[(Index, Index)] {
+        &self.conflicts
+    }
+}
+
+impl Display for InvalidColoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let uncolored: Vec<String> = self.uncolored.iter().map(Index::to_string).collect();
+        let conflicts: Vec<String> = self
+            .conflicts
+            .iter()
+            .map(|(from, to)| format!("{from}-{to}"))
+            .collect();
+        write!(
+            f,
+            "coloring is not proper: uncolored nodes [{}], same-colored edges [{}]",
+            uncolored.join(", "),
+            conflicts.join(", ")
+        )
+    }
+}
+
+impl Error for InvalidColoring {}
+
+/// Проверяет, что раскраска правильная: каждая вершина графа покрашена, и
+/// концы каждого ребра (кроме петель) разного цвета.
+///
+/// # Errors
+/// Вернет [`InvalidColoring`] с непокрашенными вершинами и ребрами между
+/// вершинами одного цвета.
+pub fn check_coloring<G: GraphView>(
+    graph: &G,
+    coloring: &Coloring,
+) -> std::result::Result<(), InvalidColoring> {
+    let neighborhoods = Neighborhoods::new(graph);
+    let uncolored: Vec<Index> = neighborhoods
+        .nodes
+        .iter()
+        .copied()
+        .filter(|&index| coloring.color_of(index).is_none())
+        .collect();
+    let mut conflicts = Vec::new();
+    for (from, list) in neighborhoods.adjacent.iter().enumerate() {
+        for &to in list.iter().filter(|&&to| from < to) {
+            let (from, to) = (neighborhoods.nodes[from], neighborhoods.nodes[to]);
+            if let Some(color) = coloring.color_of(from)
+                && coloring.color_of(to) == Some(color)
+            {
+                conflicts.push((from, to));
+            }
+        }
+    }
+
+    if uncolored.is_empty() && conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidColoring {
+            uncolored,
+            conflicts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::test_support::{Rng, assert_kind, connect, graph},
+        graph::core::{Edge, GraphKindError},
+    };
+
+    /// Вершины `node..` красятся не более чем в `count` цветов
+    fn colorable(
+        edges: &[(u32, u32, i64)],
+        colors: &mut [usize],
+        node: usize,
+        count: usize,
+    ) -> bool {
+        if node == colors.len() {
+            return true;
+        }
+        for color in 1..=count {
+            let conflict = edges.iter().any(|&(a, b, _)| {
+                let (a, b) = (a as usize - 1, b as usize - 1);
+                (a == node && b < node && colors[b] == color)
+                    || (b == node && a < node && colors[a] == color)
+            });
+            if conflict {
+                continue;
+            }
+            colors[node] = color;
+            if colorable(edges, colors, node + 1, count) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Хроматическое число перебором числа цветов
+    fn brute_force(n: u32, edges: &[(u32, u32, i64)]) -> usize {
+        (0..=n as usize)
+            .find(|&count| colorable(edges, &mut vec![0; n as usize], 0, count))
+            .expect("n цветов хватает всегда")
+    }
+
+    #[test]
+    fn exact_coloring_matches_brute_force() {
+        let mut rng = Rng::new(0x2127_599b_f432_5c37);
+        for _ in 0..300 {
+            let n = rng.below(8) as u32;
+            let percent = rng.below(100);
+            let edges = rng.edges(n, percent, 1);
+            let graph = graph(n, rng.below(2) == 0, &edges);
+            let expected = brute_force(n, &edges);
+
+            let exact = exact_coloring(&graph);
+            check_coloring(&graph, &exact).unwrap();
+            assert_eq!(exact.color_count(), expected, "ребра {edges:?}");
+            assert_eq!(chromatic_number(&graph), expected);
+
+            for heuristic in [
+                dsatur(&graph),
+                greedy_coloring(&graph, GreedyOrder::SmallestLast),
+            ] {
+                check_coloring(&graph, &heuristic).unwrap();
+                assert!(heuristic.color_count() >= expected);
+            }
+        }
+    }
+
+    /// Каждое ребро покрашено, у ребер с общим концом цвета разные, цветов
+    /// столько, какова наибольшая степень
+    fn assert_proper_edge_coloring(graph: &Graph<(), i64>, coloring: &EdgeColoring) {
+        let mut ends: HashMap<EdgeId, (Index, Index)> = HashMap::new();
+        for (&from, adj) in graph.iter() {
+            for edge in adj {
+                ends.entry(edge.id).or_insert((from, edge.node.number));
+            }
+        }
+        assert_eq!(coloring.colors().len(), ends.len());
+
+        let mut seen: HashMap<Index, Vec<usize>> = HashMap::new();
+        for (id, (from, to)) in ends {
+            let color = coloring.color_of(id).expect("ребро покрашено");
+            assert!(color < coloring.color_count());
+            for end in [from, to] {
+                let colors = seen.entry(end).or_default();
+                assert!(!colors.contains(&color), "цвет {color} дважды у {end}");
+                colors.push(color);
+            }
+        }
+        let degree = seen.values().map(Vec::len).max().unwrap_or(0);
+        assert_eq!(coloring.color_count(), degree);
+    }
+
+    #[test]
+    fn bipartite_edge_coloring_is_proper() {
+        let mut rng = Rng::new(0x3c6e_f372_fe94_f82b);
+        for _ in 0..300 {
+            // Левая доля 1..=a, правая a+1..=a+b; бывают параллельные ребра
+            let (a, b) = (1 + rng.below(4) as u32, 1 + rng.below(4) as u32);
+            let mut edges = Vec::new();
+            for _ in 0..rng.below(16) {
+                let left = 1 + rng.below(a as u64) as u32;
+                let right = a + 1 + rng.below(b as u64) as u32;
+                edges.push((left, right, 1));
+            }
+            let graph = graph(a + b, rng.below(2) == 0, &edges);
+            let coloring = bipartite_edge_coloring(&graph).unwrap();
+            assert_proper_edge_coloring(&graph, &coloring);
+        }
+
+        let triangle = graph(3, false, &[(1, 2, 1), (2, 3, 1), (1, 3, 1)]);
+        assert_kind(
+            bipartite_edge_coloring(&triangle),
+            GraphKindError::GraphMustBeBipartite,
+        );
+    }
+
+    #[test]
+    fn bipartite_edge_coloring_skips_missing_vertices() {
+        // Дуга орграфа в вершину 9, которой нет в графе
+        let mut graph = graph(3, true, &[(1, 2, 1), (3, 2, 1)]);
+        let dangling = connect(&mut graph, 1, Edge::new(&Index(9), 1, &()));
+        let coloring = bipartite_edge_coloring(&graph).unwrap();
+        assert_eq!(coloring.color_of(dangling), None);
+        assert_eq!(coloring.color_count(), 2);
+        assert_eq!(coloring.colors().len(), 2);
+    }
+}
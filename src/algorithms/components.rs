@@ -0,0 +1,270 @@
+//! Компоненты сильной связности (Тарьян, Косарайю) и граф конденсации.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
+
+use crate::{
+    graph::{
+        core::{Adjacency, Edge, Graph, Index, Node},
+        view::GraphView,
+    },
+    tasks::task_6::task_6_4,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Разбиение вершин на компоненты сильной связности.
+///
+/// Компоненты пронумерованы в топологическом порядке графа конденсации:
+/// ребра между компонентами идут только от меньшего номера к большему.
+/// Вершины внутри компоненты отсортированы.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    components: Vec<Vec<Index>>,
+    component_of: HashMap<Index, usize>,
+}
+
+impl Components {
+    fn new(mut components: Vec<Vec<Index>>) -> Self {
+        let mut component_of = HashMap::new();
+        for (number, members) in components.iter_mut().enumerate() {
+            members.sort();
+            for &index in members.iter() {
+                component_of.insert(index, number);
+            }
+        }
+        Self {
+            components,
+            component_of,
+        }
+    }
+
+    /// Количество компонент
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns true if this [`Components`] has no components.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Номер компоненты, в которой лежит вершина index
+    pub fn component_of(&self, index: Index) -> Option<usize> {
+        self.component_of.get(&index).copied()
+    }
+
+    /// Вершины каждой компоненты
+    pub fn components(&self) -> &[Vec<Index>] {
+        &self.components
+    }
+}
+
+/// Вершины графа по возрастанию номера, чтобы результат не зависел от
+/// порядка обхода HashMap
+pub(super) fn sorted_nodes<G: GraphView>(graph: &G) -> Vec<Index> {
+    let mut nodes: Vec<Index> = graph.node_indices().collect();
+    nodes.sort();
+    nodes
+}
+
+/// Соседи вершины, лежащие в графе
+pub(super) fn successors<G: GraphView>(graph: &G, index: Index) -> Vec<Index> {
+    graph
+        .neighbors(index)
+        .map(|(to, _)| to)
+        .filter(|&to| graph.contains_node(to))
+        .collect()
+}
+
+/// Стек вызовов обхода: вершина, ее соседи и сколько из них уже
+/// просмотрено
+pub(super) type CallStack = Vec<(Index, Vec<Index>, usize)>;
+
+/// Состояние алгоритма Тарьяна
+#[derive(Default)]
+struct Tarjan {
+    // Время входа и минимальное время входа, достижимое из поддерева
+    order: HashMap<Index, usize>,
+    low: HashMap<Index, usize>,
+    stack: Vec<Index>,
+    on_stack: HashSet<Index>,
+    components: Vec<Vec<Index>>,
+}
+
+impl Tarjan {
+    fn enter<G: GraphView>(&mut self, graph: &G, index: Index, calls: &mut CallStack) {
+        let time = self.order.len();
+        self.order.insert(index, time);
+        self.low.insert(index, time);
+        self.stack.push(index);
+        self.on_stack.insert(index);
+        calls.push((index, successors(graph, index), 0));
+    }
+
+    fn lower(&mut self, index: Index, time: usize) {
+        if let Some(low) = self.low.get_mut(&index) {
+            *low = (*low).min(time);
+        }
+    }
+
+    fn run<G: GraphView>(&mut self, graph: &G, root: Index) {
+        let mut calls = CallStack::new();
+        self.enter(graph, root, &mut calls);
+
+        while let Some((node, neighbors, next)) = calls.last_mut() {
+            let node = *node;
+            if let Some(&to) = neighbors.get(*next) {
+                *next += 1;
+                if !self.order.contains_key(&to) {
+                    self.enter(graph, to, &mut calls);
+                } else if self.on_stack.contains(&to) {
+                    self.lower(node, self.order[&to]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _, _)) = calls.last() {
+                self.lower(parent, self.low[&node]);
+            }
+
+            // node --- корень компоненты: снимаем ее со стека
+            if self.low[&node] == self.order[&node] {
+                let mut component = Vec::new();
+                while let Some(index) = self.stack.pop() {
+                    self.on_stack.remove(&index);
+                    component.push(index);
+                    if index == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+/// Компоненты сильной связности алгоритмом Тарьяна (один обход в
+/// глубину, без рекурсии).
+pub fn tarjan_scc<G: GraphView>(graph: &G) -> Components {
+    let mut tarjan = Tarjan::default();
+    for root in sorted_nodes(graph) {
+        if !tarjan.order.contains_key(&root) {
+            tarjan.run(graph, root);
+        }
+    }
+
+    // Тарьян находит компоненты в обратном топологическом порядке
+    tarjan.components.reverse();
+    Components::new(tarjan.components)
+}
+
+/// Компоненты сильной связности алгоритмом Косарайю: обход в глубину
+/// по графу, затем по развернутому графу в порядке убывания времени
+/// выхода.
+pub fn kosaraju_scc<G: GraphView>(graph: &G) -> Components {
+    // Порядок выхода из вершин при обходе исходного графа
+    let mut finished: Vec<Index> = Vec::new();
+    let mut visited: HashSet<Index> = HashSet::new();
+
+    for root in sorted_nodes(graph) {
+        if !visited.insert(root) {
+            continue;
+        }
+        let mut calls: CallStack = vec![(root, successors(graph, root), 0)];
+        while let Some((node, neighbors, next)) = calls.last_mut() {
+            if let Some(&to) = neighbors.get(*next) {
+                *next += 1;
+                if visited.insert(to) {
+                    calls.push((to, successors(graph, to), 0));
+                }
+            } else {
+                finished.push(*node);
+                calls.pop();
+            }
+        }
+    }
+
+    // Вершина с наибольшим временем выхода лежит в компоненте-источнике,
+    // поэтому компоненты получаются сразу в топологическом порядке
+    let reversed = graph.reversed();
+    let mut assigned: HashSet<Index> = HashSet::new();
+    let mut components: Vec<Vec<Index>> = Vec::new();
+
+    for &root in finished.iter().rev() {
+        if !assigned.insert(root) {
+            continue;
+        }
+        let mut component = vec![root];
+        let mut pending = vec![root];
+        while let Some(node) = pending.pop() {
+            for to in successors(&reversed, node) {
+                if assigned.insert(to) {
+                    component.push(to);
+                    pending.push(to);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    Components::new(components)
+}
+
+/// Граф конденсации: вершина `i` --- компонента `i` (ее значение ---
+/// вершины компоненты), ребро между компонентами есть, если есть ребро
+/// между их вершинами; его вес --- минимальный из таких ребер.
+/// Результат --- ориентированный ациклический граф.
+pub fn condensation<G: GraphView>(
+    graph: &G,
+    components: &Components,
+) -> Result<Graph<Vec<Index>, G::Weight>> {
+    let mut result: Graph<Vec<Index>, G::Weight> = Graph::new(None, Adjacency::default(), true);
+    for (number, members) in components.components().iter().enumerate() {
+        result.add_node(Node::new(Index(number as u32), members.clone()))?;
+    }
+
+    let mut lightest: HashMap<(usize, usize), G::Weight> = HashMap::new();
+    for from in graph.node_indices() {
+        let Some(from_component) = components.component_of(from) else {
+            continue;
+        };
+        for (to, weight) in graph.neighbors(from) {
+            let Some(to_component) = components.component_of(to) else {
+                continue;
+            };
+            if from_component != to_component {
+                lightest
+                    .entry((from_component, to_component))
+                    .and_modify(|old| *old = (*old).min(weight))
+                    .or_insert(weight);
+            }
+        }
+    }
+
+    let mut edges: Vec<((usize, usize), G::Weight)> = lightest.into_iter().collect();
+    edges.sort();
+    for ((from, to), weight) in edges {
+        let to_members = &components.components()[to];
+        result.add_edge(
+            &Node::new(Index(from as u32), components.components()[from].clone()),
+            &Edge::new(&Index(to as u32), weight, to_members),
+        )?;
+    }
+
+    Ok(result)
+}
+
+/// Сильно связен ли граф (из любой вершины достижима любая)
+pub fn is_strongly_connected<G: GraphView>(graph: &G) -> bool {
+    tarjan_scc(graph).len() <= 1
+}
+
+/// Слабо связен ли граф (связен ли он без учета ориентации ребер)
+pub fn is_weakly_connected<G: GraphView>(graph: &G) -> bool {
+    // Представление неориентированное, поэтому ошибки не будет
+    task_6_4(&graph.undirected()).unwrap_or(false)
+}
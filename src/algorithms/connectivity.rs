@@ -0,0 +1,307 @@
+//! Реберная и вершинная связность по теореме Менгера: число
+//! непересекающихся путей равно максимальному потоку в сети с единичными
+//! пропускными способностями.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
+
+use crate::{
+    algorithms::components::sorted_nodes,
+    graph::{
+        core::{GraphError, GraphKindError, Index},
+        view::GraphView,
+    },
+    tasks::task_11::FlowNetwork,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Вспомогательная сеть с целыми пропускными способностями. Веса исходного
+/// графа не используются: важно только число ребер.
+#[derive(Debug, Default)]
+struct UnitNetwork {
+    arcs: HashMap<Index, Vec<(Index, u32)>>,
+}
+
+impl UnitNetwork {
+    fn add_arc(&mut self, from: Index, to: Index, capacity: u32) {
+        self.arcs.entry(to).or_default();
+        self.arcs.entry(from).or_default().push((to, capacity));
+    }
+
+    /// Сеть для реберной связности: каждое ребро --- дуга емкости 1, ребро
+    /// неориентированного графа --- две встречные дуги
+    fn for_edges<G: GraphView>(graph: &G) -> Self {
+        let mut network = Self::default();
+        for from in graph.node_indices() {
+            network.arcs.entry(from).or_default();
+            for (to, _) in graph.neighbors(from) {
+                // Неориентированное ребро и так хранится в обе стороны
+                if from != to && graph.contains_node(to) {
+                    network.add_arc(from, to, 1);
+                }
+            }
+        }
+        network
+    }
+
+    /// Сеть для вершинной связности: вершина `v` с плотным номером `p`
+    /// раздваивается на вход `2p` и выход `2p + 1`, соединенные дугой
+    /// емкости 1, так что через вершину проходит не больше одного пути.
+    /// Ребро `u -> v` --- дуга из выхода `u` во вход `v`; параллельные
+    /// ребра склеиваются.
+    fn for_vertices<G: GraphView>(graph: &G, positions: &HashMap<Index, u32>) -> Self {
+        let mut network = Self::default();
+        for (&index, &pos) in positions {
+            network.add_arc(Index(2 * pos), Index(2 * pos + 1), 1);
+            let mut targets: HashSet<u32> = HashSet::new();
+            for (to, _) in graph.neighbors(index) {
+                if let Some(&to_pos) = positions.get(&to)
+                    && to_pos != pos
+                    && targets.insert(to_pos)
+                {
+                    network.add_arc(Index(2 * pos + 1), Index(2 * to_pos), 1);
+                }
+            }
+        }
+        network
+    }
+}
+
+impl GraphView for UnitNetwork {
+    type Weight = u32;
+
+    fn node_count(&self) -> usize {
+        self.arcs.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        self.arcs.contains_key(&index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.arcs.keys().copied()
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, u32)> + '_ {
+        self.arcs.get(&index).into_iter().flatten().copied()
+    }
+}
+
+/// Максимальный поток из `source` в `target` (Эдмондс–Карп, как в
+/// `task_11`) и разложение его на пути. Дуги единичные, поэтому поток по
+/// дуге --- число проходящих по ней путей.
+fn disjoint_flow_paths(
+    network: &UnitNetwork,
+    source: Index,
+    target: Index,
+) -> Result<Vec<Vec<Index>>> {
+    let mut flow = FlowNetwork::new(network)?;
+    while let Some(path) = flow.find_augmenting_path(source, target) {
+        flow.augment(&path)?;
+    }
+
+    // Дуга повторяется столько раз, сколько путей по ней идет
+    let mut outgoing: HashMap<Index, Vec<Index>> = HashMap::new();
+    for from in sorted_nodes(network) {
+        // Параллельные дуги: поток по ним хранится суммарно
+        let mut targets: Vec<Index> = network.neighbors(from).map(|(to, _)| to).collect();
+        targets.sort();
+        targets.dedup();
+        for to in targets {
+            let units = flow.flow(from, to) as usize;
+            outgoing
+                .entry(from)
+                .or_default()
+                .extend(std::iter::repeat_n(to, units));
+        }
+    }
+
+    let mut paths = Vec::new();
+    for _ in 0..flow.value() {
+        let mut path = vec![source];
+        let mut current = source;
+        while current != target {
+            // Поток сохраняется в каждой вершине, поэтому из вершины пути
+            // всегда выходит неиспользованная дуга с потоком
+            let next = outgoing
+                .get_mut(&current)
+                .and_then(Vec::pop)
+                .expect("поток сохраняется");
+
+            // Поток может содержать циклы: выбрасываем их из пути
+            if let Some(pos) = path.iter().position(|&index| index == next) {
+                path.truncate(pos + 1);
+            } else {
+                path.push(next);
+            }
+            current = next;
+        }
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+fn check_pair<G: GraphView>(graph: &G, source: Index, target: Index) -> Result<()> {
+    for index in [source, target] {
+        if !graph.contains_node(index) {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::NodeNotFound,
+                &format!("вершина {index}"),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Максимальное множество реберно-непересекающихся путей из `source` в
+/// `target`. Путей столько, сколько ребер в минимальном разрезе между ними;
+/// если вершины совпадают, путей нет.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет.
+pub fn edge_disjoint_paths<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Index,
+) -> Result<Vec<Vec<Index>>> {
+    check_pair(graph, source, target)?;
+    disjoint_flow_paths(&UnitNetwork::for_edges(graph), source, target)
+}
+
+/// Максимальное множество путей из `source` в `target`, не имеющих общих
+/// вершин, кроме концов. Прямое ребро `source -> target` дает один путь;
+/// если вершины совпадают, путей нет.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет.
+pub fn vertex_disjoint_paths<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Index,
+) -> Result<Vec<Vec<Index>>> {
+    check_pair(graph, source, target)?;
+    if source == target {
+        return Ok(Vec::new());
+    }
+
+    let indices = sorted_nodes(graph);
+    let positions: HashMap<Index, u32> = indices
+        .iter()
+        .enumerate()
+        .map(|(pos, &index)| (index, pos as u32))
+        .collect();
+    let network = UnitNetwork::for_vertices(graph, &positions);
+
+    // Поток идет из выхода source во вход target, чтобы емкость самих
+    // концов не ограничивала число путей
+    let paths = disjoint_flow_paths(
+        &network,
+        Index(2 * positions[&source] + 1),
+        Index(2 * positions[&target]),
+    )?;
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let mut original: Vec<Index> = path
+                .into_iter()
+                .map(|split| indices[(split.0 / 2) as usize])
+                .collect();
+            original.dedup();
+            original
+        })
+        .collect())
+}
+
+/// Локальная реберная связность λ(s, t): сколько ребер нужно удалить,
+/// чтобы из `source` нельзя было попасть в `target`.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет.
+pub fn local_edge_connectivity<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Index,
+) -> Result<usize> {
+    Ok(edge_disjoint_paths(graph, source, target)?.len())
+}
+
+/// Локальная вершинная связность κ(s, t): число внутренне
+/// непересекающихся путей из `source` в `target`. Для несмежных вершин
+/// это размер минимального множества вершин, разделяющего их.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет.
+pub fn local_vertex_connectivity<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Index,
+) -> Result<usize> {
+    Ok(vertex_disjoint_paths(graph, source, target)?.len())
+}
+
+/// Реберная связность λ графа: наименьшее число ребер, удаление которых
+/// делает граф несвязным (орграф --- не сильно связным). У графа из одной
+/// вершины и пустого графа она нулевая.
+///
+/// Достаточно потоков из одной вершины во все остальные (и обратно для
+/// орграфа): минимальный разрез отделяет ее от какой-то вершины.
+///
+/// # Errors
+/// Вернет [`GraphError`], если поток переполнился.
+pub fn edge_connectivity<G: GraphView>(graph: &G) -> Result<usize> {
+    let nodes = sorted_nodes(graph);
+    let Some((&first, rest)) = nodes.split_first() else {
+        return Ok(0);
+    };
+
+    let network = UnitNetwork::for_edges(graph);
+    let mut best: Option<usize> = None;
+    for &other in rest {
+        let mut count = disjoint_flow_paths(&network, first, other)?.len();
+        if graph.is_directed() {
+            count = count.min(disjoint_flow_paths(&network, other, first)?.len());
+        }
+        best = Some(best.map_or(count, |old| old.min(count)));
+    }
+
+    Ok(best.unwrap_or(0))
+}
+
+/// Вершинная связность κ графа: наименьшее число вершин, удаление которых
+/// делает граф несвязным (орграф --- не сильно связным). У полного графа
+/// на `n` вершинах она равна `n - 1`.
+///
+/// Перебираются все пары несмежных вершин (в орграфе --- упорядоченные
+/// пары без дуги между ними).
+///
+/// # Errors
+/// Вернет [`GraphError`], если поток переполнился.
+pub fn vertex_connectivity<G: GraphView>(graph: &G) -> Result<usize> {
+    let nodes = sorted_nodes(graph);
+    let adjacent: HashSet<(Index, Index)> = nodes
+        .iter()
+        .flat_map(|&from| graph.neighbors(from).map(move |(to, _)| (from, to)))
+        .collect();
+
+    let mut best = nodes.len().saturating_sub(1);
+    for &source in &nodes {
+        for &target in &nodes {
+            let ordered = graph.is_directed() || source < target;
+            if source == target || !ordered || adjacent.contains(&(source, target)) {
+                continue;
+            }
+            best = best.min(local_vertex_connectivity(graph, source, target)?);
+        }
+    }
+
+    Ok(best)
+}
@@ -0,0 +1,405 @@
+//! Топологическая сортировка и задачи на ориентированных ациклических
+//! графах: критический путь, число путей, истоки и стоки.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+};
+
+use crate::{
+    algorithms::components::{CallStack, sorted_nodes, successors},
+    graph::{
+        core::{ColorNode, GraphError, GraphKindError, Index},
+        view::GraphView,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Результат топологической сортировки: порядок вершин или цикл,
+/// из-за которого порядка нет.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopologicalOrder {
+    /// Каждое ребро идет от вершины, стоящей раньше, к стоящей позже
+    Order(Vec<Index>),
+    /// Вершины цикла по ходу ребер, первая не повторяется в конце
+    Cycle(Vec<Index>),
+}
+
+impl TopologicalOrder {
+    /// Порядок, если граф ацикличен; иначе ошибка
+    /// [`GraphKindError::GraphMustBeAcyclic`] с вершинами цикла
+    pub fn into_order(self) -> std::result::Result<Vec<Index>, GraphError> {
+        match self {
+            TopologicalOrder::Order(order) => Ok(order),
+            TopologicalOrder::Cycle(cycle) => Err(GraphError::new(
+                GraphKindError::GraphMustBeAcyclic,
+                &format!("цикл {}", format_cycle(&cycle)),
+            )),
+        }
+    }
+}
+
+/// Цикл в виде `a -> b -> c -> a`
+fn format_cycle(cycle: &[Index]) -> String {
+    cycle
+        .iter()
+        .chain(cycle.first())
+        .map(|index| index.to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+fn ensure_directed<G: GraphView>(graph: &G) -> Result<()> {
+    if graph.is_directed() {
+        Ok(())
+    } else {
+        Err(Box::new(GraphError::new(
+            GraphKindError::GraphMustBeDirected,
+            "топологический порядок есть только у орграфа",
+        )))
+    }
+}
+
+/// Топологическая сортировка алгоритмом Кана: вершины без входящих ребер
+/// снимаются по одной, начиная с вершины с меньшим номером, поэтому
+/// порядок --- лексикографически наименьший.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф неориентированный.
+pub fn topological_sort_kahn<G: GraphView>(graph: &G) -> Result<TopologicalOrder> {
+    ensure_directed(graph)?;
+
+    let nodes = sorted_nodes(graph);
+    let mut in_degree: HashMap<Index, usize> = nodes.iter().map(|&index| (index, 0)).collect();
+    for &from in &nodes {
+        for to in successors(graph, from) {
+            *in_degree.entry(to).or_default() += 1;
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<Index>> = nodes
+        .iter()
+        .filter(|index| in_degree[index] == 0)
+        .map(|&index| Reverse(index))
+        .collect();
+    let mut order = Vec::with_capacity(nodes.len());
+
+    while let Some(Reverse(from)) = ready.pop() {
+        order.push(from);
+        for to in successors(graph, from) {
+            if let Some(degree) = in_degree.get_mut(&to) {
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(Reverse(to));
+                }
+            }
+        }
+    }
+
+    if order.len() == nodes.len() {
+        return Ok(TopologicalOrder::Order(order));
+    }
+
+    // У каждой оставшейся вершины есть входящее ребро из оставшейся,
+    // поэтому, идя по входящим ребрам, мы обязательно замкнем цикл
+    let remaining: HashSet<Index> = nodes
+        .into_iter()
+        .filter(|index| in_degree[index] > 0)
+        .collect();
+    let reversed = graph.reversed();
+    let mut walk: Vec<Index> = Vec::new();
+    let mut position: HashMap<Index, usize> = HashMap::new();
+    let mut current = *remaining
+        .iter()
+        .min()
+        .expect("осталась хотя бы одна вершина");
+
+    while !position.contains_key(&current) {
+        position.insert(current, walk.len());
+        walk.push(current);
+        current = successors(&reversed, current)
+            .into_iter()
+            .filter(|prev| remaining.contains(prev))
+            .min()
+            .expect("у оставшейся вершины есть предшественник");
+    }
+
+    let mut cycle = walk.split_off(position[&current]);
+    cycle.reverse();
+    Ok(TopologicalOrder::Cycle(cycle))
+}
+
+/// Топологическая сортировка обходом в глубину (без рекурсии): вершины в
+/// порядке убывания времени выхода. Серая вершина среди соседей означает
+/// обратное ребро, и цикл берется из стека обхода.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф неориентированный.
+pub fn topological_sort_dfs<G: GraphView>(graph: &G) -> Result<TopologicalOrder> {
+    ensure_directed(graph)?;
+
+    let nodes = sorted_nodes(graph);
+    let mut color: HashMap<Index, ColorNode> = nodes
+        .iter()
+        .map(|&index| (index, ColorNode::White))
+        .collect();
+    let mut finished: Vec<Index> = Vec::with_capacity(nodes.len());
+
+    for root in nodes {
+        if color[&root] != ColorNode::White {
+            continue;
+        }
+        color.insert(root, ColorNode::Gray);
+        let mut calls: CallStack = vec![(root, successors(graph, root), 0)];
+
+        while let Some((node, neighbors, next)) = calls.last_mut() {
+            let Some(&to) = neighbors.get(*next) else {
+                color.insert(*node, ColorNode::Black);
+                finished.push(*node);
+                calls.pop();
+                continue;
+            };
+            *next += 1;
+
+            match color[&to] {
+                ColorNode::White => {
+                    color.insert(to, ColorNode::Gray);
+                    calls.push((to, successors(graph, to), 0));
+                }
+                ColorNode::Gray => {
+                    // Серые вершины --- ровно те, что лежат в стеке
+                    let start = calls
+                        .iter()
+                        .position(|&(index, _, _)| index == to)
+                        .expect("серая вершина лежит в стеке");
+                    let cycle = calls[start..].iter().map(|&(index, _, _)| index).collect();
+                    return Ok(TopologicalOrder::Cycle(cycle));
+                }
+                ColorNode::Black => {}
+            }
+        }
+    }
+
+    finished.reverse();
+    Ok(TopologicalOrder::Order(finished))
+}
+
+/// Топологический порядок ациклического орграфа
+fn dag_order<G: GraphView>(graph: &G) -> Result<Vec<Index>> {
+    Ok(topological_sort_kahn(graph)?.into_order()?)
+}
+
+/// Вершины без входящих ребер (истоки), по возрастанию номера
+pub fn sources<G: GraphView>(graph: &G) -> Vec<Index> {
+    let mut has_incoming: HashSet<Index> = HashSet::new();
+    for from in graph.node_indices() {
+        has_incoming.extend(successors(graph, from));
+    }
+    sorted_nodes(graph)
+        .into_iter()
+        .filter(|index| !has_incoming.contains(index))
+        .collect()
+}
+
+/// Вершины без исходящих ребер (стоки), по возрастанию номера
+pub fn sinks<G: GraphView>(graph: &G) -> Vec<Index> {
+    sorted_nodes(graph)
+        .into_iter()
+        .filter(|&index| successors(graph, index).is_empty())
+        .collect()
+}
+
+/// Критический путь ациклического орграфа, где ребро --- работа, а его
+/// вес --- ее длительность.
+///
+/// Для каждой вершины (события) хранятся раннее время наступления
+/// (длина самого длинного пути, ведущего в нее) и позднее (насколько можно
+/// задержать событие, не удлинив весь проект).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriticalPath<W> {
+    length: W,
+    path: Vec<Index>,
+    earliest: HashMap<Index, W>,
+    latest: HashMap<Index, W>,
+}
+
+impl<W: Weight> CriticalPath<W> {
+    /// Длина критического (самого длинного) пути
+    pub fn length(&self) -> W {
+        self.length
+    }
+
+    /// Вершины критического пути от начала до конца
+    pub fn path(&self) -> &[Index] {
+        &self.path
+    }
+
+    /// Раннее время наступления события index
+    pub fn earliest(&self, index: Index) -> Option<W> {
+        self.earliest.get(&index).copied()
+    }
+
+    /// Позднее время наступления события index
+    pub fn latest(&self, index: Index) -> Option<W> {
+        self.latest.get(&index).copied()
+    }
+
+    /// Резерв времени события: на сколько его можно задержать. У вершин
+    /// критического пути резерв нулевой.
+    pub fn slack(&self, index: Index) -> Option<W> {
+        self.latest(index)?.checked_sub(self.earliest(index)?)
+    }
+}
+
+/// Критический путь: самый длинный путь ациклического орграфа (начинается
+/// в любой вершине) вместе с ранними и поздними временами событий.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф неориентированный, в нем есть цикл или
+/// длина пути переполнилась.
+pub fn critical_path<G: GraphView>(graph: &G) -> Result<CriticalPath<G::Weight>> {
+    let order = dag_order(graph)?;
+
+    let mut earliest: HashMap<Index, G::Weight> = order
+        .iter()
+        .map(|&index| (index, G::Weight::ZERO))
+        .collect();
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+    for &from in &order {
+        let start = earliest[&from];
+        for (to, weight) in graph.neighbors(from) {
+            let finish = start.try_add(weight)?;
+            if earliest.get(&to).is_some_and(|&old| finish > old) {
+                earliest.insert(to, finish);
+                parent.insert(to, from);
+            }
+        }
+    }
+
+    // Конец пути --- вершина с наибольшим ранним временем (при равенстве
+    // --- с меньшим номером)
+    let end = order
+        .iter()
+        .copied()
+        .max_by_key(|&index| (earliest[&index], Reverse(index)));
+    let length = end.map_or(G::Weight::ZERO, |index| earliest[&index]);
+    let mut path = Vec::new();
+    let mut current = end;
+    while let Some(index) = current {
+        path.push(index);
+        current = parent.get(&index).copied();
+    }
+    path.reverse();
+
+    let mut latest: HashMap<Index, G::Weight> = HashMap::new();
+    for &from in order.iter().rev() {
+        let mut time = length;
+        for (to, weight) in graph.neighbors(from) {
+            let Some(&next) = latest.get(&to) else {
+                continue;
+            };
+            let start = next.checked_sub(weight).ok_or_else(|| {
+                GraphError::new(
+                    GraphKindError::WeightOverflow,
+                    &format!("{next} - {weight}"),
+                )
+            })?;
+            time = time.min(start);
+        }
+        latest.insert(from, time);
+    }
+
+    Ok(CriticalPath {
+        length,
+        path,
+        earliest,
+        latest,
+    })
+}
+
+/// Самый длинный путь из `source` в `target` ациклического орграфа;
+/// [`None`], если `target` недостижима.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет, граф неориентированный,
+/// в нем есть цикл или длина пути переполнилась.
+pub fn longest_path<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Index,
+) -> Result<Option<(G::Weight, Vec<Index>)>> {
+    check_nodes(graph, source, target)?;
+    let order = dag_order(graph)?;
+
+    let mut best: HashMap<Index, G::Weight> = HashMap::from([(source, G::Weight::ZERO)]);
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+    for &from in &order {
+        let Some(&start) = best.get(&from) else {
+            continue;
+        };
+        for (to, weight) in graph.neighbors(from) {
+            let finish = start.try_add(weight)?;
+            if best.get(&to).is_none_or(|&old| finish > old) {
+                best.insert(to, finish);
+                parent.insert(to, from);
+            }
+        }
+    }
+
+    let Some(&length) = best.get(&target) else {
+        return Ok(None);
+    };
+    let mut path = vec![target];
+    let mut current = target;
+    while current != source {
+        current = parent[&current];
+        path.push(current);
+    }
+    path.reverse();
+    Ok(Some((length, path)))
+}
+
+/// Число различных путей из `from` в `to` ациклического орграфа
+/// (параллельные ребра дают разные пути; путь из вершины в себя один ---
+/// пустой).
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет, граф неориентированный,
+/// в нем есть цикл или число путей не помещается в `u128`.
+pub fn count_paths<G: GraphView>(graph: &G, from: Index, to: Index) -> Result<u128> {
+    check_nodes(graph, from, to)?;
+    let order = dag_order(graph)?;
+
+    let mut ways: HashMap<Index, u128> = HashMap::from([(from, 1)]);
+    for &node in &order {
+        let Some(&count) = ways.get(&node) else {
+            continue;
+        };
+        for next in successors(graph, node) {
+            let total = ways.entry(next).or_default();
+            *total = total.checked_add(count).ok_or_else(|| {
+                GraphError::new(
+                    GraphKindError::WeightOverflow,
+                    &format!("число путей в вершину {next}"),
+                )
+            })?;
+        }
+    }
+
+    Ok(ways.get(&to).copied().unwrap_or(0))
+}
+
+fn check_nodes<G: GraphView>(graph: &G, source: Index, target: Index) -> Result<()> {
+    for index in [source, target] {
+        if !graph.contains_node(index) {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::NodeNotFound,
+                &format!("вершина {index}"),
+            )));
+        }
+    }
+    Ok(())
+}
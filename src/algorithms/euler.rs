@@ -0,0 +1,335 @@
+//! Эйлеровы циклы и пути: проверка условий существования и построение
+//! алгоритмом Хирхольцера.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Debug,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::components::sorted_nodes,
+    graph::{
+        core::{EdgeId, Graph, GraphError, GraphKindError, Index},
+        union_find::UnionFind,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Ребро в порядке обхода: `from -> to` --- направление, в котором по нему
+/// прошли (для неориентированного графа может не совпадать с записью в
+/// списках смежности).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrailEdge<W> {
+    /// id ребра в исходном графе
+    pub id: EdgeId,
+    pub from: Index,
+    pub to: Index,
+    pub weight: W,
+}
+
+/// Почему в графе нет эйлерова пути
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EulerObstruction {
+    /// Вершины, нарушающие условие на степени: в неориентированном графе
+    /// --- вершины нечетной степени (их больше двух), в орграфе ---
+    /// вершины, у которых полустепени исхода и захода различаются
+    DegreeImbalance(Vec<Index>),
+    /// Ребра лежат в разных компонентах слабой связности; вершины
+    /// компонент, в которых есть ребра
+    Disconnected(Vec<Vec<Index>>),
+}
+
+/// Есть ли в графе эйлеров цикл или путь.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Eulerian {
+    /// Есть эйлеров цикл (в том числе в графе без ребер)
+    Circuit,
+    /// Есть эйлеров путь из `start` в `end`, но не цикл
+    Path {
+        start: Index,
+        end: Index,
+    },
+    NotEulerian(EulerObstruction),
+}
+
+impl Eulerian {
+    pub fn is_circuit(&self) -> bool {
+        matches!(self, Eulerian::Circuit)
+    }
+
+    /// Есть ли эйлеров путь (цикл тоже считается)
+    pub fn has_trail(&self) -> bool {
+        !matches!(self, Eulerian::NotEulerian(_))
+    }
+
+    /// Ошибка [`GraphKindError::GraphMustBeEulerian`] с причиной, если
+    /// эйлерова пути нет
+    pub fn check(&self) -> std::result::Result<(), GraphError> {
+        let join = |nodes: &[Index]| {
+            nodes
+                .iter()
+                .map(|index| index.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let description = match self {
+            Eulerian::NotEulerian(EulerObstruction::DegreeImbalance(nodes)) => {
+                format!("не сбалансированы степени вершин {}", join(nodes))
+            }
+            Eulerian::NotEulerian(EulerObstruction::Disconnected(components)) => format!(
+                "ребра лежат в разных компонентах: {}",
+                components
+                    .iter()
+                    .map(|component| format!("{{{}}}", join(component)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => return Ok(()),
+        };
+        Err(GraphError::new(
+            GraphKindError::GraphMustBeEulerian,
+            &description,
+        ))
+    }
+}
+
+/// Эйлеров путь или цикл: ребра по порядку обхода.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EulerTrail<W> {
+    edges: Vec<TrailEdge<W>>,
+    total: W,
+}
+
+impl<W: Weight> EulerTrail<W> {
+    /// Ребра по порядку обхода; конец каждого --- начало следующего
+    pub fn edges(&self) -> &[TrailEdge<W>] {
+        &self.edges
+    }
+
+    /// Вершины по порядку обхода, на одну больше, чем ребер (для цикла
+    /// первая повторяется в конце). Для графа без ребер пусто
+    pub fn nodes(&self) -> Vec<Index> {
+        self.edges
+            .first()
+            .map(|edge| edge.from)
+            .into_iter()
+            .chain(self.edges.iter().map(|edge| edge.to))
+            .collect()
+    }
+
+    /// Путь замкнут, то есть это цикл
+    pub fn is_closed(&self) -> bool {
+        self.edges.first().map(|edge| edge.from) == self.edges.last().map(|edge| edge.to)
+    }
+
+    /// Суммарный вес ребер
+    pub fn total(&self) -> W {
+        self.total
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+/// Все ребра графа по возрастанию id, половины неориентированного ребра
+/// берутся один раз
+pub(super) fn graph_edges<T, W>(graph: &Graph<T, W>) -> Vec<TrailEdge<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let mut seen: HashSet<EdgeId> = HashSet::new();
+    let mut edges = Vec::new();
+    for from in sorted_nodes(graph) {
+        for edge in graph.get_adjacency(&from).into_iter().flatten() {
+            if graph.get_is_directed() || seen.insert(edge.id) {
+                edges.push(TrailEdge {
+                    id: edge.id,
+                    from,
+                    to: edge.node.number,
+                    weight: edge.weight,
+                });
+            }
+        }
+    }
+    edges.sort_by_key(|edge| edge.id);
+    edges
+}
+
+/// Разность степеней каждой вершины: в орграфе исход минус заход, в
+/// неориентированном графе --- степень (петля дает два)
+fn degree_balance<W>(edges: &[TrailEdge<W>], directed: bool) -> HashMap<Index, i64> {
+    let mut balance: HashMap<Index, i64> = HashMap::new();
+    for edge in edges {
+        if directed {
+            *balance.entry(edge.from).or_default() += 1;
+            *balance.entry(edge.to).or_default() -= 1;
+        } else {
+            *balance.entry(edge.from).or_default() += 1;
+            *balance.entry(edge.to).or_default() += 1;
+        }
+    }
+    balance
+}
+
+/// Проверка условий существования эйлерова пути для набора ребер (ребро
+/// может входить несколько раз). Сначала проверяются степени, затем
+/// связность
+pub(super) fn classify<W>(edges: &[TrailEdge<W>], directed: bool) -> Eulerian {
+    let balance = degree_balance(edges, directed);
+    let mut unbalanced: Vec<(Index, i64)> = balance
+        .into_iter()
+        .filter(|&(_, value)| if directed { value != 0 } else { value % 2 != 0 })
+        .collect();
+    unbalanced.sort();
+
+    let path = match unbalanced.as_slice() {
+        [] => None,
+        // Неориентированный: путь между двумя вершинами нечетной степени
+        [(start, _), (end, _)] if !directed => Some((*start, *end)),
+        // Орграф: из вершины с лишним исходящим в вершину с лишним входящим
+        [(first, 1), (second, -1)] => Some((*first, *second)),
+        [(first, -1), (second, 1)] => Some((*second, *first)),
+        _ => {
+            return Eulerian::NotEulerian(EulerObstruction::DegreeImbalance(
+                unbalanced.into_iter().map(|(index, _)| index).collect(),
+            ));
+        }
+    };
+
+    let mut sets = UnionFind::new();
+    for edge in edges {
+        sets.union(edge.from, edge.to);
+    }
+    if sets.component_count() > 1 {
+        return Eulerian::NotEulerian(EulerObstruction::Disconnected(sets.components()));
+    }
+
+    match path {
+        None => Eulerian::Circuit,
+        Some((start, end)) => Eulerian::Path { start, end },
+    }
+}
+
+/// Алгоритм Хирхольцера: обход из `start` по неиспользованным ребрам, в
+/// тупике ребро снимается со стека в ответ. Условия существования пути
+/// должны быть уже проверены ([`classify`]). Из вершины ребра берутся по
+/// порядку в `edges`
+pub(super) fn build_trail<W: Weight>(
+    edges: &[TrailEdge<W>],
+    directed: bool,
+    start: Index,
+) -> Result<EulerTrail<W>> {
+    let mut incident: HashMap<Index, Vec<usize>> = HashMap::new();
+    for (position, edge) in edges.iter().enumerate() {
+        incident.entry(edge.from).or_default().push(position);
+        if !directed && edge.to != edge.from {
+            incident.entry(edge.to).or_default().push(position);
+        }
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut next: HashMap<Index, usize> = HashMap::new();
+    // Вершина и ребро, по которому в нее пришли
+    let mut stack: Vec<(Index, Option<TrailEdge<W>>)> = vec![(start, None)];
+    let mut trail = Vec::with_capacity(edges.len());
+    while let Some(&(node, _)) = stack.last() {
+        let candidates = incident.get(&node).map_or(&[][..], Vec::as_slice);
+        let pointer = next.entry(node).or_default();
+        while *pointer < candidates.len() && used[candidates[*pointer]] {
+            *pointer += 1;
+        }
+        match candidates.get(*pointer) {
+            Some(&position) => {
+                used[position] = true;
+                let edge = edges[position];
+                let to = if edge.from == node {
+                    edge.to
+                } else {
+                    edge.from
+                };
+                stack.push((
+                    to,
+                    Some(TrailEdge {
+                        from: node,
+                        to,
+                        ..edge
+                    }),
+                ));
+            }
+            None => {
+                if let Some((_, Some(edge))) = stack.pop() {
+                    trail.push(edge);
+                }
+            }
+        }
+    }
+    trail.reverse();
+
+    let mut total = W::ZERO;
+    for edge in &trail {
+        total = total.try_add(edge.weight)?;
+    }
+    Ok(EulerTrail {
+        edges: trail,
+        total,
+    })
+}
+
+/// Есть ли в графе эйлеров цикл или путь, а если нет --- почему.
+///
+/// В неориентированном графе цикл есть, когда степени всех вершин четны,
+/// путь --- когда нечетных ровно две (путь идет от меньшей из них). В
+/// орграфе для цикла у каждой вершины полустепени исхода и захода равны,
+/// для пути у одной вершины исходящих на одно больше, у другой на одно
+/// меньше. Кроме того, все ребра должны лежать в одной компоненте слабой
+/// связности; изолированные вершины не мешают.
+pub fn eulerian<T, W>(graph: &Graph<T, W>) -> Eulerian
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    classify(&graph_edges(graph), graph.get_is_directed())
+}
+
+/// Эйлеров цикл, если он есть, иначе эйлеров путь, построенный
+/// алгоритмом Хирхольцера за `O(V + E)`. Цикл начинается в наименьшей
+/// вершине с ребрами. Каждое ребро (в мультиграфе --- каждое из
+/// параллельных, петли тоже) проходится ровно один раз.
+///
+/// # Errors
+/// Вернет [`GraphError`] с причиной из [`eulerian`], если эйлерова пути
+/// нет, или если переполнился суммарный вес.
+pub fn hierholzer<T, W>(graph: &Graph<T, W>) -> Result<EulerTrail<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let edges = graph_edges(graph);
+    let directed = graph.get_is_directed();
+    let kind = classify(&edges, directed);
+    kind.check()?;
+    let start = match kind {
+        Eulerian::Path { start, .. } => start,
+        _ => match edges.iter().map(|edge| edge.from.min(edge.to)).min() {
+            Some(start) => start,
+            None => {
+                return Ok(EulerTrail {
+                    edges: Vec::new(),
+                    total: W::ZERO,
+                });
+            }
+        },
+    };
+    build_trail(&edges, directed, start)
+}
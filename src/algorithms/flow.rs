@@ -0,0 +1,657 @@
+//! Алгоритмы максимального потока за общим интерфейсом: Эдмондс–Карп из
+//! задания 11, Диниц и проталкивание предпотока (FIFO и по наибольшей
+//! высоте).
+
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::Display,
+};
+
+use crate::{
+    algorithms::components::sorted_nodes,
+    graph::{
+        core::{GraphError, GraphKindError, Index},
+        view::GraphView,
+        weight::{Weight, ensure_non_negative},
+    },
+    tasks::task_11::{FlowNetwork, task_11_network},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Алгоритм поиска максимального потока.
+///
+/// Результат --- [`FlowNetwork`] с найденным потоком, поэтому поток по
+/// ребрам и минимальный разрез получаются одинаково для любого алгоритма.
+pub trait MaxFlowSolver {
+    /// Название алгоритма
+    fn name(&self) -> &'static str;
+
+    /// Максимальный поток из `source` в `sink`
+    ///
+    /// # Errors
+    /// Вернет [`GraphError`], если одной из вершин нет, граф
+    /// неориентированный, в нем есть ребро отрицательного веса или поток
+    /// переполнился.
+    fn solve<G: GraphView>(
+        &self,
+        graph: &G,
+        source: Index,
+        sink: Index,
+    ) -> Result<FlowNetwork<G::Weight>>;
+}
+
+/// Правило выбора активной вершины в алгоритме проталкивания предпотока
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionRule {
+    /// Очередь: вершина становится в конец, когда у нее появляется избыток
+    Fifo,
+    /// Сначала вершина с наибольшей высотой
+    HighestLabel,
+}
+
+/// Алгоритм Эдмондса–Карпа (задание 11): кратчайшие увеличивающие пути
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EdmondsKarp;
+
+/// Алгоритм Диница: слоистая сеть и блокирующий поток в ней
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dinic;
+
+/// Проталкивание предпотока (Голдберг–Тарьян)
+#[derive(Debug, Clone, Copy)]
+pub struct PushRelabel {
+    /// Какую активную вершину разряжать следующей
+    pub rule: SelectionRule,
+}
+
+/// Выбор алгоритма во время выполнения
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxFlowAlgorithm {
+    EdmondsKarp,
+    Dinic,
+    PushRelabelFifo,
+    PushRelabelHighestLabel,
+}
+
+impl MaxFlowAlgorithm {
+    /// Все реализованные алгоритмы
+    pub const ALL: [MaxFlowAlgorithm; 4] = [
+        MaxFlowAlgorithm::EdmondsKarp,
+        MaxFlowAlgorithm::Dinic,
+        MaxFlowAlgorithm::PushRelabelFifo,
+        MaxFlowAlgorithm::PushRelabelHighestLabel,
+    ];
+}
+
+impl MaxFlowSolver for EdmondsKarp {
+    fn name(&self) -> &'static str {
+        "edmonds-karp"
+    }
+
+    fn solve<G: GraphView>(
+        &self,
+        graph: &G,
+        source: Index,
+        sink: Index,
+    ) -> Result<FlowNetwork<G::Weight>> {
+        check_network(graph, source, sink)?;
+        task_11_network(graph, source, sink)
+    }
+}
+
+impl MaxFlowSolver for Dinic {
+    fn name(&self) -> &'static str {
+        "dinic"
+    }
+
+    fn solve<G: GraphView>(
+        &self,
+        graph: &G,
+        source: Index,
+        sink: Index,
+    ) -> Result<FlowNetwork<G::Weight>> {
+        check_network(graph, source, sink)?;
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.positions[&source], residual.positions[&sink]);
+        if s != t {
+            while let Some(level) = residual.levels(s, t) {
+                residual.blocking_flow(s, t, &level)?;
+            }
+        }
+        residual.into_network(graph, source)
+    }
+}
+
+impl MaxFlowSolver for PushRelabel {
+    fn name(&self) -> &'static str {
+        match self.rule {
+            SelectionRule::Fifo => "push-relabel-fifo",
+            SelectionRule::HighestLabel => "push-relabel-highest-label",
+        }
+    }
+
+    fn solve<G: GraphView>(
+        &self,
+        graph: &G,
+        source: Index,
+        sink: Index,
+    ) -> Result<FlowNetwork<G::Weight>> {
+        check_network(graph, source, sink)?;
+        let mut residual = Residual::new(graph);
+        let (s, t) = (residual.positions[&source], residual.positions[&sink]);
+        if s != t {
+            residual.push_relabel(s, t, self.rule)?;
+        }
+        residual.into_network(graph, source)
+    }
+}
+
+impl MaxFlowSolver for MaxFlowAlgorithm {
+    fn name(&self) -> &'static str {
+        match self {
+            MaxFlowAlgorithm::EdmondsKarp => EdmondsKarp.name(),
+            MaxFlowAlgorithm::Dinic => Dinic.name(),
+            MaxFlowAlgorithm::PushRelabelFifo => PushRelabel {
+                rule: SelectionRule::Fifo,
+            }
+            .name(),
+            MaxFlowAlgorithm::PushRelabelHighestLabel => PushRelabel {
+                rule: SelectionRule::HighestLabel,
+            }
+            .name(),
+        }
+    }
+
+    fn solve<G: GraphView>(
+        &self,
+        graph: &G,
+        source: Index,
+        sink: Index,
+    ) -> Result<FlowNetwork<G::Weight>> {
+        match self {
+            MaxFlowAlgorithm::EdmondsKarp => EdmondsKarp.solve(graph, source, sink),
+            MaxFlowAlgorithm::Dinic => Dinic.solve(graph, source, sink),
+            MaxFlowAlgorithm::PushRelabelFifo => PushRelabel {
+                rule: SelectionRule::Fifo,
+            }
+            .solve(graph, source, sink),
+            MaxFlowAlgorithm::PushRelabelHighestLabel => PushRelabel {
+                rule: SelectionRule::HighestLabel,
+            }
+            .solve(graph, source, sink),
+        }
+    }
+}
+
+/// Алгоритмы максимального потока нашли разные значения
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowMismatch {
+    values: Vec<(&'static str, String)>,
+}
+
+impl FlowMismatch {
+    /// Название алгоритма и найденное им значение
+    pub fn values(&self) -> &[(&'static str, String)] {
+        &self.values
+    }
+}
+
+impl Display for FlowMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+        write!(f, "max flow algorithms disagree: {}", values.join(", "))
+    }
+}
+
+impl Error for FlowMismatch {}
+
+/// Запускает все алгоритмы из [`MaxFlowAlgorithm::ALL`] и проверяет, что
+/// величины потока совпадают.
+///
+/// # Errors
+/// Вернет [`FlowMismatch`], если значения различаются, или ошибку
+/// алгоритма.
+pub fn check_max_flow<G: GraphView>(graph: &G, source: Index, sink: Index) -> Result<G::Weight> {
+    let mut values: Vec<(&'static str, G::Weight)> = Vec::new();
+    for algorithm in MaxFlowAlgorithm::ALL {
+        let network = algorithm.solve(graph, source, sink)?;
+        values.push((algorithm.name(), network.value()));
+    }
+
+    let first = values[0].1;
+    if values.iter().all(|&(_, value)| value == first) {
+        Ok(first)
+    } else {
+        Err(Box::new(FlowMismatch {
+            values: values
+                .into_iter()
+                .map(|(name, value)| (name, value.to_string()))
+                .collect(),
+        }))
+    }
+}
+
+fn check_network<G: GraphView>(graph: &G, source: Index, sink: Index) -> Result<()> {
+    if !graph.is_directed() {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::GraphMustBeDirected,
+            "поток ищется в сети (орграфе)",
+        )));
+    }
+    for index in [source, sink] {
+        if !graph.contains_node(index) {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::NodeNotFound,
+                &format!("вершина {index}"),
+            )));
+        }
+    }
+    ensure_non_negative(graph)?;
+    Ok(())
+}
+
+/// Остаточная сеть на плотных номерах вершин. Дуги хранятся парами:
+/// дуга `a ^ 1` --- обратная к дуге `a`, у исходных ребер четные номера.
+struct Residual<W> {
+    indices: Vec<Index>,
+    positions: HashMap<Index, usize>,
+    heads: Vec<usize>,
+    capacity: Vec<W>,
+    residual: Vec<W>,
+    arcs: Vec<Vec<usize>>,
+}
+
+impl<W: Weight> Residual<W> {
+    fn new<G: GraphView<Weight = W>>(graph: &G) -> Self {
+        let mut indices = sorted_nodes(graph);
+        // Концы ребер, которых нет среди вершин, тоже участвуют в сети
+        for from in sorted_nodes(graph) {
+            for (to, _) in graph.neighbors(from) {
+                if !graph.contains_node(to) {
+                    indices.push(to);
+                }
+            }
+        }
+        indices.sort();
+        indices.dedup();
+        let positions: HashMap<Index, usize> = indices
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+
+        let mut residual = Self {
+            arcs: vec![Vec::new(); indices.len()],
+            indices,
+            positions,
+            heads: Vec::new(),
+            capacity: Vec::new(),
+            residual: Vec::new(),
+        };
+        for from in sorted_nodes(graph) {
+            for (to, weight) in graph.neighbors(from) {
+                // Поток по петле ничего не меняет
+                if from == to {
+                    continue;
+                }
+                let (from, to) = (residual.positions[&from], residual.positions[&to]);
+                residual.add_arc(from, to, weight);
+                residual.add_arc(to, from, W::ZERO);
+            }
+        }
+        residual
+    }
+
+    fn add_arc(&mut self, from: usize, to: usize, capacity: W) {
+        self.arcs[from].push(self.heads.len());
+        self.heads.push(to);
+        self.capacity.push(capacity);
+        self.residual.push(capacity);
+    }
+
+    /// Пускает amount по дуге arc
+    fn push(&mut self, arc: usize, amount: W) -> Result<()> {
+        self.residual[arc] = self.residual[arc].checked_sub(amount).ok_or_else(|| {
+            GraphError::new(
+                GraphKindError::WeightOverflow,
+                &format!("{} - {amount}", self.residual[arc]),
+            )
+        })?;
+        self.residual[arc ^ 1] = self.residual[arc ^ 1].try_add(amount)?;
+        Ok(())
+    }
+
+    /// Уровни вершин (расстояния от s по дугам с ненулевой остаточной
+    /// пропускной способностью); [`None`], если t недостижима
+    fn levels(&self, s: usize, t: usize) -> Option<Vec<usize>> {
+        let mut level = vec![usize::MAX; self.indices.len()];
+        level[s] = 0;
+        let mut queue = VecDeque::from([s]);
+        while let Some(from) = queue.pop_front() {
+            for &arc in &self.arcs[from] {
+                let to = self.heads[arc];
+                if self.residual[arc] > W::ZERO && level[to] == usize::MAX {
+                    level[to] = level[from] + 1;
+                    queue.push_back(to);
+                }
+            }
+        }
+        (level[t] != usize::MAX).then_some(level)
+    }
+
+    /// Блокирующий поток в слоистой сети обходом в глубину без рекурсии.
+    /// `next[v]` --- первая дуга v, которая еще может вести в t.
+    fn blocking_flow(&mut self, s: usize, t: usize, level: &[usize]) -> Result<()> {
+        let mut level = level.to_vec();
+        let mut next = vec![0; self.indices.len()];
+        let mut path: Vec<usize> = Vec::new();
+        let mut node = s;
+
+        loop {
+            if node == t {
+                let amount = path
+                    .iter()
+                    .map(|&arc| self.residual[arc])
+                    .min()
+                    .unwrap_or(W::ZERO);
+                for &arc in &path {
+                    self.push(arc, amount)?;
+                }
+                path.clear();
+                node = s;
+                continue;
+            }
+
+            let advance = self.arcs[node][next[node]..].iter().position(|&arc| {
+                self.residual[arc] > W::ZERO && level[self.heads[arc]] == level[node] + 1
+            });
+            match advance {
+                Some(skipped) => {
+                    next[node] += skipped;
+                    let arc = self.arcs[node][next[node]];
+                    path.push(arc);
+                    node = self.heads[arc];
+                }
+                None => {
+                    // Из node в t не пройти: убираем ее из слоистой сети
+                    next[node] = self.arcs[node].len();
+                    level[node] = usize::MAX;
+                    let Some(arc) = path.pop() else {
+                        return Ok(());
+                    };
+                    node = self.heads[arc ^ 1];
+                    next[node] += 1;
+                }
+            }
+        }
+    }
+
+    /// Точные высоты: расстояние до t по дугам с ненулевым остатком.
+    /// Вершины, из которых t недостижима, и исток получают высоту n.
+    fn global_heights(&self, s: usize, t: usize) -> Vec<usize> {
+        let n = self.indices.len();
+        let mut height = vec![n; n];
+        height[t] = 0;
+        let mut queue = VecDeque::from([t]);
+        while let Some(to) = queue.pop_front() {
+            for &arc in &self.arcs[to] {
+                // Обратная дуга arc ^ 1 ведет из from в to
+                let from = self.heads[arc];
+                if from != s && height[from] == n && self.residual[arc ^ 1] > W::ZERO {
+                    height[from] = height[to] + 1;
+                    queue.push_back(from);
+                }
+            }
+        }
+        height[s] = n;
+        height
+    }
+
+    fn push_relabel(&mut self, s: usize, t: usize, rule: SelectionRule) -> Result<()> {
+        let n = self.indices.len();
+        let mut excess = vec![W::ZERO; n];
+        let mut current = vec![0; n];
+        let mut active = Active::new(rule, n);
+
+        for pos in 0..self.arcs[s].len() {
+            let arc = self.arcs[s][pos];
+            let amount = self.residual[arc];
+            if amount > W::ZERO {
+                self.push(arc, amount)?;
+                let to = self.heads[arc];
+                excess[to] = excess[to].try_add(amount)?;
+            }
+        }
+
+        // Высоты время от времени пересчитываются заново: без этого
+        // вершины поднимаются по одной единице и работа растет в разы
+        let mut height = self.global_heights(s, t);
+        let mut relabels = 0;
+        active.refill(&excess, &height, s, t);
+
+        while let Some(node) = active.pop() {
+            // Разрядка: проталкиваем избыток, пока он есть
+            while excess[node] > W::ZERO {
+                let Some(&arc) = self.arcs[node].get(current[node]) else {
+                    // Допустимых дуг нет: поднимаем вершину. Дуга с
+                    // остатком есть всегда --- хотя бы обратно к истоку
+                    height[node] = self.arcs[node]
+                        .iter()
+                        .filter(|&&arc| self.residual[arc] > W::ZERO)
+                        .map(|&arc| height[self.heads[arc]] + 1)
+                        .min()
+                        .unwrap_or(2 * n - 1);
+                    current[node] = 0;
+                    relabels += 1;
+                    continue;
+                };
+
+                let to = self.heads[arc];
+                if self.residual[arc] > W::ZERO && height[node] == height[to] + 1 {
+                    let amount = excess[node].min(self.residual[arc]);
+                    self.push(arc, amount)?;
+                    excess[node] = excess[node].checked_sub(amount).unwrap_or(W::ZERO);
+                    if excess[to] == W::ZERO && to != s && to != t {
+                        active.insert(to, height[to]);
+                    }
+                    excess[to] = excess[to].try_add(amount)?;
+                } else {
+                    current[node] += 1;
+                }
+            }
+
+            if relabels >= n {
+                relabels = 0;
+                height = self.global_heights(s, t);
+                current.fill(0);
+                active.refill(&excess, &height, s, t);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Переносит поток по каждому ребру в [`FlowNetwork`]
+    fn into_network<G: GraphView<Weight = W>>(
+        self,
+        graph: &G,
+        source: Index,
+    ) -> Result<FlowNetwork<W>> {
+        let flows = (0..self.heads.len()).step_by(2).filter_map(|arc| {
+            let amount = self.capacity[arc].checked_sub(self.residual[arc])?;
+            let from = self.indices[self.heads[arc ^ 1]];
+            let to = self.indices[self.heads[arc]];
+            (amount > W::ZERO).then_some((from, to, amount))
+        });
+        FlowNetwork::new(graph)?.with_flows(flows, source)
+    }
+}
+
+/// Множество активных вершин (с положительным избытком)
+enum Active {
+    Fifo(VecDeque<usize>),
+    /// Вершины, разложенные по высоте, и наибольшая непустая высота
+    HighestLabel {
+        buckets: Vec<Vec<usize>>,
+        top: usize,
+    },
+}
+
+impl Active {
+    fn new(rule: SelectionRule, n: usize) -> Self {
+        match rule {
+            SelectionRule::Fifo => Active::Fifo(VecDeque::new()),
+            // Высота вершины с избытком не превосходит 2n - 1
+            SelectionRule::HighestLabel => Active::HighestLabel {
+                buckets: vec![Vec::new(); 2 * n],
+                top: 0,
+            },
+        }
+    }
+
+    fn insert(&mut self, node: usize, height: usize) {
+        match self {
+            Active::Fifo(queue) => queue.push_back(node),
+            Active::HighestLabel { buckets, top } => {
+                buckets[height].push(node);
+                *top = (*top).max(height);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        match self {
+            Active::Fifo(queue) => queue.pop_front(),
+            Active::HighestLabel { buckets, top } => loop {
+                if let Some(node) = buckets[*top].pop() {
+                    return Some(node);
+                }
+                if *top == 0 {
+                    return None;
+                }
+                *top -= 1;
+            },
+        }
+    }
+
+    /// Заново заполняет множество после пересчета высот
+    fn refill<W: Weight>(&mut self, excess: &[W], height: &[usize], s: usize, t: usize) {
+        match self {
+            Active::Fifo(queue) => queue.clear(),
+            Active::HighestLabel { buckets, top } => {
+                buckets.iter_mut().for_each(Vec::clear);
+                *top = 0;
+            }
+        }
+        for node in 0..excess.len() {
+            if node != s && node != t && excess[node] > W::ZERO {
+                self.insert(node, height[node]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::test_support::{assert_conserved, graph};
+
+    /// Все алгоритмы дают `expected`; поток каждого допустим, а
+    /// минимальный разрез равен его величине
+    fn check(n: u32, edges: &[(u32, u32, i64)], source: u32, sink: u32, expected: i64) {
+        let graph = graph(n, true, edges);
+        let (source, sink) = (Index(source), Index(sink));
+        assert_eq!(check_max_flow(&graph, source, sink).unwrap(), expected);
+
+        for algorithm in MaxFlowAlgorithm::ALL {
+            let network = algorithm.solve(&graph, source, sink).unwrap();
+            for (_, _, flow, capacity) in network.edge_flows() {
+                assert!((0..=capacity).contains(&flow), "{}", algorithm.name());
+            }
+            assert_conserved(
+                network
+                    .edge_flows()
+                    .into_iter()
+                    .map(|(from, to, flow, _)| (from, to, flow)),
+                source,
+                sink,
+                expected,
+            );
+            let cut = network.min_cut(source).unwrap();
+            assert_eq!(cut.capacity(), expected, "{}", algorithm.name());
+        }
+    }
+
+    #[test]
+    fn classic_network_with_antiparallel_arcs() {
+        check(
+            6,
+            &[
+                (1, 2, 16),
+                (1, 3, 13),
+                (2, 3, 10),
+                (3, 2, 4),
+                (2, 4, 12),
+                (4, 3, 9),
+                (3, 5, 14),
+                (5, 4, 7),
+                (4, 6, 20),
+                (5, 6, 4),
+            ],
+            1,
+            6,
+            23,
+        );
+    }
+
+    #[test]
+    fn antiparallel_arcs_both_usable() {
+        check(
+            4,
+            &[
+                (1, 2, 10),
+                (1, 3, 10),
+                (2, 3, 5),
+                (3, 2, 5),
+                (2, 4, 4),
+                (3, 4, 15),
+            ],
+            1,
+            4,
+            19,
+        );
+        // Встречная дуга в исток не мешает
+        check(3, &[(1, 2, 5), (2, 1, 5), (2, 3, 3), (1, 3, 2)], 1, 3, 5);
+    }
+
+    #[test]
+    fn parallel_arcs_add_up() {
+        check(3, &[(1, 2, 3), (1, 2, 4), (2, 3, 5), (2, 3, 1)], 1, 3, 6);
+        check(
+            4,
+            &[
+                (1, 2, 2),
+                (1, 2, 2),
+                (1, 2, 2),
+                (2, 1, 7),
+                (2, 3, 3),
+                (2, 4, 2),
+                (3, 4, 9),
+            ],
+            1,
+            4,
+            5,
+        );
+    }
+
+    #[test]
+    fn unreachable_sink() {
+        check(4, &[(1, 2, 5), (3, 4, 5), (4, 2, 1)], 1, 4, 0);
+    }
+}
@@ -0,0 +1,287 @@
+//! Паросочетания: наибольшее паросочетание двудольного графа
+//! (Хопкрофт–Карп) и минимальное вершинное покрытие по теореме Кёнига.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+};
+
+use crate::{
+    algorithms::bipartite::bipartition,
+    graph::{
+        core::Index,
+        view::{GraphView, ensure_undirected},
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Паросочетание: множество ребер без общих концов.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Matching {
+    pairs: Vec<(Index, Index)>,
+    mates: HashMap<Index, Index>,
+}
+
+impl Matching {
+    /// Паросочетание из пар вершин; пары сортируются. Что пары --- ребра
+    /// графа без общих концов, проверяет
+    /// [`augmenting_path`](crate::algorithms::augmenting_path)
+    pub fn new(mut pairs: Vec<(Index, Index)>) -> Self {
+        pairs.sort();
+        let mates = pairs.iter().flat_map(|&(a, b)| [(a, b), (b, a)]).collect();
+        Self { pairs, mates }
+    }
+
+    /// Пары вершин по возрастанию. В двудольном графе первая вершина
+    /// пары лежит в первой доле
+    pub fn pairs(&self) -> &[(Index, Index)] {
+        &self.pairs
+    }
+
+    /// Число ребер в паросочетании
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Вершина, с которой сопоставлена `index`
+    pub fn mate(&self, index: Index) -> Option<Index> {
+        self.mates.get(&index).copied()
+    }
+
+    pub fn is_matched(&self, index: Index) -> bool {
+        self.mates.contains_key(&index)
+    }
+}
+
+/// Двудольный граф на плотных номерах: левая доля `0..left.len()`,
+/// правая `0..right.len()`
+struct BipartiteGraph {
+    left: Vec<Index>,
+    right: Vec<Index>,
+    // Соседи каждой левой вершины в правой доле, без повторов
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl BipartiteGraph {
+    fn new<G: GraphView>(graph: &G) -> Result<Self> {
+        ensure_undirected(graph, "паросочетание ищется в неориентированном графе")?;
+
+        let (left, right) = bipartition(graph).into_parts()?;
+        let positions: HashMap<Index, usize> = right
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+        let adjacency = left
+            .iter()
+            .map(|&from| {
+                let mut targets: Vec<usize> = graph
+                    .neighbors(from)
+                    .filter_map(|(to, _)| positions.get(&to).copied())
+                    .collect();
+                targets.sort();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        Ok(Self {
+            left,
+            right,
+            adjacency,
+        })
+    }
+}
+
+/// Состояние алгоритма Хопкрофта–Карпа
+struct HopcroftKarp<'a> {
+    graph: &'a BipartiteGraph,
+    mate_left: Vec<Option<usize>>,
+    mate_right: Vec<Option<usize>>,
+    // Слой левой вершины в последнем обходе в ширину
+    layer: Vec<Option<usize>>,
+}
+
+impl<'a> HopcroftKarp<'a> {
+    fn new(graph: &'a BipartiteGraph) -> Self {
+        Self {
+            graph,
+            mate_left: vec![None; graph.left.len()],
+            mate_right: vec![None; graph.right.len()],
+            layer: vec![None; graph.left.len()],
+        }
+    }
+
+    /// Раскладывает левые вершины по слоям от свободных и возвращает длину
+    /// кратчайшего увеличивающего пути (в левых вершинах), если он есть
+    fn layers(&mut self) -> Option<usize> {
+        let mut queue = VecDeque::new();
+        for (from, mate) in self.mate_left.iter().enumerate() {
+            self.layer[from] = if mate.is_none() {
+                queue.push_back(from);
+                Some(0)
+            } else {
+                None
+            };
+        }
+
+        let mut shortest = None;
+        while let Some(from) = queue.pop_front() {
+            let layer = self.layer[from].expect("вершина в очереди");
+            if shortest.is_some_and(|shortest| layer >= shortest) {
+                continue;
+            }
+            for &to in &self.graph.adjacency[from] {
+                match self.mate_right[to] {
+                    None => shortest = Some(shortest.map_or(layer, |old: usize| old.min(layer))),
+                    Some(next) if self.layer[next].is_none() => {
+                        self.layer[next] = Some(layer + 1);
+                        queue.push_back(next);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        shortest
+    }
+
+    /// Ищет увеличивающий путь по слоям из свободной вершины `root` обходом
+    /// в глубину без рекурсии и чередует вдоль него паросочетание
+    fn augment(&mut self, root: usize, shortest: usize, next: &mut [usize]) -> bool {
+        let mut stack = vec![root];
+        // Правая вершина, через которую пришли в каждую вершину стека
+        // (кроме корня)
+        let mut via: Vec<usize> = Vec::new();
+
+        while let Some(&from) = stack.last() {
+            let Some(&to) = self.graph.adjacency[from].get(next[from]) else {
+                // Тупик: в этой фазе через вершину пути нет
+                self.layer[from] = None;
+                stack.pop();
+                via.pop();
+                continue;
+            };
+            next[from] += 1;
+
+            let layer = self.layer[from].expect("вершина на стеке в слое");
+            match self.mate_right[to] {
+                None if layer == shortest => {
+                    via.push(to);
+                    for (&left, &right) in stack.iter().zip(&via) {
+                        self.mate_left[left] = Some(right);
+                        self.mate_right[right] = Some(left);
+                    }
+                    return true;
+                }
+                Some(mate) if layer < shortest && self.layer[mate] == Some(layer + 1) => {
+                    via.push(to);
+                    stack.push(mate);
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn run(&mut self) {
+        while let Some(shortest) = self.layers() {
+            let mut next = vec![0; self.graph.left.len()];
+            for root in 0..self.graph.left.len() {
+                if self.mate_left[root].is_none() {
+                    self.augment(root, shortest, &mut next);
+                }
+            }
+        }
+    }
+
+    fn matching(&self) -> Matching {
+        Matching::new(
+            self.mate_left
+                .iter()
+                .enumerate()
+                .filter_map(|(from, mate)| {
+                    mate.map(|to| (self.graph.left[from], self.graph.right[to]))
+                })
+                .collect(),
+        )
+    }
+
+    /// Минимальное вершинное покрытие по теореме Кёнига. Z --- вершины,
+    /// достижимые из свободных левых вершин по чередующимся путям; покрытие
+    /// --- левые вершины вне Z и правые вершины из Z.
+    fn vertex_cover(&self) -> Vec<Index> {
+        let mut left_seen = vec![false; self.graph.left.len()];
+        let mut right_seen = vec![false; self.graph.right.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for (from, mate) in self.mate_left.iter().enumerate() {
+            if mate.is_none() {
+                left_seen[from] = true;
+                queue.push_back(from);
+            }
+        }
+
+        while let Some(from) = queue.pop_front() {
+            // Из левой доли идем по ребрам вне паросочетания, из правой ---
+            // по ребру паросочетания
+            for &to in &self.graph.adjacency[from] {
+                if right_seen[to] || self.mate_left[from] == Some(to) {
+                    continue;
+                }
+                right_seen[to] = true;
+                if let Some(mate) = self.mate_right[to]
+                    && !left_seen[mate]
+                {
+                    left_seen[mate] = true;
+                    queue.push_back(mate);
+                }
+            }
+        }
+
+        let mut cover: Vec<Index> = left_seen
+            .iter()
+            .zip(&self.graph.left)
+            .filter(|&(&seen, _)| !seen)
+            .chain(
+                right_seen
+                    .iter()
+                    .zip(&self.graph.right)
+                    .filter(|&(&seen, _)| seen),
+            )
+            .map(|(_, &index)| index)
+            .collect();
+        cover.sort();
+        cover
+    }
+}
+
+/// Наибольшее паросочетание двудольного неориентированного графа
+/// алгоритмом Хопкрофта–Карпа за `O(E √V)`. Доли определяются
+/// автоматически ([`bipartition`]).
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный или не двудольный
+/// (в описании --- нечетный цикл).
+pub fn hopcroft_karp<G: GraphView>(graph: &G) -> Result<Matching> {
+    let graph = BipartiteGraph::new(graph)?;
+    let mut state = HopcroftKarp::new(&graph);
+    state.run();
+    Ok(state.matching())
+}
+
+/// Минимальное вершинное покрытие двудольного графа: наименьшее множество
+/// вершин, задевающее каждое ребро. По теореме Кёнига оно равно по размеру
+/// наибольшему паросочетанию и строится из него.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный или не двудольный.
+pub fn minimum_vertex_cover<G: GraphView>(graph: &G) -> Result<Vec<Index>> {
+    let graph = BipartiteGraph::new(graph)?;
+    let mut state = HopcroftKarp::new(&graph);
+    state.run();
+    Ok(state.vertex_cover())
+}
@@ -0,0 +1,571 @@
+//! Поток минимальной стоимости: последовательные кратчайшие пути с
+//! потенциалами (Дейкстра по приведенным стоимостям).
+//!
+//! Пропускная способность и стоимость ребра берутся из
+//! [`Edge::flow_capacity`](crate::Edge::flow_capacity) и
+//! [`Edge::flow_cost`](crate::Edge::flow_cost).
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt::Debug,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::components::sorted_nodes,
+    graph::{
+        core::{EdgeId, Graph, GraphError, GraphKindError, Index},
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Поток по одному ребру графа
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeFlow<W> {
+    /// id ребра
+    pub id: EdgeId,
+    pub from: Index,
+    pub to: Index,
+    /// Сколько единиц потока идет по ребру
+    pub flow: W,
+}
+
+/// Найденный поток: величина, стоимость и поток по каждому ребру.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCostFlow<W> {
+    value: W,
+    cost: W,
+    edges: Vec<EdgeFlow<W>>,
+}
+
+impl<W: Weight> MinCostFlow<W> {
+    /// Сколько единиц потока отправлено
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Суммарная стоимость: сумма по ребрам потока, умноженного на
+    /// стоимость единицы
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+
+    /// Ребра с ненулевым потоком по возрастанию id
+    pub fn edges(&self) -> &[EdgeFlow<W>] {
+        &self.edges
+    }
+
+    /// Поток по ребру id (ноль, если по нему ничего не идет)
+    pub fn flow_on(&self, id: EdgeId) -> W {
+        self.edges
+            .iter()
+            .find(|edge| edge.id == id)
+            .map_or(W::ZERO, |edge| edge.flow)
+    }
+}
+
+fn overflow(description: &str) -> GraphError {
+    GraphError::new(GraphKindError::WeightOverflow, description)
+}
+
+/// Остаточная сеть на плотных номерах. Дуга `a ^ 1` --- обратная к `a`;
+/// у прямых дуг номера четные, стоимость обратной --- минус стоимость
+/// прямой.
+struct CostNetwork<W> {
+    indices: Vec<Index>,
+    positions: HashMap<Index, usize>,
+    heads: Vec<usize>,
+    capacity: Vec<W>,
+    residual: Vec<W>,
+    cost: Vec<W>,
+    // Ребро графа, которому соответствует прямая дуга (у вспомогательных
+    // дуг его нет)
+    edge_ids: Vec<Option<EdgeId>>,
+    arcs: Vec<Vec<usize>>,
+}
+
+impl<W: Weight> CostNetwork<W> {
+    fn new<T>(graph: &Graph<T, W>) -> Result<Self>
+    where
+        T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    {
+        if !graph.get_is_directed() {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::GraphMustBeDirected,
+                "поток ищется в сети (орграфе)",
+            )));
+        }
+
+        let indices = sorted_nodes(graph);
+        let positions: HashMap<Index, usize> = indices
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+        let mut network = Self {
+            arcs: vec![Vec::new(); indices.len()],
+            indices,
+            positions,
+            heads: Vec::new(),
+            capacity: Vec::new(),
+            residual: Vec::new(),
+            cost: Vec::new(),
+            edge_ids: Vec::new(),
+        };
+
+        for from in network.indices.clone() {
+            for edge in graph.get_adjacency(&from).into_iter().flatten() {
+                let capacity = edge.flow_capacity();
+                if capacity < W::ZERO {
+                    return Err(Box::new(GraphError::new(
+                        GraphKindError::NegativeWeight,
+                        &format!("ребро {} с пропускной способностью {capacity}", edge.id),
+                    )));
+                }
+                let Some(&to) = network.positions.get(&edge.node.number) else {
+                    continue;
+                };
+                // Петля не меняет баланс, но ее стоимость учитывать незачем
+                let from = network.positions[&from];
+                if from != to {
+                    network.add_arc(from, to, capacity, edge.flow_cost(), Some(edge.id));
+                }
+            }
+        }
+        Ok(network)
+    }
+
+    fn add_node(&mut self) -> usize {
+        self.arcs.push(Vec::new());
+        self.arcs.len() - 1
+    }
+
+    fn add_arc(&mut self, from: usize, to: usize, capacity: W, cost: W, id: Option<EdgeId>) {
+        for (tail, head, residual) in [(from, to, capacity), (to, from, W::ZERO)] {
+            self.arcs[tail].push(self.heads.len());
+            self.heads.push(head);
+            self.capacity.push(capacity);
+            self.residual.push(residual);
+            self.cost.push(cost);
+            self.edge_ids.push(id);
+        }
+    }
+
+    fn position(&self, index: Index) -> Result<usize> {
+        self.positions.get(&index).copied().ok_or_else(|| {
+            Box::new(GraphError::new(
+                GraphKindError::NodeNotFound,
+                &format!("вершина {index}"),
+            )) as Box<dyn Error>
+        })
+    }
+
+    /// Приведенная стоимость дуги `c + p(tail) - p(head)`. У обратной дуги
+    /// стоимость `-c`, но `-c` не записать в беззнаковом типе, поэтому
+    /// она считается без явного отрицания.
+    fn reduced_cost(&self, arc: usize, potential: &[W]) -> Result<W> {
+        let (tail, head) = (self.heads[arc ^ 1], self.heads[arc]);
+        let cost = self.cost[arc];
+        let reduced = if arc & 1 == 0 {
+            // c + p(tail) - p(head)
+            cost.checked_add(potential[tail])
+                .and_then(|sum| sum.checked_sub(potential[head]))
+        } else {
+            // -c + p(tail) - p(head) = p(tail) - (c + p(head))
+            cost.checked_add(potential[head])
+                .and_then(|sum| potential[tail].checked_sub(sum))
+        };
+        reduced.ok_or_else(|| Box::new(overflow(&format!("приведенная стоимость дуги {arc}"))) as _)
+    }
+
+    /// Начальные потенциалы. При неотрицательных стоимостях подходят нули,
+    /// иначе --- расстояния Беллмана–Форда от source.
+    fn initial_potential(&self, source: usize) -> Result<Vec<W>> {
+        let n = self.arcs.len();
+        let mut potential = vec![W::ZERO; n];
+        let has_negative = (0..self.heads.len())
+            .step_by(2)
+            .any(|arc| self.residual[arc] > W::ZERO && self.cost[arc] < W::ZERO);
+        if !has_negative {
+            return Ok(potential);
+        }
+
+        let mut dist: Vec<Option<W>> = vec![None; n];
+        dist[source] = Some(W::ZERO);
+        for pass in 0..n {
+            let mut changed = false;
+            for arc in (0..self.heads.len()).step_by(2) {
+                let (tail, head) = (self.heads[arc ^ 1], self.heads[arc]);
+                let Some(start) = dist[tail] else {
+                    continue;
+                };
+                if self.residual[arc] == W::ZERO {
+                    continue;
+                }
+                let finish = start.try_add(self.cost[arc])?;
+                if dist[head].is_none_or(|old| finish < old) {
+                    dist[head] = Some(finish);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+            if pass + 1 == n {
+                return Err(Box::new(GraphError::new(
+                    GraphKindError::NegativeWeight,
+                    "цикл отрицательной стоимости: поток неограниченно дешевеет",
+                )));
+            }
+        }
+
+        for (value, found) in potential.iter_mut().zip(dist) {
+            *value = found.unwrap_or(W::ZERO);
+        }
+        Ok(potential)
+    }
+
+    /// Отправляет из source в sink не больше limit единиц (без limit ---
+    /// максимальный поток) и возвращает отправленное количество
+    fn send(&mut self, source: usize, sink: usize, limit: Option<W>) -> Result<W> {
+        let n = self.arcs.len();
+        let mut potential = self.initial_potential(source)?;
+        let mut sent = W::ZERO;
+
+        while limit.is_none_or(|limit| sent < limit) && source != sink {
+            // Дейкстра по приведенным стоимостям: они неотрицательны
+            let mut dist: Vec<Option<W>> = vec![None; n];
+            let mut parent: Vec<Option<usize>> = vec![None; n];
+            dist[source] = Some(W::ZERO);
+            let mut heap = BinaryHeap::from([Reverse((W::ZERO, source))]);
+            while let Some(Reverse((weight, from))) = heap.pop() {
+                if dist[from].is_some_and(|best| weight > best) {
+                    continue;
+                }
+                for &arc in &self.arcs[from] {
+                    if self.residual[arc] == W::ZERO {
+                        continue;
+                    }
+                    let to = self.heads[arc];
+                    let candidate = weight.try_add(self.reduced_cost(arc, &potential)?)?;
+                    if dist[to].is_none_or(|old| candidate < old) {
+                        dist[to] = Some(candidate);
+                        parent[to] = Some(arc);
+                        heap.push(Reverse((candidate, to)));
+                    }
+                }
+            }
+
+            if dist[sink].is_none() {
+                break;
+            }
+            for (value, found) in potential.iter_mut().zip(&dist) {
+                if let Some(found) = found {
+                    *value = value.try_add(*found)?;
+                }
+            }
+
+            // Путь от стока к истоку по дугам-родителям
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some(arc) = parent[node] {
+                path.push(arc);
+                node = self.heads[arc ^ 1];
+            }
+            let mut amount = path
+                .iter()
+                .map(|&arc| self.residual[arc])
+                .min()
+                .unwrap_or(W::ZERO);
+            if let Some(limit) = limit {
+                let rest = limit.checked_sub(sent).unwrap_or(W::ZERO);
+                amount = amount.min(rest);
+            }
+
+            for arc in path {
+                self.residual[arc] = self.residual[arc]
+                    .checked_sub(amount)
+                    .ok_or_else(|| overflow(&format!("{} - {amount}", self.residual[arc])))?;
+                self.residual[arc ^ 1] = self.residual[arc ^ 1].try_add(amount)?;
+            }
+            sent = sent.try_add(amount)?;
+        }
+
+        Ok(sent)
+    }
+
+    /// Поток по ребрам графа и его стоимость
+    fn result(&self, value: W) -> Result<MinCostFlow<W>> {
+        let mut edges = Vec::new();
+        let mut cost = W::ZERO;
+        for arc in (0..self.heads.len()).step_by(2) {
+            let Some(id) = self.edge_ids[arc] else {
+                continue;
+            };
+            let flow = self.capacity[arc]
+                .checked_sub(self.residual[arc])
+                .unwrap_or(W::ZERO);
+            if flow == W::ZERO {
+                continue;
+            }
+            let edge_cost = flow
+                .checked_mul(self.cost[arc])
+                .ok_or_else(|| overflow(&format!("{flow} * {}", self.cost[arc])))?;
+            cost = cost.try_add(edge_cost)?;
+            edges.push(EdgeFlow {
+                id,
+                from: self.indices[self.heads[arc ^ 1]],
+                to: self.indices[self.heads[arc]],
+                flow,
+            });
+        }
+        edges.sort_by_key(|edge| edge.id);
+
+        Ok(MinCostFlow { value, cost, edges })
+    }
+}
+
+/// Максимальный поток из `source` в `sink` наименьшей стоимости.
+///
+/// # Errors
+/// Вернет [`GraphError`], если одной из вершин нет, граф
+/// неориентированный, у ребра отрицательная пропускная способность, есть
+/// цикл отрицательной стоимости или стоимость переполнилась.
+pub fn min_cost_max_flow<T, W>(
+    graph: &Graph<T, W>,
+    source: Index,
+    sink: Index,
+) -> Result<MinCostFlow<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let mut network = CostNetwork::new(graph)?;
+    let (s, t) = (network.position(source)?, network.position(sink)?);
+    let value = network.send(s, t, None)?;
+    network.result(value)
+}
+
+/// Поток величины `amount` из `source` в `sink` наименьшей стоимости.
+///
+/// # Errors
+/// Вернет [`GraphError`] с [`GraphKindError::InfeasibleFlow`], если
+/// столько не пропустить, и те же ошибки, что [`min_cost_max_flow`].
+pub fn min_cost_flow<T, W>(
+    graph: &Graph<T, W>,
+    source: Index,
+    sink: Index,
+    amount: W,
+) -> Result<MinCostFlow<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let mut network = CostNetwork::new(graph)?;
+    let (s, t) = (network.position(source)?, network.position(sink)?);
+    let value = network.send(s, t, Some(amount))?;
+    if value < amount {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::InfeasibleFlow,
+            &format!("из {source} в {sink} проходит только {value} из {amount}"),
+        )));
+    }
+    network.result(value)
+}
+
+/// Самая дешевая перевозка: каждая вершина из `supply` отправляет
+/// указанное количество, каждая вершина из `demand` получает указанное.
+/// Сумма предложения должна равняться сумме спроса.
+///
+/// # Errors
+/// Вернет [`GraphError`] с [`GraphKindError::InfeasibleFlow`], если
+/// суммы не равны или пропускных способностей не хватает, и те же ошибки,
+/// что [`min_cost_max_flow`].
+pub fn min_cost_transport<T, W>(
+    graph: &Graph<T, W>,
+    supply: &HashMap<Index, W>,
+    demand: &HashMap<Index, W>,
+) -> Result<MinCostFlow<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let mut network = CostNetwork::new(graph)?;
+    let source = network.add_node();
+    let sink = network.add_node();
+
+    let mut totals = [W::ZERO; 2];
+    for (total, balances, is_supply) in [(0, supply, true), (1, demand, false)] {
+        let mut balances: Vec<(&Index, &W)> = balances.iter().collect();
+        balances.sort();
+        for (&index, &amount) in balances {
+            if amount < W::ZERO {
+                return Err(Box::new(GraphError::new(
+                    GraphKindError::NegativeWeight,
+                    &format!("баланс вершины {index}: {amount}"),
+                )));
+            }
+            let node = network.position(index)?;
+            if is_supply {
+                network.add_arc(source, node, amount, W::ZERO, None);
+            } else {
+                network.add_arc(node, sink, amount, W::ZERO, None);
+            }
+            totals[total] = totals[total].try_add(amount)?;
+        }
+    }
+
+    let [offered, required] = totals;
+    if offered != required {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::InfeasibleFlow,
+            &format!("предложение {offered} не равно спросу {required}"),
+        )));
+    }
+
+    let value = network.send(source, sink, Some(offered))?;
+    if value < offered {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::InfeasibleFlow,
+            &format!("удалось перевезти только {value} из {offered}"),
+        )));
+    }
+    network.result(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{
+            flow::check_max_flow,
+            test_support::{Rng, assert_conserved, assert_kind, connect, nodes},
+        },
+        graph::core::Edge,
+    };
+
+    /// Сеть на вершинах `1..=n` с дугами `(откуда, куда, пропускная
+    /// способность, стоимость)`
+    fn network(n: u32, arcs: &[(u32, u32, i64, i64)]) -> Graph<(), i64> {
+        let mut graph = nodes(n, true);
+        for &(from, to, capacity, cost) in arcs {
+            connect(
+                &mut graph,
+                from,
+                Edge::new(&Index(to), capacity, &()).with_cost(cost),
+            );
+        }
+        graph
+    }
+
+    /// Поток по каждой дуге допустим, сохраняется и равен `value`
+    fn assert_feasible(graph: &Graph<(), i64>, flow: &MinCostFlow<i64>, source: u32, sink: u32) {
+        for edge in flow.edges() {
+            let (_, arc) = graph.get_edge(edge.id).unwrap();
+            assert!((0..=arc.flow_capacity()).contains(&edge.flow));
+        }
+        assert_conserved(
+            flow.edges()
+                .iter()
+                .map(|edge| (edge.from, edge.to, edge.flow)),
+            Index(source),
+            Index(sink),
+            flow.value(),
+        );
+    }
+
+    fn sample() -> Graph<(), i64> {
+        network(
+            4,
+            &[
+                (1, 2, 2, 1),
+                (1, 3, 2, 5),
+                (2, 3, 1, 1),
+                (2, 4, 1, 6),
+                (3, 4, 3, 1),
+            ],
+        )
+    }
+
+    #[test]
+    fn cheapest_maximum_flow() {
+        let graph = sample();
+        let flow = min_cost_max_flow(&graph, Index(1), Index(4)).unwrap();
+        assert_feasible(&graph, &flow, 1, 4);
+        assert_eq!((flow.value(), flow.cost()), (4, 22));
+    }
+
+    #[test]
+    fn flow_of_given_amount() {
+        let graph = sample();
+        // 1→2→3→4 за 3, затем 1→3→4 за 6
+        let flow = min_cost_flow(&graph, Index(1), Index(4), 2).unwrap();
+        assert_feasible(&graph, &flow, 1, 4);
+        assert_eq!((flow.value(), flow.cost()), (2, 9));
+
+        assert_kind(
+            min_cost_flow(&graph, Index(1), Index(4), 5),
+            GraphKindError::InfeasibleFlow,
+        );
+    }
+
+    #[test]
+    fn negative_cost_cycle() {
+        // Цикл 2→3→4→2 стоимости -1 достижим из истока
+        let graph = network(
+            4,
+            &[(1, 2, 1, 1), (2, 3, 1, -3), (3, 4, 1, 1), (4, 2, 1, 1)],
+        );
+        assert_kind(
+            min_cost_max_flow(&graph, Index(1), Index(4)),
+            GraphKindError::NegativeWeight,
+        );
+
+        // Отрицательная дуга без цикла допустима
+        let graph = network(3, &[(1, 2, 1, -3), (2, 3, 1, 1), (1, 3, 1, 0)]);
+        let flow = min_cost_max_flow(&graph, Index(1), Index(3)).unwrap();
+        assert_eq!((flow.value(), flow.cost()), (2, -2));
+    }
+
+    #[test]
+    fn transport() {
+        let graph = network(4, &[(1, 3, 5, 2), (2, 3, 5, 1), (1, 4, 5, 1), (2, 4, 5, 4)]);
+        let supply = HashMap::from([(Index(1), 2), (Index(2), 2)]);
+        let demand = HashMap::from([(Index(3), 2), (Index(4), 2)]);
+        let flow = min_cost_transport(&graph, &supply, &demand).unwrap();
+        assert_eq!((flow.value(), flow.cost()), (4, 4));
+
+        // Предложение не равно спросу
+        let demand = HashMap::from([(Index(3), 2), (Index(4), 1)]);
+        assert_kind(
+            min_cost_transport(&graph, &supply, &demand),
+            GraphKindError::InfeasibleFlow,
+        );
+    }
+
+    #[test]
+    fn value_equals_maximum_flow() {
+        let mut rng = Rng::new(0x5851_f42d_4c95_7f2d);
+        for _ in 0..200 {
+            let n = 2 + rng.below(6) as u32;
+            let mut arcs = Vec::new();
+            for from in 1..=n {
+                for to in 1..=n {
+                    if from != to && rng.below(100) < 35 {
+                        arcs.push((from, to, rng.below(10) as i64, rng.below(10) as i64));
+                    }
+                }
+            }
+            let graph = network(n, &arcs);
+            let flow = min_cost_max_flow(&graph, Index(1), Index(n)).unwrap();
+            assert_feasible(&graph, &flow, 1, n);
+            let expected = check_max_flow(&graph, Index(1), Index(n)).unwrap();
+            assert_eq!(flow.value(), expected, "дуги {arcs:?}");
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Стабильный интерфейс алгоритмов над [`Graph<T>`].
+//!
+//! Функции ниже --- обертки над решениями заданий из `tasks` с
+//! понятными именами и типами: вершины передаются как [`Index`], а
+//! отсутствие результата --- как [`None`] вместо `-1`. Функции, принимающие
+//! [`GraphView`], работают и с [`CsrGraph<T>`](crate::CsrGraph).
+//!
+//! Алгоритмы, не относящиеся к заданиям, лежат в подмодулях и тоже
+//! доступны отсюда.
+
+use std::{error::Error, fmt::Debug};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    graph::{
+        core::{Graph, GraphType, Index},
+        view::GraphView,
+        weight::Weight,
+    },
+    tasks::{
+        task_2::task_2_4,
+        task_3::task_3_5,
+        task_4::task_4_6,
+        task_5::task_5_18,
+        task_6::task_6_4,
+        task_7::task_7_kraskal,
+        task_8::task_8_1,
+        task_9::task_9_2,
+        task_10::task_10_3,
+        task_11::{task_11, task_11_network},
+    },
+};
+
+pub mod assignment;
+pub mod astar;
+pub mod biconnected;
+pub mod bipartite;
+pub mod blossom;
+pub mod coloring;
+pub mod components;
+pub mod connectivity;
+pub mod dag;
+pub mod euler;
+pub mod flow;
+pub mod matching;
+pub mod min_cost_flow;
+pub mod postman;
+pub mod shortest_path;
+pub mod spanning_tree;
+pub mod tsp;
+
+#[cfg(test)]
+mod test_support;
+
+pub use crate::tasks::task_11::{FlowNetwork, MinCut};
+pub use assignment::{Assignment, Objective, hungarian};
+pub use astar::{Coordinates, SearchResult, astar, euclidean, manhattan};
+pub use biconnected::{
+    Biconnectivity, articulation_points, biconnected_components, biconnectivity, bridges,
+};
+pub use bipartite::{Bipartition, bipartition, is_bipartite};
+pub use blossom::{
+    augmenting_path, is_maximum_matching, maximum_matching, maximum_weight_matching,
+};
+pub use coloring::{
+    Coloring, EdgeColoring, GreedyOrder, InvalidColoring, bipartite_edge_coloring, check_coloring,
+    chromatic_number, dsatur, exact_coloring, greedy_coloring, greedy_coloring_in_order,
+};
+pub use components::{
+    Components, condensation, is_strongly_connected, is_weakly_connected, kosaraju_scc, tarjan_scc,
+};
+pub use connectivity::{
+    edge_connectivity, edge_disjoint_paths, local_edge_connectivity, local_vertex_connectivity,
+    vertex_connectivity, vertex_disjoint_paths,
+};
+pub use dag::{
+    CriticalPath, TopologicalOrder, count_paths, critical_path, longest_path, sinks, sources,
+    topological_sort_dfs, topological_sort_kahn,
+};
+pub use euler::{EulerObstruction, EulerTrail, Eulerian, TrailEdge, eulerian, hierholzer};
+pub use flow::{
+    Dinic, EdmondsKarp, FlowMismatch, MaxFlowAlgorithm, MaxFlowSolver, PushRelabel, SelectionRule,
+    check_max_flow,
+};
+pub use matching::{Matching, hopcroft_karp, minimum_vertex_cover};
+pub use min_cost_flow::{
+    EdgeFlow, MinCostFlow, min_cost_flow, min_cost_max_flow, min_cost_transport,
+};
+pub use postman::chinese_postman;
+pub use shortest_path::{
+    AllPairsPaths, NegativeCycle, ShortestPaths, bellman_ford, dijkstra, floyd_warshall,
+    shortest_path, spfa,
+};
+pub use spanning_tree::{SpanningForest, TreeEdge, boruvka, kruskal, prim};
+pub use tsp::{
+    HELD_KARP_MAX_NODES, Tour, christofides, hamiltonian_cycle, hamiltonian_path, held_karp,
+    nearest_neighbor, two_opt,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Полустепень захода вершины `node` орграфа (задание 2).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф неориентированный.
+pub fn in_degree<G: GraphView>(graph: &G, node: Index) -> Result<u32> {
+    task_2_4(graph, &node)
+}
+
+/// Степень (число исходящих ребер) каждой вершины орграфа, по
+/// возрастанию номера вершины (задание 3).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф неориентированный.
+pub fn degrees<G: GraphView>(graph: &G) -> Result<Vec<(Index, u32)>> {
+    let mut result: Vec<(Index, u32)> = task_3_5(graph)?
+        .into_iter()
+        .map(|(node, degree)| (node.into(), degree))
+        .collect();
+    result.sort();
+    Ok(result)
+}
+
+/// Пересечение двух графов (задание 4). Если ориентация графов разная,
+/// результат будет ориентированным. Исходные графы не изменяются.
+pub fn intersection<T: Clone + DeserializeOwned + Debug + Serialize + Default, W: Weight>(
+    graph_1: &Graph<T, W>,
+    graph_2: &Graph<T, W>,
+) -> Result<Graph<T, W>> {
+    task_4_6(&mut graph_1.clone(), &mut graph_2.clone())
+}
+
+/// Определяет, является ли орграф деревом, лесом или ни тем, ни другим
+/// (задание 5).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф неориентированный.
+pub fn classify<G: GraphView>(graph: &G) -> Result<GraphType> {
+    task_5_18(graph)
+}
+
+/// Проверяет связность неориентированного графа (задание 6).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф ориентированный.
+pub fn is_connected<G: GraphView>(graph: &G) -> Result<bool> {
+    task_6_4(graph)
+}
+
+/// Каркас минимального веса неориентированного графа (задание 7); для
+/// несвязного графа --- остовный лес. Вес и число деревьев дают
+/// [`kruskal`], [`prim`] и [`boruvka`].
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф ориентированный.
+pub fn minimum_spanning_tree<
+    T: Clone + DeserializeOwned + Debug + Serialize + Default,
+    W: Weight,
+>(
+    graph: &Graph<T, W>,
+) -> Result<Graph<T, W>> {
+    task_7_kraskal(graph)
+}
+
+/// Существует ли путь длиной не более `limit` из `start` в `destination`
+/// (задание 8).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф ориентированный,
+/// в нем есть ребро отрицательного веса или длина пути переполнилась.
+pub fn has_path_within<G: GraphView>(
+    graph: &G,
+    start: Index,
+    destination: Index,
+    limit: G::Weight,
+) -> Result<bool> {
+    task_8_1(graph, *start, *destination, limit)
+}
+
+/// Вершина, сумма кратчайших путей от которой до остальных не превосходит
+/// `limit` (задание 9).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф ориентированный
+/// или сумма путей переполнилась.
+pub fn node_with_total_distance_within<G: GraphView>(
+    graph: &G,
+    limit: G::Weight,
+) -> Result<Option<Index>> {
+    let found = task_9_2(graph, &limit)?;
+    Ok(u32::try_from(found).ok().map(Index))
+}
+
+/// Вершина, каждый кратчайший путь от которой до остальных не превосходит
+/// `limit` (задание 10).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф ориентированный
+/// или длина пути переполнилась.
+pub fn node_with_each_distance_within<G: GraphView>(
+    graph: &G,
+    limit: G::Weight,
+) -> Result<Option<Index>> {
+    let found = task_10_3(graph, &limit)?;
+    Ok(u32::try_from(found).ok().map(Index))
+}
+
+/// Величина максимального потока из `source` в `sink` (задание 11).
+/// Для пошагового выполнения используйте [`FlowNetwork`], для других
+/// алгоритмов --- [`MaxFlowSolver`].
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф неориентированный,
+/// в нем есть ребро отрицательного веса или поток переполнился.
+pub fn max_flow<G: GraphView>(graph: &G, source: Index, sink: Index) -> Result<G::Weight> {
+    task_11(graph, source, sink)
+}
+
+/// Максимальный поток из `source` в `sink` целиком: сеть, в которой
+/// увеличивающих путей не осталось. Из нее берутся поток по каждому ребру
+/// ([`FlowNetwork::edge_flows`]) и минимальный разрез
+/// ([`FlowNetwork::min_cut`]).
+///
+/// # Errors
+/// Вернет [`GraphError`](crate::GraphError), если граф неориентированный,
+/// в нем есть ребро отрицательного веса или поток переполнился.
+pub fn max_flow_network<G: GraphView>(
+    graph: &G,
+    source: Index,
+    sink: Index,
+) -> Result<FlowNetwork<G::Weight>> {
+    task_11_network(graph, source, sink)
+}
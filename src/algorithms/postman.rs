@@ -0,0 +1,304 @@
+//! Задача китайского почтальона: кратчайший замкнутый маршрут,
+//! проходящий по каждому ребру хотя бы раз.
+
+use std::{collections::HashMap, error::Error, fmt::Debug};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::{
+        blossom::minimum_weight_perfect_matching,
+        euler::{
+            EulerObstruction, EulerTrail, Eulerian, TrailEdge, build_trail, classify, graph_edges,
+        },
+        min_cost_flow::min_cost_transport,
+        shortest_path::floyd_warshall,
+    },
+    graph::{
+        core::{Edge, Graph, GraphError, GraphKindError, Index, Node},
+        union_find::UnionFind,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Повторные проходы для неориентированного графа: вершины нечетной
+/// степени разбиваются на пары паросочетанием минимального веса по
+/// кратчайшим расстояниям (Флойд–Уоршелл), и ребра кратчайшего пути между
+/// вершинами каждой пары проходятся еще раз.
+fn undirected_repeats<T, W>(
+    graph: &Graph<T, W>,
+    edges: &[TrailEdge<W>],
+) -> Result<Vec<TrailEdge<W>>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let mut degree: HashMap<Index, usize> = HashMap::new();
+    for edge in edges {
+        *degree.entry(edge.from).or_default() += 1;
+        *degree.entry(edge.to).or_default() += 1;
+    }
+    let mut odd: Vec<Index> = degree
+        .into_iter()
+        .filter(|&(_, degree)| degree % 2 == 1)
+        .map(|(index, _)| index)
+        .collect();
+    if odd.is_empty() {
+        return Ok(Vec::new());
+    }
+    odd.sort();
+
+    let paths = floyd_warshall(graph)?;
+    let matching = minimum_weight_perfect_matching(&odd, |from, to| paths.distance(from, to))?;
+
+    // Из параллельных ребер повторяем самое легкое
+    let mut lightest: HashMap<(Index, Index), TrailEdge<W>> = HashMap::new();
+    for &edge in edges {
+        let key = (edge.from.min(edge.to), edge.from.max(edge.to));
+        let best = lightest.entry(key).or_insert(edge);
+        if (edge.weight, edge.id) < (best.weight, best.id) {
+            *best = edge;
+        }
+    }
+
+    let mut repeats = Vec::new();
+    for &(from, to) in matching.pairs() {
+        let path = paths.path(from, to).ok_or_else(|| {
+            GraphError::new(
+                GraphKindError::EdgeNotFound,
+                &format!("нет пути из {from} в {to}"),
+            )
+        })?;
+        for step in path.windows(2) {
+            let key = (step[0].min(step[1]), step[0].max(step[1]));
+            repeats.push(lightest[&key]);
+        }
+    }
+    Ok(repeats)
+}
+
+/// Повторные проходы для орграфа: вершины, в которые входит больше ребер,
+/// чем выходит, отправляют разницу в вершины с обратным перекосом потоком
+/// наименьшей стоимости (пропускная способность не ограничена,
+/// стоимость --- вес ребра); ребро проходится еще столько раз, сколько
+/// единиц потока по нему идет.
+fn directed_repeats<W: Weight>(edges: &[TrailEdge<W>]) -> Result<Vec<TrailEdge<W>>> {
+    let mut balance: HashMap<Index, i64> = HashMap::new();
+    for edge in edges {
+        *balance.entry(edge.from).or_default() -= 1;
+        *balance.entry(edge.to).or_default() += 1;
+    }
+    let (mut supply, mut demand): (HashMap<Index, W>, HashMap<Index, W>) = Default::default();
+    let mut total: i64 = 0;
+    for (&index, &value) in &balance {
+        if value > 0 {
+            supply.insert(index, W::from_f64(value as f64));
+            total += value;
+        } else if value < 0 {
+            demand.insert(index, W::from_f64(-value as f64));
+        }
+    }
+    if total == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Сеть с теми же ребрами: пропускной способности хватит на весь
+    // перекос, стоимость --- вес
+    let unbounded = W::from_f64(total as f64);
+    let mut network: Graph<(), W> = Graph::default();
+    network.to_directed_mut();
+    network.to_multigraph_mut();
+    let mut nodes: Vec<Index> = balance.keys().copied().collect();
+    nodes.sort();
+    for index in nodes {
+        network.add_node(Node::new(index, ()))?;
+    }
+    let mut originals = HashMap::new();
+    for &edge in edges {
+        if edge.from == edge.to {
+            continue;
+        }
+        let id = network.add_edge(
+            &Node::new(edge.from, ()),
+            &Edge::new(&edge.to, edge.weight, &())
+                .with_capacity(unbounded)
+                .with_cost(edge.weight),
+        )?;
+        originals.insert(id, edge);
+    }
+
+    let flow = min_cost_transport(&network, &supply, &demand)?;
+    let one = W::from_f64(1.0);
+    let mut repeats = Vec::new();
+    for edge_flow in flow.edges() {
+        let mut left = edge_flow.flow;
+        while left > W::ZERO {
+            repeats.push(originals[&edge_flow.id]);
+            left = left.checked_sub(one).unwrap_or(W::ZERO);
+        }
+    }
+    Ok(repeats)
+}
+
+/// Маршрут китайского почтальона: кратчайший замкнутый обход, проходящий
+/// по каждому ребру (в орграфе --- по направлению) хотя бы раз. Ребра,
+/// пройденные повторно, встречаются в [`EulerTrail::edges`] несколько
+/// раз с одним id; [`EulerTrail::total`] --- длина всего маршрута.
+///
+/// В неориентированном графе вершины нечетной степени разбиваются на пары
+/// совершенным паросочетанием минимального веса по расстояниям
+/// Флойда–Уоршелла (`O(V³)`), в орграфе перекос степеней выравнивается
+/// потоком наименьшей стоимости. Затем эйлеров цикл строится алгоритмом
+/// Хирхольцера из наименьшей вершины с ребрами.
+///
+/// # Errors
+/// Вернет [`GraphError`], если у ребра отрицательный вес, ребра лежат в
+/// разных компонентах, в орграфе по ребрам нельзя вернуться назад
+/// ([`GraphKindError::InfeasibleFlow`]) или длина маршрута переполнилась.
+pub fn chinese_postman<T, W>(graph: &Graph<T, W>) -> Result<EulerTrail<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let mut edges = graph_edges(graph);
+    if let Some(edge) = edges.iter().find(|edge| edge.weight < W::ZERO) {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::NegativeWeight,
+            &format!("ребро {} из {} в {}", edge.id, edge.from, edge.to),
+        )));
+    }
+
+    // Несвязность проверяем до поиска повторов: без нее расстояний между
+    // компонентами просто нет
+    let mut sets = UnionFind::new();
+    for edge in &edges {
+        sets.union(edge.from, edge.to);
+    }
+    if sets.component_count() > 1 {
+        Eulerian::NotEulerian(EulerObstruction::Disconnected(sets.components())).check()?;
+    }
+
+    let directed = graph.get_is_directed();
+    let repeats = if directed {
+        directed_repeats(&edges)?
+    } else {
+        undirected_repeats(graph, &edges)?
+    };
+    edges.extend(repeats);
+
+    classify(&edges, directed).check()?;
+    // Без ребер маршрут пустой, начало не важно
+    let start = edges
+        .iter()
+        .map(|edge| edge.from.min(edge.to))
+        .min()
+        .unwrap_or(Index(0));
+    build_trail(&edges, directed, start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::test_support::{Rng, assert_kind, graph};
+
+    /// Маршрут замкнут, идет по ребрам графа (в орграфе --- по
+    /// направлению) и проходит каждое ребро хотя бы раз
+    fn assert_covers(graph: &Graph<(), i64>, trail: &EulerTrail<i64>) {
+        assert!(trail.is_closed());
+        for pair in trail.edges().windows(2) {
+            assert_eq!(pair[0].to, pair[1].from, "маршрут разрывается");
+        }
+        for edge in trail.edges() {
+            let (from, stored) = graph.get_edge(edge.id).unwrap();
+            let to = stored.node.number;
+            assert!(
+                (edge.from, edge.to) == (from, to)
+                    || !graph.get_is_directed() && (edge.from, edge.to) == (to, from),
+                "ребро {} не из {} в {}",
+                edge.id,
+                edge.from,
+                edge.to
+            );
+            assert_eq!(edge.weight, stored.weight);
+        }
+        for (_, adj) in graph.iter() {
+            for edge in adj {
+                assert!(
+                    trail.edges().iter().any(|step| step.id == edge.id),
+                    "ребро {} не пройдено",
+                    edge.id
+                );
+            }
+        }
+        let total: i64 = trail.edges().iter().map(|edge| edge.weight).sum();
+        assert_eq!(trail.total(), total);
+    }
+
+    #[test]
+    fn repeats_shortest_paths() {
+        // Путь 1 - 2 - 3 проходится туда и обратно
+        let path = graph(3, false, &[(1, 2, 1), (2, 3, 2)]);
+        let trail = chinese_postman(&path).unwrap();
+        assert_covers(&path, &trail);
+        assert_eq!(trail.total(), 6);
+
+        // Перекос степеней 1 и 3 выравнивается дугой 3 → 1
+        let network = graph(3, true, &[(1, 2, 1), (2, 3, 1), (3, 1, 1), (1, 3, 5)]);
+        let trail = chinese_postman(&network).unwrap();
+        assert_covers(&network, &trail);
+        assert_eq!(trail.total(), 9);
+
+        // Эйлеров граф обходится без повторов
+        let triangle = graph(3, false, &[(1, 2, 3), (2, 3, 4), (3, 1, 5)]);
+        let trail = chinese_postman(&triangle).unwrap();
+        assert_covers(&triangle, &trail);
+        assert_eq!((trail.len(), trail.total()), (3, 12));
+    }
+
+    #[test]
+    fn no_route() {
+        // Из 2 в 1 не вернуться
+        assert_kind(
+            chinese_postman(&graph(2, true, &[(1, 2, 1)])),
+            GraphKindError::InfeasibleFlow,
+        );
+        assert_kind(
+            chinese_postman(&graph(4, false, &[(1, 2, 1), (3, 4, 1)])),
+            GraphKindError::GraphMustBeEulerian,
+        );
+        assert_kind(
+            chinese_postman(&graph(2, false, &[(1, 2, -1)])),
+            GraphKindError::NegativeWeight,
+        );
+    }
+
+    #[test]
+    fn random_routes_cover_every_edge() {
+        let mut rng = Rng::new(0xd1b5_4a32_d192_ed03);
+        for _ in 0..200 {
+            let n = 2 + rng.below(6) as u32;
+            let directed = rng.below(2) == 0;
+            // Путь (в орграфе --- цикл) через все вершины дает связность
+            let mut edges: Vec<(u32, u32, i64)> =
+                (1..n).map(|a| (a, a + 1, rng.below(9) as i64)).collect();
+            if directed {
+                edges.push((n, 1, rng.below(9) as i64));
+            }
+            for (a, b, weight) in rng.edges(n, 30, 9) {
+                // Параллельные и встречные ребра тоже бывают
+                if rng.below(2) == 0 {
+                    edges.push((a, b, weight));
+                } else {
+                    edges.push((b, a, weight));
+                }
+            }
+            let graph = graph(n, directed, &edges);
+            let trail = chinese_postman(&graph).unwrap();
+            assert_covers(&graph, &trail);
+            let once: i64 = edges.iter().map(|&(_, _, weight)| weight).sum();
+            assert!(trail.total() >= once);
+        }
+    }
+}
@@ -0,0 +1,476 @@
+//! Кратчайшие пути: из одной вершины (Беллман–Форд, SPFA, Дейкстра) и
+//! между всеми парами вершин (Флойд–Уоршелл).
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::Display,
+};
+
+use crate::graph::{
+    core::{GraphError, GraphKindError, Index},
+    view::GraphView,
+    weight::{Weight, ensure_non_negative},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Дерево кратчайших путей из вершины `source`: расстояния и предки.
+/// Недостижимых вершин в нем нет.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<W> {
+    source: Index,
+    dist: HashMap<Index, W>,
+    parent: HashMap<Index, Index>,
+}
+
+impl<W> ShortestPaths<W>
+where
+    W: Weight,
+{
+    /// Returns the source of this [`ShortestPaths<W>`].
+    pub fn source(&self) -> Index {
+        self.source
+    }
+
+    /// Длина кратчайшего пути до `to`; [`None`], если `to` недостижима
+    pub fn distance(&self, to: Index) -> Option<W> {
+        self.dist.get(&to).copied()
+    }
+
+    /// Предыдущая вершина на кратчайшем пути до `to`
+    pub fn predecessor(&self, to: Index) -> Option<Index> {
+        self.parent.get(&to).copied()
+    }
+
+    /// Расстояния до всех достижимых вершин
+    pub fn distances(&self) -> &HashMap<Index, W> {
+        &self.dist
+    }
+
+    /// Кратчайший путь от `source` до `to` включительно
+    pub fn path_to(&self, to: Index) -> Option<Vec<Index>> {
+        if !self.dist.contains_key(&to) {
+            return None;
+        }
+
+        let mut path = vec![to];
+        let mut current = to;
+        while let Some(&prev) = self.parent.get(&current) {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Ошибка: из источника достижим цикл отрицательного веса.
+///
+/// Цикл `[a, b, c]` означает ребра `a -> b -> c -> a`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle {
+    cycle: Vec<Index>,
+}
+
+impl NegativeCycle {
+    /// Вершины цикла в порядке обхода
+    pub fn cycle(&self) -> &[Index] {
+        &self.cycle
+    }
+}
+
+impl Error for NegativeCycle {}
+
+impl Display for NegativeCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph has a negative cycle: ")?;
+        for index in &self.cycle {
+            write!(f, "{index} -> ")?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, "{first}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Ищет цикл в графе предков, начиная обход с вершин starts
+fn parent_cycle(
+    parent: &HashMap<Index, Index>,
+    starts: impl IntoIterator<Item = Index>,
+) -> Option<Vec<Index>> {
+    // Номер обхода, в котором вершина была пройдена
+    let mut walk_of: HashMap<Index, usize> = HashMap::new();
+
+    for (walk, start) in starts.into_iter().enumerate() {
+        let mut current = start;
+        loop {
+            match walk_of.get(&current) {
+                // Вернулись в вершину текущего обхода --- это цикл
+                Some(&seen) if seen == walk => {
+                    let mut cycle = vec![current];
+                    let mut node = parent[&current];
+                    while node != current {
+                        cycle.push(node);
+                        node = parent[&node];
+                    }
+                    // Шли по предкам, то есть против ребер
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                // Пришли в уже проверенную часть
+                Some(_) => break,
+                None => {
+                    walk_of.insert(current, walk);
+                    match parent.get(&current) {
+                        Some(&prev) => current = prev,
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn check_source<G: GraphView>(graph: &G, source: Index) -> Result<()> {
+    if graph.contains_node(source) {
+        Ok(())
+    } else {
+        Err(Box::new(GraphError::new(
+            GraphKindError::NodeNotFound,
+            &format!("вершина {source}"),
+        )))
+    }
+}
+
+/// Кратчайшие пути из `source` алгоритмом Беллмана–Форда. Веса могут быть
+/// отрицательными; в неориентированном графе отрицательное ребро само по
+/// себе --- отрицательный цикл.
+///
+/// # Errors
+/// Вернет [`NegativeCycle`], если из `source` достижим отрицательный цикл,
+/// и [`GraphError`], если вершины `source` нет или длина пути
+/// переполнилась.
+pub fn bellman_ford<G: GraphView>(graph: &G, source: Index) -> Result<ShortestPaths<G::Weight>> {
+    check_source(graph, source)?;
+
+    let edges: Vec<(Index, Index, G::Weight)> = graph
+        .node_indices()
+        .flat_map(|from| {
+            graph
+                .neighbors(from)
+                .map(move |(to, weight)| (from, to, weight))
+        })
+        .collect();
+
+    let mut dist: HashMap<Index, G::Weight> = HashMap::from([(source, G::Weight::ZERO)]);
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+
+    // Ребро орграфа может вести в вершину, которой нет в графе, поэтому
+    // считаем все концы ребер
+    let nodes: HashSet<Index> = graph
+        .node_indices()
+        .chain(edges.iter().map(|&(_, to, _)| to))
+        .collect();
+
+    // После n - 1 проходов расстояния окончательные; если ребро
+    // релаксируется и на n-м, то есть отрицательный цикл
+    for _ in 0..nodes.len() {
+        let mut last_relaxed = None;
+        for &(from, to, weight) in &edges {
+            let Some(&through) = dist.get(&from) else {
+                continue;
+            };
+            let new_weight = through.try_add(weight)?;
+            if dist.get(&to).is_none_or(|&old| new_weight < old) {
+                dist.insert(to, new_weight);
+                parent.insert(to, from);
+                last_relaxed = Some(to);
+            }
+        }
+
+        let Some(last) = last_relaxed else {
+            return Ok(ShortestPaths {
+                source,
+                dist,
+                parent,
+            });
+        };
+
+        // Цикл в графе предков всегда отрицательный
+        if let Some(cycle) = parent_cycle(&parent, [last].into_iter().chain(parent.keys().copied()))
+        {
+            return Err(Box::new(NegativeCycle { cycle }));
+        }
+    }
+
+    // Релаксация на n-м проходе без цикла в графе предков невозможна, но
+    // на всякий случай проверяем граф предков целиком
+    match parent_cycle(&parent, parent.keys().copied()) {
+        Some(cycle) => Err(Box::new(NegativeCycle { cycle })),
+        None => Ok(ShortestPaths {
+            source,
+            dist,
+            parent,
+        }),
+    }
+}
+
+/// Кратчайшие пути из `source` алгоритмом SPFA (Беллман–Форд с
+/// очередью). Результат тот же, что у [`bellman_ford`], но обычно
+/// быстрее на разреженных графах.
+///
+/// # Errors
+/// Те же, что у [`bellman_ford`].
+pub fn spfa<G: GraphView>(graph: &G, source: Index) -> Result<ShortestPaths<G::Weight>> {
+    check_source(graph, source)?;
+
+    let node_count = graph.node_count();
+    let mut dist: HashMap<Index, G::Weight> = HashMap::from([(source, G::Weight::ZERO)]);
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+    // Сколько раз улучшалось расстояние до вершины
+    let mut relaxed: HashMap<Index, usize> = HashMap::new();
+    let mut queue = VecDeque::from([source]);
+    let mut in_queue: HashSet<Index> = HashSet::from([source]);
+
+    while let Some(from) = queue.pop_front() {
+        in_queue.remove(&from);
+        let through = dist[&from];
+
+        for (to, weight) in graph.neighbors(from) {
+            let new_weight = through.try_add(weight)?;
+            if dist.get(&to).is_some_and(|&old| old <= new_weight) {
+                continue;
+            }
+            dist.insert(to, new_weight);
+            parent.insert(to, from);
+
+            // Без отрицательных циклов расстояние улучшается не более
+            // n - 1 раз, иначе в графе предков рано или поздно появится цикл
+            let count = relaxed.entry(to).or_insert(0);
+            *count += 1;
+            if *count >= node_count
+                && let Some(cycle) =
+                    parent_cycle(&parent, [to].into_iter().chain(parent.keys().copied()))
+            {
+                return Err(Box::new(NegativeCycle { cycle }));
+            }
+
+            if in_queue.insert(to) {
+                queue.push_back(to);
+            }
+        }
+    }
+
+    Ok(ShortestPaths {
+        source,
+        dist,
+        parent,
+    })
+}
+
+/// Дейкстра из `source`; если задан `target`, останавливается, как только
+/// расстояние до него станет окончательным
+fn dijkstra_until<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Option<Index>,
+) -> Result<ShortestPaths<G::Weight>> {
+    check_source(graph, source)?;
+    ensure_non_negative(graph)?;
+
+    let mut dist: HashMap<Index, G::Weight> = HashMap::from([(source, G::Weight::ZERO)]);
+    let mut parent: HashMap<Index, Index> = HashMap::new();
+    // BinaryHeap --- max-куча, поэтому кладем Reverse
+    let mut heap = BinaryHeap::from([Reverse((G::Weight::ZERO, source))]);
+
+    while let Some(Reverse((weight, from))) = heap.pop() {
+        // Устаревшая запись в куче
+        if weight > dist[&from] {
+            continue;
+        }
+        if Some(from) == target {
+            break;
+        }
+
+        for (to, edge_weight) in graph.neighbors(from) {
+            let new_weight = weight.try_add(edge_weight)?;
+            if dist.get(&to).is_none_or(|&old| new_weight < old) {
+                dist.insert(to, new_weight);
+                parent.insert(to, from);
+                heap.push(Reverse((new_weight, to)));
+            }
+        }
+    }
+
+    Ok(ShortestPaths {
+        source,
+        dist,
+        parent,
+    })
+}
+
+/// Кратчайшие пути из `source` до всех вершин алгоритмом Дейкстры.
+///
+/// # Errors
+/// Вернет [`GraphError`], если вершины `source` нет, в графе есть ребро
+/// отрицательного веса или длина пути переполнилась.
+pub fn dijkstra<G: GraphView>(graph: &G, source: Index) -> Result<ShortestPaths<G::Weight>> {
+    dijkstra_until(graph, source, None)
+}
+
+/// Кратчайший путь из `source` в `target` (Дейкстра с ранней остановкой):
+/// его длина и вершины от `source` до `target` включительно. [`None`],
+/// если `target` недостижима.
+///
+/// # Errors
+/// Те же, что у [`dijkstra`], а также если вершины `target` нет.
+pub fn shortest_path<G: GraphView>(
+    graph: &G,
+    source: Index,
+    target: Index,
+) -> Result<Option<(G::Weight, Vec<Index>)>> {
+    check_source(graph, target)?;
+    let paths = dijkstra_until(graph, source, Some(target))?;
+    Ok(paths.distance(target).zip(paths.path_to(target)))
+}
+
+/// Кратчайшие пути между всеми парами вершин: матрица расстояний и
+/// матрица следующих вершин на пути (next-hop). Вершины пронумерованы по
+/// возрастанию [`Index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairsPaths<W> {
+    indices: Vec<Index>,
+    positions: HashMap<Index, usize>,
+    dist: Vec<Vec<Option<W>>>,
+    next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W> AllPairsPaths<W>
+where
+    W: Weight,
+{
+    /// Вершины в порядке строк и столбцов матриц
+    pub fn indices(&self) -> &[Index] {
+        &self.indices
+    }
+
+    /// Длина кратчайшего пути из `from` в `to`
+    pub fn distance(&self, from: Index, to: Index) -> Option<W> {
+        self.dist[*self.positions.get(&from)?][*self.positions.get(&to)?]
+    }
+
+    /// Следующая после `from` вершина на кратчайшем пути в `to`
+    pub fn next_hop(&self, from: Index, to: Index) -> Option<Index> {
+        let next = self.next[*self.positions.get(&from)?][*self.positions.get(&to)?]?;
+        Some(self.indices[next])
+    }
+
+    /// Кратчайший путь из `from` в `to` включительно
+    pub fn path(&self, from: Index, to: Index) -> Option<Vec<Index>> {
+        let to_pos = *self.positions.get(&to)?;
+        let mut current = *self.positions.get(&from)?;
+        self.dist[current][to_pos]?;
+
+        let mut path = vec![self.indices[current]];
+        while current != to_pos {
+            current = self.next[current][to_pos]?;
+            path.push(self.indices[current]);
+        }
+        Some(path)
+    }
+
+    /// Матрица расстояний; [`None`] --- пути нет
+    pub fn distance_matrix(&self) -> &[Vec<Option<W>>] {
+        &self.dist
+    }
+
+    /// Матрица следующих вершин на кратчайших путях
+    pub fn next_hop_matrix(&self) -> Vec<Vec<Option<Index>>> {
+        self.next
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|next| next.map(|pos| self.indices[pos]))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Кратчайшие пути между всеми парами вершин алгоритмом Флойда–Уоршелла.
+/// Веса могут быть отрицательными.
+///
+/// # Errors
+/// Вернет [`NegativeCycle`], если в графе есть отрицательный цикл, и
+/// [`GraphError`], если длина пути переполнилась.
+pub fn floyd_warshall<G: GraphView>(graph: &G) -> Result<AllPairsPaths<G::Weight>> {
+    let mut indices: Vec<Index> = graph.node_indices().collect();
+    indices.sort();
+    let positions: HashMap<Index, usize> = indices
+        .iter()
+        .enumerate()
+        .map(|(pos, &index)| (index, pos))
+        .collect();
+
+    let len = indices.len();
+    let mut dist: Vec<Vec<Option<G::Weight>>> = vec![vec![None; len]; len];
+    let mut next: Vec<Vec<Option<usize>>> = vec![vec![None; len]; len];
+
+    for (i, (dst, nxt)) in dist.iter_mut().zip(next.iter_mut()).enumerate() {
+        dst[i] = Some(G::Weight::ZERO);
+        nxt[i] = Some(i);
+    }
+
+    // Ребра; из параллельных берем самое легкое
+    for (from, &index) in indices.iter().enumerate() {
+        for (to_index, weight) in graph.neighbors(index) {
+            let Some(&to) = positions.get(&to_index) else {
+                continue;
+            };
+            if dist[from][to].is_none_or(|old| weight < old) {
+                dist[from][to] = Some(weight);
+                next[from][to] = Some(to);
+            }
+        }
+    }
+
+    for middle in 0..len {
+        // Строка middle на этой итерации не меняется, пока нет
+        // отрицательного цикла через middle
+        let after_middle = dist[middle].clone();
+        for from in 0..len {
+            let Some(to_middle) = dist[from][middle] else {
+                continue;
+            };
+            let hop = next[from][middle];
+            for (to, after) in after_middle.iter().enumerate() {
+                let Some(after) = *after else {
+                    continue;
+                };
+                let through_middle = to_middle.try_add(after)?;
+                if dist[from][to].is_none_or(|old| through_middle < old) {
+                    dist[from][to] = Some(through_middle);
+                    next[from][to] = hop;
+                }
+            }
+        }
+
+        // Отрицательное расстояние от вершины до самой себя --- вершина
+        // лежит на отрицательном цикле; сам цикл найдет Беллман–Форд
+        if let Some(pos) = (0..len).find(|&pos| dist[pos][pos].is_some_and(|d| d < G::Weight::ZERO))
+        {
+            bellman_ford(graph, indices[pos])?;
+        }
+    }
+
+    Ok(AllPairsPaths {
+        indices,
+        positions,
+        dist,
+        next,
+    })
+}
@@ -0,0 +1,295 @@
+//! Остовные деревья: алгоритмы Краскала, Прима и Борувки. Для
+//! несвязного графа строится остовный лес --- по дереву на компоненту.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt::Debug,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::{assignment::Objective, components::sorted_nodes},
+    graph::{
+        core::{EdgeId, Graph, GraphError, GraphKindError, Index},
+        union_find::UnionFind,
+        view::ensure_undirected,
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Ребро остова
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeEdge<W> {
+    /// id ребра в исходном графе
+    pub id: EdgeId,
+    pub from: Index,
+    pub to: Index,
+    pub weight: W,
+}
+
+/// Остовный лес: ребра, их суммарный вес и число деревьев (компонент
+/// связности графа).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningForest<W> {
+    edges: Vec<TreeEdge<W>>,
+    total: W,
+    components: usize,
+}
+
+impl<W: Weight> SpanningForest<W> {
+    /// Ребра леса по возрастанию id
+    pub fn edges(&self) -> &[TreeEdge<W>] {
+        &self.edges
+    }
+
+    /// Суммарный вес ребер леса
+    pub fn total(&self) -> W {
+        self.total
+    }
+
+    /// Число деревьев в лесу; изолированная вершина --- отдельное дерево
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Лес --- одно дерево, то есть граф связен
+    pub fn is_tree(&self) -> bool {
+        self.components <= 1
+    }
+
+    /// Лес как неориентированный граф на всех вершинах исходного. Ребра
+    /// копируются из исходного графа вместе с пропускной способностью и
+    /// стоимостью.
+    pub fn to_graph<T>(&self, graph: &Graph<T, W>) -> Result<Graph<T, W>>
+    where
+        T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    {
+        let mut forest: Graph<T, W> = Graph::default().to_not_directed()?;
+        if graph.get_is_multigraph() {
+            forest.to_multigraph_mut();
+        }
+        for index in sorted_nodes(graph) {
+            if let Some(node) = graph.get_node(&index) {
+                forest.add_node(node.clone())?;
+            }
+        }
+        for tree_edge in &self.edges {
+            let edge = graph
+                .get_adjacency(&tree_edge.from)
+                .into_iter()
+                .flatten()
+                .find(|edge| edge.id == tree_edge.id)
+                .ok_or_else(|| {
+                    GraphError::new(
+                        GraphKindError::EdgeNotFound,
+                        &format!("ребро {}", tree_edge.id),
+                    )
+                })?;
+            let from = graph.get_node(&tree_edge.from).ok_or_else(|| {
+                GraphError::new(
+                    GraphKindError::NodeNotFound,
+                    &format!("вершина {}", tree_edge.from),
+                )
+            })?;
+            forest.add_edge(from, edge)?;
+        }
+        Ok(forest)
+    }
+}
+
+/// Ребра графа на плотных номерах, упорядоченные от лучшего к худшему:
+/// по весу (для максимального остова --- по убыванию), при равных весах
+/// по id. Порядок строгий, поэтому все три алгоритма строят один и тот же
+/// лес.
+struct RankedEdges<W> {
+    nodes: Vec<Index>,
+    // Концы ребра (плотные номера) и само ребро; позиция --- ранг
+    edges: Vec<(usize, usize, TreeEdge<W>)>,
+}
+
+impl<W: Weight> RankedEdges<W> {
+    fn new<T>(graph: &Graph<T, W>, objective: Objective) -> Result<Self>
+    where
+        T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    {
+        ensure_undirected(graph, "остов строится в неориентированном графе")?;
+
+        let nodes = sorted_nodes(graph);
+        let positions: HashMap<Index, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+        let mut seen: HashSet<EdgeId> = HashSet::new();
+        let mut edges = Vec::new();
+        for (from_pos, &from) in nodes.iter().enumerate() {
+            for edge in graph.get_adjacency(&from).into_iter().flatten() {
+                let to = edge.node.number;
+                // Петля в остов не входит; половины ребра берем один раз
+                let Some(&to_pos) = positions.get(&to) else {
+                    continue;
+                };
+                if to_pos == from_pos || !seen.insert(edge.id) {
+                    continue;
+                }
+                edges.push((
+                    from_pos,
+                    to_pos,
+                    TreeEdge {
+                        id: edge.id,
+                        from,
+                        to,
+                        weight: edge.weight,
+                    },
+                ));
+            }
+        }
+
+        edges.sort_by(|(_, _, a), (_, _, b)| match objective {
+            Objective::Minimize => (a.weight, a.id).cmp(&(b.weight, b.id)),
+            Objective::Maximize => (Reverse(a.weight), a.id).cmp(&(Reverse(b.weight), b.id)),
+        });
+        Ok(Self { nodes, edges })
+    }
+
+    /// Лес из ребер с данными рангами
+    fn forest(&self, mut ranks: Vec<usize>) -> Result<SpanningForest<W>> {
+        ranks.sort_by_key(|&rank| self.edges[rank].2.id);
+        let mut total = W::ZERO;
+        let mut edges = Vec::with_capacity(ranks.len());
+        for rank in ranks {
+            let edge = self.edges[rank].2;
+            total = total.try_add(edge.weight)?;
+            edges.push(edge);
+        }
+        Ok(SpanningForest {
+            components: self.nodes.len() - edges.len(),
+            edges,
+            total,
+        })
+    }
+}
+
+/// Остовный лес алгоритмом Краскала: ребра перебираются от лучшего к
+/// худшему, ребро берется, если соединяет разные деревья (проверка через
+/// [`UnionFind`]). `O(E log E)`.
+///
+/// С [`Objective::Maximize`] строится остов максимального веса.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный или вес
+/// переполнился.
+pub fn kruskal<T, W>(graph: &Graph<T, W>, objective: Objective) -> Result<SpanningForest<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let ranked = RankedEdges::new(graph, objective)?;
+    let mut sets: UnionFind = ranked.nodes.iter().copied().collect();
+    let chosen = ranked
+        .edges
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, _, edge))| sets.union(edge.from, edge.to))
+        .map(|(rank, _)| rank)
+        .collect();
+    ranked.forest(chosen)
+}
+
+/// Остовный лес алгоритмом Прима: дерево растет из наименьшей еще не
+/// покрытой вершины, следующее ребро --- лучшее из выходящих из дерева
+/// (двоичная куча). `O(E log E)`.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный или вес
+/// переполнился.
+pub fn prim<T, W>(graph: &Graph<T, W>, objective: Objective) -> Result<SpanningForest<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let ranked = RankedEdges::new(graph, objective)?;
+    let n = ranked.nodes.len();
+    let mut incident: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    for (rank, &(from, to, _)) in ranked.edges.iter().enumerate() {
+        incident[from].push((rank, to));
+        incident[to].push((rank, from));
+    }
+
+    let mut in_tree = vec![false; n];
+    let mut chosen = Vec::new();
+    for root in 0..n {
+        if in_tree[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        let mut heap: BinaryHeap<Reverse<(usize, usize)>> =
+            incident[root].iter().copied().map(Reverse).collect();
+        while let Some(Reverse((rank, to))) = heap.pop() {
+            if in_tree[to] {
+                continue;
+            }
+            in_tree[to] = true;
+            chosen.push(rank);
+            heap.extend(
+                incident[to]
+                    .iter()
+                    .filter(|&&(_, next)| !in_tree[next])
+                    .copied()
+                    .map(Reverse),
+            );
+        }
+    }
+    ranked.forest(chosen)
+}
+
+/// Остовный лес алгоритмом Борувки: на каждом шаге каждое дерево
+/// добавляет лучшее ребро, ведущее из него наружу; число деревьев
+/// уменьшается хотя бы вдвое. `O(E log V)`.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный или вес
+/// переполнился.
+pub fn boruvka<T, W>(graph: &Graph<T, W>, objective: Objective) -> Result<SpanningForest<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    let ranked = RankedEdges::new(graph, objective)?;
+    let mut sets: UnionFind = ranked.nodes.iter().copied().collect();
+    let mut chosen = Vec::new();
+
+    loop {
+        // Лучшее ребро из каждого дерева (по представителю множества)
+        let mut best: HashMap<Index, usize> = HashMap::new();
+        for (rank, (_, _, edge)) in ranked.edges.iter().enumerate() {
+            let (from, to) = (sets.find(edge.from), sets.find(edge.to));
+            if from == to {
+                continue;
+            }
+            for root in [from, to].into_iter().flatten() {
+                let old = best.entry(root).or_insert(rank);
+                *old = (*old).min(rank);
+            }
+        }
+
+        let mut merged = false;
+        for rank in best.into_values() {
+            let edge = ranked.edges[rank].2;
+            // Ребро могло быть выбрано обоими деревьями
+            if sets.union(edge.from, edge.to) {
+                chosen.push(rank);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    ranked.forest(chosen)
+}
@@ -0,0 +1,97 @@
+//! Общее для тестов алгоритмов: графы по списку ребер, воспроизводимый
+//! генератор случайных чисел и проверки результатов.
+
+use std::{collections::HashMap, error::Error, fmt::Debug};
+
+use crate::graph::core::{Edge, EdgeId, Graph, GraphError, GraphKindError, Index, Node};
+
+/// Мультиграф на вершинах `1..=n` без ребер
+pub(super) fn nodes(n: u32, directed: bool) -> Graph<(), i64> {
+    let mut graph = Graph::default();
+    if directed {
+        graph.to_directed_mut();
+    }
+    graph.to_multigraph_mut();
+    for index in 1..=n {
+        graph.add_node(Node::new(Index(index), ())).unwrap();
+    }
+    graph
+}
+
+/// Добавляет ребро `edge` из вершины `from` и возвращает его id
+pub(super) fn connect(graph: &mut Graph<(), i64>, from: u32, edge: Edge<(), i64>) -> EdgeId {
+    graph.add_edge(&Node::new(Index(from), ()), &edge).unwrap()
+}
+
+/// Мультиграф на вершинах `1..=n` с ребрами `(откуда, куда, вес)`
+pub(super) fn graph(n: u32, directed: bool, edges: &[(u32, u32, i64)]) -> Graph<(), i64> {
+    let mut graph = nodes(n, directed);
+    for &(from, to, weight) in edges {
+        connect(&mut graph, from, Edge::new(&Index(to), weight, &()));
+    }
+    graph
+}
+
+/// Генератор xorshift64: случайные тесты воспроизводимы
+pub(super) struct Rng(u64);
+
+impl Rng {
+    /// `seed` не должен быть нулем
+    pub(super) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Случайное число из `0..bound`
+    pub(super) fn below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+
+    /// Ребра между вершинами `a < b` из `1..=n`, каждое с вероятностью
+    /// `percent`%, с весами из `1..=max_weight`
+    pub(super) fn edges(&mut self, n: u32, percent: u64, max_weight: u64) -> Vec<(u32, u32, i64)> {
+        let mut edges = Vec::new();
+        for a in 1..=n {
+            for b in a + 1..=n {
+                if self.below(100) < percent {
+                    edges.push((a, b, self.below(max_weight) as i64 + 1));
+                }
+            }
+        }
+        edges
+    }
+}
+
+/// Результат --- ошибка [`GraphError`] вида `kind`
+pub(super) fn assert_kind<T: Debug>(result: Result<T, Box<dyn Error>>, kind: GraphKindError) {
+    let error = result.unwrap_err();
+    let error = error.downcast_ref::<GraphError>().expect("ошибка графа");
+    assert_eq!(error.kind(), &kind, "{error}");
+}
+
+/// Поток по дугам `(откуда, куда, поток)` сохраняется во всех вершинах,
+/// кроме истока и стока, и равен `value`
+pub(super) fn assert_conserved(
+    flows: impl IntoIterator<Item = (Index, Index, i64)>,
+    source: Index,
+    sink: Index,
+    value: i64,
+) {
+    let mut balance: HashMap<Index, i64> = HashMap::new();
+    for (from, to, flow) in flows {
+        *balance.entry(from).or_default() -= flow;
+        *balance.entry(to).or_default() += flow;
+    }
+    for (index, found) in balance {
+        let expected = if index == source {
+            -value
+        } else if index == sink {
+            value
+        } else {
+            0
+        };
+        assert_eq!(found, expected, "баланс вершины {index}");
+    }
+}
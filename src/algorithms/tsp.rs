@@ -0,0 +1,683 @@
+//! Гамильтоновы циклы и пути, задача коммивояжера: точные алгоритмы
+//! (Хелд–Карп, метод ветвей и границ) и эвристики (ближайший сосед,
+//! 2-opt, Кристофидес).
+
+use std::{collections::HashMap, error::Error, fmt::Debug};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    algorithms::{
+        assignment::Objective,
+        blossom::minimum_weight_perfect_matching,
+        components::sorted_nodes,
+        euler::{TrailEdge, build_trail},
+        spanning_tree::kruskal,
+    },
+    graph::{
+        core::{Graph, GraphError, GraphKindError, Index},
+        view::{GraphView, ensure_undirected},
+        weight::Weight,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Наибольшее число вершин для [`held_karp`]: таблица занимает
+/// `2^(n-1) * (n-1)` ячеек
+pub const HELD_KARP_MAX_NODES: usize = 20;
+
+/// Маршрут: вершины по порядку обхода и его стоимость. Для цикла первая
+/// вершина не повторяется в конце, в стоимость входит ребро из последней
+/// в первую.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tour<W> {
+    nodes: Vec<Index>,
+    cost: W,
+    is_cycle: bool,
+}
+
+impl<W: Weight> Tour<W> {
+    /// Вершины по порядку обхода
+    pub fn nodes(&self) -> &[Index] {
+        &self.nodes
+    }
+
+    /// Суммарный вес ребер маршрута
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+
+    /// Маршрут замкнут (цикл), а не путь
+    pub fn is_cycle(&self) -> bool {
+        self.is_cycle
+    }
+
+    pub fn into_nodes(self) -> Vec<Index> {
+        self.nodes
+    }
+}
+
+/// Матрица весов на плотных номерах (по возрастанию [`Index`]); из
+/// параллельных ребер берется самое легкое, петли не нужны
+struct Distances<W> {
+    nodes: Vec<Index>,
+    weight: Vec<Vec<Option<W>>>,
+}
+
+impl<W: Weight> Distances<W> {
+    fn new<G: GraphView<Weight = W>>(graph: &G) -> Self {
+        let nodes = sorted_nodes(graph);
+        let positions: HashMap<Index, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos))
+            .collect();
+        let mut weight = vec![vec![None; nodes.len()]; nodes.len()];
+        for (from, &index) in nodes.iter().enumerate() {
+            for (to, found) in graph.neighbors(index) {
+                let Some(&to) = positions.get(&to) else {
+                    continue;
+                };
+                let cell: &mut Option<W> = &mut weight[from][to];
+                if from != to && cell.is_none_or(|old| found < old) {
+                    *cell = Some(found);
+                }
+            }
+        }
+        Self { nodes, weight }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn position(&self, index: Index) -> Result<usize> {
+        self.nodes.binary_search(&index).map_err(|_| {
+            Box::new(GraphError::new(
+                GraphKindError::NodeNotFound,
+                &format!("вершина {index}"),
+            )) as Box<dyn Error>
+        })
+    }
+
+    /// Стоимость маршрута по плотным номерам; [`None`], если какого-то
+    /// ребра нет
+    fn cost(&self, order: &[usize], is_cycle: bool) -> Result<Option<W>> {
+        let closing = match (is_cycle && order.len() > 1, order.first(), order.last()) {
+            (true, Some(&first), Some(&last)) => Some((last, first)),
+            _ => None,
+        };
+        let mut total = W::ZERO;
+        for (from, to) in order
+            .windows(2)
+            .map(|step| (step[0], step[1]))
+            .chain(closing)
+        {
+            let Some(weight) = self.weight[from][to] else {
+                return Ok(None);
+            };
+            total = total.try_add(weight)?;
+        }
+        Ok(Some(total))
+    }
+
+    /// Обход ближайшего соседа из start; [`None`], если зашли в тупик
+    fn greedy_order(&self, start: usize) -> Option<Vec<usize>> {
+        let n = self.len();
+        let mut visited = vec![false; n];
+        let mut order = vec![start];
+        visited[start] = true;
+        while order.len() < n {
+            let last = *order.last().expect("маршрут начат");
+            let (_, next) = (0..n)
+                .filter(|&next| !visited[next])
+                .filter_map(|next| self.weight[last][next].map(|weight| (weight, next)))
+                .min()?;
+            visited[next] = true;
+            order.push(next);
+        }
+        Some(order)
+    }
+
+    fn tour(&self, order: &[usize], is_cycle: bool) -> Result<Option<Tour<W>>> {
+        Ok(self.cost(order, is_cycle)?.map(|cost| Tour {
+            nodes: order.iter().map(|&pos| self.nodes[pos]).collect(),
+            cost,
+            is_cycle,
+        }))
+    }
+}
+
+/// Кратчайший гамильтонов цикл динамикой Хелда–Карпа по подмножествам:
+/// `best[S][v]` --- кратчайший путь из первой вершины через все вершины
+/// `S`, заканчивающийся в `v`. `O(2^n n²)` времени и `O(2^n n)` памяти,
+/// поэтому вершин не больше [`HELD_KARP_MAX_NODES`]. Возвращает [`None`],
+/// если гамильтонова цикла нет.
+///
+/// # Errors
+/// Вернет [`GraphError`], если вершин слишком много или стоимость
+/// переполнилась.
+pub fn held_karp<G: GraphView>(graph: &G) -> Result<Option<Tour<G::Weight>>> {
+    let distances = Distances::new(graph);
+    let n = distances.len();
+    if n > HELD_KARP_MAX_NODES {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::GraphTooLarge,
+            &format!("{n} вершин, Хелд–Карп считает до {HELD_KARP_MAX_NODES}"),
+        )));
+    }
+    if n <= 1 {
+        return distances.tour(&(0..n).collect::<Vec<_>>(), true);
+    }
+
+    // Вершина 0 --- начало цикла, в подмножествах только остальные:
+    // бит j --- вершина j + 1
+    const UNREACHED: u8 = u8::MAX;
+    const FROM_START: u8 = u8::MAX - 1;
+    let m = n - 1;
+    let full = (1usize << m) - 1;
+    let mut best = vec![<G::Weight as Weight>::ZERO; (full + 1) * m];
+    let mut parent = vec![UNREACHED; (full + 1) * m];
+    for last in 0..m {
+        if let Some(weight) = distances.weight[0][last + 1] {
+            best[(1 << last) * m + last] = weight;
+            parent[(1 << last) * m + last] = FROM_START;
+        }
+    }
+
+    for set in 1..=full {
+        for last in 0..m {
+            let cell = set * m + last;
+            if parent[cell] == UNREACHED {
+                continue;
+            }
+            let length = best[cell];
+            for next in (0..m).filter(|&next| set & (1 << next) == 0) {
+                let Some(weight) = distances.weight[last + 1][next + 1] else {
+                    continue;
+                };
+                let candidate = length.try_add(weight)?;
+                let target = (set | (1 << next)) * m + next;
+                if parent[target] == UNREACHED || candidate < best[target] {
+                    best[target] = candidate;
+                    parent[target] = last as u8;
+                }
+            }
+        }
+    }
+
+    let mut finish: Option<(G::Weight, usize)> = None;
+    for last in 0..m {
+        let cell = full * m + last;
+        if parent[cell] == UNREACHED {
+            continue;
+        }
+        let Some(weight) = distances.weight[last + 1][0] else {
+            continue;
+        };
+        let total = best[cell].try_add(weight)?;
+        if finish.is_none_or(|(old, _)| total < old) {
+            finish = Some((total, last));
+        }
+    }
+    let Some((_, mut last)) = finish else {
+        return Ok(None);
+    };
+
+    let mut order = Vec::with_capacity(n);
+    let mut set = full;
+    loop {
+        order.push(last + 1);
+        let previous = parent[set * m + last];
+        set &= !(1 << last);
+        if previous == FROM_START {
+            break;
+        }
+        last = previous as usize;
+    }
+    order.push(0);
+    order.reverse();
+    distances.tour(&order, true)
+}
+
+/// Перебор с отсечениями: маршрут продлевается ребрами от легких к
+/// тяжелым, ветка отбрасывается, если ее стоимость вместе с нижней
+/// оценкой остатка не лучше найденного. Оценка --- сумма самых легких
+/// входящих ребер вершин, в которые еще предстоит войти
+struct BranchAndBound<'a, W> {
+    distances: &'a Distances<W>,
+    // Соседи каждой вершины по возрастанию веса ребра
+    sorted: Vec<Vec<(W, usize)>>,
+    cheapest_in: Vec<W>,
+    is_cycle: bool,
+    visited: Vec<bool>,
+    order: Vec<usize>,
+    best: Option<(W, Vec<usize>)>,
+}
+
+impl<'a, W: Weight> BranchAndBound<'a, W> {
+    /// [`None`], если в какую-то вершину не входит ни одного ребра
+    fn new(distances: &'a Distances<W>, is_cycle: bool) -> Option<Self> {
+        let n = distances.len();
+        let sorted = (0..n)
+            .map(|from| {
+                let mut next: Vec<(W, usize)> = (0..n)
+                    .filter_map(|to| distances.weight[from][to].map(|weight| (weight, to)))
+                    .collect();
+                next.sort();
+                next
+            })
+            .collect();
+        let mut cheapest_in = Vec::with_capacity(n);
+        for to in 0..n {
+            let cheapest = (0..n).filter_map(|from| distances.weight[from][to]).min();
+            match cheapest {
+                Some(weight) => cheapest_in.push(weight),
+                // Без входящих ребер в вершину можно только начать путь
+                None if !is_cycle => cheapest_in.push(W::ZERO),
+                None => return None,
+            }
+        }
+        Some(Self {
+            distances,
+            sorted,
+            cheapest_in,
+            is_cycle,
+            visited: vec![false; n],
+            order: Vec::with_capacity(n),
+            best: None,
+        })
+    }
+
+    fn search(&mut self, cost: W, bound: W) -> Result<()> {
+        // При переполнении оценки ветку не отбрасываем
+        let lower = cost.checked_add(bound);
+        if let (Some((best, _)), Some(lower)) = (&self.best, lower)
+            && lower >= *best
+        {
+            return Ok(());
+        }
+        let last = *self.order.last().expect("маршрут начат");
+        if self.order.len() == self.distances.len() {
+            let total = if self.is_cycle {
+                match self.distances.weight[last][self.order[0]] {
+                    Some(weight) => cost.try_add(weight)?,
+                    None => return Ok(()),
+                }
+            } else {
+                cost
+            };
+            if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+                self.best = Some((total, self.order.clone()));
+            }
+            return Ok(());
+        }
+
+        for position in 0..self.sorted[last].len() {
+            let (weight, next) = self.sorted[last][position];
+            if self.visited[next] {
+                continue;
+            }
+            let remaining = bound.checked_sub(self.cheapest_in[next]).ok_or_else(|| {
+                GraphError::new(
+                    GraphKindError::WeightOverflow,
+                    &format!("{bound} - {}", self.cheapest_in[next]),
+                )
+            })?;
+            self.visited[next] = true;
+            self.order.push(next);
+            self.search(cost.try_add(weight)?, remaining)?;
+            self.order.pop();
+            self.visited[next] = false;
+        }
+        Ok(())
+    }
+
+    /// Перебор из вершины start
+    fn run_from(&mut self, start: usize) -> Result<()> {
+        let mut bound = W::ZERO;
+        for (to, &cheapest) in self.cheapest_in.iter().enumerate() {
+            // В начало пути не входят, в начало цикла входят в конце
+            if to != start || self.is_cycle {
+                bound = bound.try_add(cheapest)?;
+            }
+        }
+        self.visited[start] = true;
+        self.order.push(start);
+        self.search(W::ZERO, bound)?;
+        self.order.pop();
+        self.visited[start] = false;
+        Ok(())
+    }
+}
+
+fn branch_and_bound<G: GraphView>(graph: &G, is_cycle: bool) -> Result<Option<Tour<G::Weight>>> {
+    let distances = Distances::new(graph);
+    let n = distances.len();
+    if n <= 1 {
+        return distances.tour(&(0..n).collect::<Vec<_>>(), is_cycle);
+    }
+    let Some(mut search) = BranchAndBound::new(&distances, is_cycle) else {
+        return Ok(None);
+    };
+    // Начальная граница --- маршрут ближайшего соседа, если он есть
+    if let Some(order) = distances.greedy_order(0)
+        && let Some(cost) = distances.cost(&order, is_cycle)?
+    {
+        search.best = Some((cost, order));
+    }
+    // Цикл проходит через все вершины, поэтому начинать можно с первой
+    let starts = if is_cycle { 1 } else { n };
+    for start in 0..starts {
+        search.run_from(start)?;
+    }
+    match search.best {
+        Some((_, order)) => distances.tour(&order, is_cycle),
+        None => Ok(None),
+    }
+}
+
+/// Кратчайший гамильтонов цикл методом ветвей и границ. Время в худшем
+/// случае экспоненциальное, но ограничения на число вершин нет; веса
+/// могут быть отрицательными. Возвращает [`None`], если гамильтонова
+/// цикла нет.
+///
+/// # Errors
+/// Вернет [`GraphError`], если стоимость переполнилась.
+pub fn hamiltonian_cycle<G: GraphView>(graph: &G) -> Result<Option<Tour<G::Weight>>> {
+    branch_and_bound(graph, true)
+}
+
+/// Кратчайший гамильтонов путь (с любыми концами) методом ветвей и
+/// границ. Возвращает [`None`], если гамильтонова пути нет.
+///
+/// # Errors
+/// Вернет [`GraphError`], если стоимость переполнилась.
+pub fn hamiltonian_path<G: GraphView>(graph: &G) -> Result<Option<Tour<G::Weight>>> {
+    branch_and_bound(graph, false)
+}
+
+/// Эвристика ближайшего соседа: из `start` каждый раз идем по самому
+/// легкому ребру в еще не посещенную вершину, в конце возвращаемся в
+/// `start`. `O(n²)`. Возвращает [`None`], если жадный обход зашел в
+/// тупик или из последней вершины нет ребра в `start`.
+///
+/// # Errors
+/// Вернет [`GraphError`], если вершины `start` нет или стоимость
+/// переполнилась.
+pub fn nearest_neighbor<G: GraphView>(graph: &G, start: Index) -> Result<Option<Tour<G::Weight>>> {
+    let distances = Distances::new(graph);
+    match distances.greedy_order(distances.position(start)?) {
+        Some(order) => distances.tour(&order, true),
+        None => Ok(None),
+    }
+}
+
+/// Улучшает маршрут заменами 2-opt: пара ребер `(a, b)`, `(c, d)`
+/// меняется на `(a, c)`, `(b, d)` с разворотом участка между ними, пока
+/// это уменьшает стоимость. Для пути концы не меняются. Результат ---
+/// локальный минимум, не хуже исходного.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный (разворот участка
+/// меняет стоимость его ребер), вершины маршрута нет или ребра между
+/// соседними вершинами маршрута нет.
+pub fn two_opt<G: GraphView>(graph: &G, tour: &Tour<G::Weight>) -> Result<Tour<G::Weight>> {
+    ensure_undirected(graph, "2-opt работает с неориентированным графом")?;
+    let distances = Distances::new(graph);
+    let mut order = tour
+        .nodes
+        .iter()
+        .map(|&index| distances.position(index))
+        .collect::<Result<Vec<usize>>>()?;
+    if distances.cost(&order, tour.is_cycle)?.is_none() {
+        return Err(Box::new(GraphError::new(
+            GraphKindError::EdgeNotFound,
+            "между соседними вершинами маршрута нет ребра",
+        )));
+    }
+
+    let n = order.len();
+    // Число ребер маршрута; ребро i --- из order[i] в order[i + 1]
+    let edges = if tour.is_cycle {
+        n
+    } else {
+        n.saturating_sub(1)
+    };
+    let weight =
+        |order: &[usize], from: usize, to: usize| distances.weight[order[from]][order[to % n]];
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..edges {
+            for j in i + 2..edges {
+                // У цикла первое и последнее ребра соседние
+                if tour.is_cycle && i == 0 && j == n - 1 {
+                    continue;
+                }
+                let (Some(ab), Some(cd), Some(ac), Some(bd)) = (
+                    weight(&order, i, i + 1),
+                    weight(&order, j, j + 1),
+                    weight(&order, i, j),
+                    weight(&order, i + 1, j + 1),
+                ) else {
+                    continue;
+                };
+                if ac.try_add(bd)? < ab.try_add(cd)? {
+                    order[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+
+    Ok(distances
+        .tour(&order, tour.is_cycle)?
+        .expect("ребра маршрута проверены"))
+}
+
+/// Эвристика Кристофидеса для метрического графа (полного, с
+/// неравенством треугольника): минимальный остов ([`kruskal`]), к нему
+/// совершенное паросочетание минимального веса на вершинах нечетной
+/// степени, эйлеров цикл получившегося мультиграфа и пропуск повторных
+/// вершин. Маршрут не более чем в полтора раза длиннее оптимального.
+/// `O(n³)`.
+///
+/// # Errors
+/// Вернет [`GraphError`], если граф ориентированный, не полный или
+/// стоимость переполнилась.
+pub fn christofides<T, W>(graph: &Graph<T, W>) -> Result<Tour<W>>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    ensure_undirected(graph, "Кристофидес работает с неориентированным графом")?;
+    let distances = Distances::new(graph);
+    let n = distances.len();
+    for from in 0..n {
+        if let Some(to) = (from + 1..n).find(|&to| distances.weight[from][to].is_none()) {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::EdgeNotFound,
+                &format!(
+                    "граф должен быть полным: нет ребра между {} и {}",
+                    distances.nodes[from], distances.nodes[to]
+                ),
+            )));
+        }
+    }
+    if n <= 2 {
+        return Ok(distances
+            .tour(&(0..n).collect::<Vec<_>>(), true)?
+            .expect("граф полный"));
+    }
+
+    let mut edges: Vec<TrailEdge<W>> = kruskal(graph, Objective::Minimize)?
+        .edges()
+        .iter()
+        .map(|edge| TrailEdge {
+            id: edge.id,
+            from: edge.from,
+            to: edge.to,
+            weight: edge.weight,
+        })
+        .collect();
+
+    let mut degree: HashMap<Index, usize> = HashMap::new();
+    for edge in &edges {
+        *degree.entry(edge.from).or_default() += 1;
+        *degree.entry(edge.to).or_default() += 1;
+    }
+    let mut odd: Vec<Index> = degree
+        .into_iter()
+        .filter(|&(_, degree)| degree % 2 == 1)
+        .map(|(index, _)| index)
+        .collect();
+    odd.sort();
+
+    // Ребро паросочетания --- самое легкое из параллельных
+    let lightest = |from: Index, to: Index| {
+        graph
+            .get_adjacency(&from)
+            .into_iter()
+            .flatten()
+            .filter(|edge| edge.node.number == to)
+            .min_by_key(|edge| (edge.weight, edge.id))
+    };
+    let matching = minimum_weight_perfect_matching(&odd, |from, to| {
+        lightest(from, to).map(|edge| edge.weight)
+    })?;
+    for &(from, to) in matching.pairs() {
+        let edge = lightest(from, to).expect("граф полный");
+        edges.push(TrailEdge {
+            id: edge.id,
+            from,
+            to,
+            weight: edge.weight,
+        });
+    }
+
+    // Эйлеров цикл с пропуском уже пройденных вершин
+    let circuit = build_trail(&edges, false, distances.nodes[0])?;
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for index in circuit.nodes() {
+        let position = distances.position(index)?;
+        if !visited[position] {
+            visited[position] = true;
+            order.push(position);
+        }
+    }
+    Ok(distances.tour(&order, true)?.expect("граф полный"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::test_support::{Rng, assert_kind, graph, nodes};
+
+    /// Вершины маршрута --- перестановка `1..=n`, стоимость --- сумма
+    /// легчайших ребер между соседними вершинами
+    fn assert_tour(n: u32, edges: &[(u32, u32, i64)], directed: bool, tour: &Tour<i64>) {
+        let mut sorted: Vec<u32> = tour.nodes().iter().map(|index| **index).collect();
+        sorted.sort();
+        assert_eq!(sorted, (1..=n).collect::<Vec<_>>());
+        if n < 3 {
+            return;
+        }
+        let weight = |a: Index, b: Index| {
+            edges
+                .iter()
+                .filter(|&&(x, y, _)| {
+                    (Index(x), Index(y)) == (a, b) || !directed && (Index(y), Index(x)) == (a, b)
+                })
+                .map(|&(_, _, weight)| weight)
+                .min()
+                .expect("ребро маршрута")
+        };
+        let nodes = tour.nodes();
+        let cost: i64 = (0..nodes.len())
+            .map(|i| weight(nodes[i], nodes[(i + 1) % nodes.len()]))
+            .sum();
+        assert_eq!(tour.cost(), cost);
+    }
+
+    #[test]
+    fn held_karp_matches_branch_and_bound() {
+        let mut rng = Rng::new(0x94d0_49bb_1331_11eb);
+        for _ in 0..300 {
+            let n = 1 + rng.below(7) as u32;
+            let directed = rng.below(2) == 0;
+            let mut edges = Vec::new();
+            for a in 1..=n {
+                for b in 1..=n {
+                    if a != b && (directed || a < b) && rng.below(100) < 70 {
+                        edges.push((a, b, rng.below(20) as i64 + 1));
+                    }
+                }
+            }
+            let graph = graph(n, directed, &edges);
+            let exact = held_karp(&graph).unwrap();
+            let searched = hamiltonian_cycle(&graph).unwrap();
+            assert_eq!(
+                exact.as_ref().map(Tour::cost),
+                searched.as_ref().map(Tour::cost),
+                "ребра {edges:?}"
+            );
+            for tour in exact.iter().chain(&searched) {
+                assert!(tour.is_cycle());
+                assert_tour(n, &edges, directed, tour);
+            }
+        }
+    }
+
+    #[test]
+    fn heuristics_on_metric_graphs() {
+        let mut rng = Rng::new(0xbf58_476d_1ce4_e5b9);
+        for _ in 0..100 {
+            // Манхэттенские расстояния между точками --- метрика
+            let n = 3 + rng.below(6) as u32;
+            let points: Vec<(i64, i64)> = (0..n)
+                .map(|_| (rng.below(20) as i64, rng.below(20) as i64))
+                .collect();
+            let mut edges = Vec::new();
+            for a in 1..=n {
+                for b in a + 1..=n {
+                    let ((x1, y1), (x2, y2)) = (points[a as usize - 1], points[b as usize - 1]);
+                    edges.push((a, b, (x1 - x2).abs() + (y1 - y2).abs()));
+                }
+            }
+            let graph = graph(n, false, &edges);
+            let optimum = held_karp(&graph).unwrap().unwrap().cost();
+
+            let greedy = nearest_neighbor(&graph, Index(1)).unwrap().unwrap();
+            assert_tour(n, &edges, false, &greedy);
+            let improved = two_opt(&graph, &greedy).unwrap();
+            assert_tour(n, &edges, false, &improved);
+            assert!(optimum <= improved.cost() && improved.cost() <= greedy.cost());
+
+            let approximate = christofides(&graph).unwrap();
+            assert_tour(n, &edges, false, &approximate);
+            assert!(optimum <= approximate.cost() && 2 * approximate.cost() <= 3 * optimum);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let cycle = graph(3, true, &[(1, 2, 1), (2, 3, 1), (3, 1, 1)]);
+        let tour = held_karp(&cycle).unwrap().unwrap();
+        assert_kind(
+            two_opt(&cycle, &tour),
+            GraphKindError::GraphMustBeUndirected,
+        );
+        assert_kind(christofides(&cycle), GraphKindError::GraphMustBeUndirected);
+
+        let path = graph(3, false, &[(1, 2, 1), (2, 3, 1)]);
+        assert!(held_karp(&path).unwrap().is_none());
+        assert!(hamiltonian_cycle(&path).unwrap().is_none());
+        assert_kind(christofides(&path), GraphKindError::EdgeNotFound);
+
+        let large = nodes(HELD_KARP_MAX_NODES as u32 + 1, false);
+        assert_kind(held_karp(&large), GraphKindError::GraphTooLarge);
+    }
+}
@@ -2,7 +2,6 @@ use std::{
     collections::{
         HashMap, HashSet,
         hash_map::{self, Entry},
-        hash_set,
     },
     error::Error,
     fmt::{Debug, Display},
@@ -10,17 +9,17 @@ use std::{
     hash::Hash,
     io::{BufReader, BufWriter, Write},
     ops::Deref,
+    slice,
 };
 
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
+use crate::graph::{union_find::UnionFind, view::GraphView, weight::Weight};
+
 #[derive(Debug, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Index(pub u32);
 
-
-impl PartialEq<u32> for Index
-where
-{
+impl PartialEq<u32> for Index {
     fn eq(&self, other: &u32) -> bool {
         self.0 == *other
     }
@@ -44,9 +43,22 @@ impl From<u32> for Index {
     }
 }
 
-type Weight = u32;
+/// Идентификатор ребра. Не меняется при удалении других ребер; обе
+/// половины неориентированного ребра имеют один id. `EdgeId(0)` ---
+/// еще не назначенный id.
+#[derive(
+    Debug, Default, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash,
+)]
+pub struct EdgeId(pub u32);
+
+impl Display for EdgeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
-type PairEdges<T> = (Option<Edge<T>>, Option<Edge<T>>);
+type PairEdges<T, W> = (Option<Edge<T, W>>, Option<Edge<T, W>>);
 
 #[derive(Debug)]
 pub struct GraphError {
@@ -54,11 +66,21 @@ pub struct GraphError {
     description: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum GraphKindError {
     NodeAlreadyExist,
     NodeNotFound,
+    EdgeNotFound,
+    EdgeAlreadyExist,
     GraphMustBeDirected,
+    GraphMustBeUndirected,
+    WeightOverflow,
+    NegativeWeight,
+    GraphMustBeAcyclic,
+    InfeasibleFlow,
+    GraphMustBeBipartite,
+    GraphMustBeEulerian,
+    GraphTooLarge,
 }
 
 impl GraphError {
@@ -68,6 +90,11 @@ impl GraphError {
             description: description.to_string(),
         }
     }
+
+    /// Returns the kind of this [`GraphError`].
+    pub fn kind(&self) -> &GraphKindError {
+        &self.kind
+    }
 }
 
 impl Error for GraphError {
@@ -95,6 +122,22 @@ impl Display for GraphError {
                 )
             }
 
+            GraphKindError::EdgeNotFound => {
+                write!(
+                    f,
+                    "this edge not found in graph.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::EdgeAlreadyExist => {
+                write!(
+                    f,
+                    "this edge already exist in graph.\nDescription: {}",
+                    &self.description
+                )
+            }
+
             GraphKindError::GraphMustBeDirected => {
                 write!(
                     f,
@@ -102,6 +145,70 @@ impl Display for GraphError {
                     &self.description
                 )
             }
+
+            GraphKindError::GraphMustBeUndirected => {
+                write!(
+                    f,
+                    "this graph must be undirected.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::WeightOverflow => {
+                write!(
+                    f,
+                    "sum of weights overflowed.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::NegativeWeight => {
+                write!(
+                    f,
+                    "this edge weight must be non-negative.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::GraphMustBeAcyclic => {
+                write!(
+                    f,
+                    "this graph must be acyclic.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::InfeasibleFlow => {
+                write!(
+                    f,
+                    "the required flow cannot be sent.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::GraphMustBeBipartite => {
+                write!(
+                    f,
+                    "this graph must be bipartite.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::GraphMustBeEulerian => {
+                write!(
+                    f,
+                    "this graph must have an Eulerian path.\nDescription: {}",
+                    &self.description
+                )
+            }
+
+            GraphKindError::GraphTooLarge => {
+                write!(
+                    f,
+                    "this graph is too large for the algorithm.\nDescription: {}",
+                    &self.description
+                )
+            }
         }
     }
 }
@@ -139,96 +246,148 @@ impl<T> Node<T> {
 // Edge part
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Edge<T>
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Edge<T, W = u32>
 where
     T: Clone,
+    W: Weight,
 {
     pub node: Node<T>,
-    pub weight: Weight,
+    pub weight: W,
+    #[serde(default)]
+    pub id: EdgeId,
+    /// Пропускная способность в потоковых задачах; если не задана ---
+    /// вес ребра
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<W>,
+    /// Стоимость единицы потока; если не задана --- ноль
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<W>,
 }
 
-impl<T> PartialEq for Edge<T>
+/// Ребра сравниваются только по вершине, в которую идут
+impl<T, W> PartialEq for Edge<T, W>
 where
     T: Clone,
+    W: Weight,
 {
     fn eq(&self, other: &Self) -> bool {
         self.node.number == other.node.number
     }
 }
 
-impl<T> Eq for Edge<T> where T: Clone {}
+impl<T, W> Eq for Edge<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+}
 
-impl<T> Hash for Edge<T>
+impl<T, W> Hash for Edge<T, W>
 where
     T: Clone,
+    W: Weight,
 {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.node.number.hash(state);
     }
 }
 
-impl<T> Default for Edge<T>
+impl<T, W> Default for Edge<T, W>
 where
     T: Default + Clone,
+    W: Weight,
 {
     fn default() -> Self {
-        Edge::<T> {
+        Edge::<T, W> {
             node: Node::default(),
-            weight: 0,
+            weight: W::ZERO,
+            id: EdgeId::default(),
+            capacity: None,
+            cost: None,
         }
     }
 }
 
-impl<T> Display for Edge<T>
+impl<T, W> Display for Edge<T, W>
 where
     T: Display + Clone,
+    W: Weight,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "  Edge: {{\n    Number: {},\n    Weight: {},\n    Value: {}\n  }}",
-            self.node.number, self.weight, self.node.value
+            "  Edge: {{\n    Id: {},\n    Number: {},\n    Weight: {},\n    Value: {}\n  }}",
+            self.id, self.node.number, self.weight, self.node.value
         )
     }
 }
 
-impl<T> Edge<T>
+impl<T, W> Edge<T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    /// Creates a new [`Edge<T>`].
-    pub fn new(number: &Index, weight: Weight, value: &T) -> Self {
+    /// Creates a new [`Edge<T>`]. Id назначит граф при добавлении.
+    pub fn new(number: &Index, weight: W, value: &T) -> Self {
         Self {
             weight,
             node: Node::new(*number, value.clone()),
+            id: EdgeId::default(),
+            capacity: None,
+            cost: None,
         }
     }
+
+    /// Задает пропускную способность отдельно от веса
+    pub fn with_capacity(mut self, capacity: W) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Задает стоимость единицы потока
+    pub fn with_cost(mut self, cost: W) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Пропускная способность: заданная отдельно или вес ребра
+    pub fn flow_capacity(&self) -> W {
+        self.capacity.unwrap_or(self.weight)
+    }
+
+    /// Стоимость единицы потока (ноль, если не задана)
+    pub fn flow_cost(&self) -> W {
+        self.cost.unwrap_or(W::ZERO)
+    }
 }
 
 // Adjacency part
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Adjacency<T>
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Adjacency<T, W = u32>
 where
     T: Clone,
+    W: Weight,
 {
-    edges: HashSet<Edge<T>>,
+    edges: Vec<Edge<T, W>>,
 }
 
-impl<T> Default for Adjacency<T>
+impl<T, W> Default for Adjacency<T, W>
 where
     T: Clone,
+    W: Weight,
 {
     fn default() -> Self {
-        Self {
-            edges: HashSet::new(),
-        }
+        Self { edges: Vec::new() }
     }
 }
 
-impl<T> Display for Adjacency<T>
+impl<T, W> Display for Adjacency<T, W>
 where
     T: Display + Clone,
+    W: Weight,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut print_format = String::from("[\n");
@@ -240,71 +399,87 @@ where
     }
 }
 
-impl<T> Adjacency<T>
+impl<T, W> Adjacency<T, W>
 where
     T: Clone,
+    W: Weight,
 {
     /// Creates a new [`Adjacency<T>`]
-    pub fn new(edge: Edge<T>) -> Self {
-        let mut new_edges = HashSet::new();
-        new_edges.insert(edge);
-        Self { edges: new_edges }
+    pub fn new(edge: Edge<T, W>) -> Self {
+        Self { edges: vec![edge] }
     }
 
-    pub fn contains(&self, edge: &Edge<T>) -> bool {
+    /// Есть ли ребро в ту же вершину, что и edge
+    pub fn contains(&self, edge: &Edge<T, W>) -> bool {
         self.edges.contains(edge)
     }
 
+    /// Returns the edge with given id of this [`Adjacency<T>`].
+    pub fn get_by_id(&self, id: EdgeId) -> Option<&Edge<T, W>> {
+        self.edges.iter().find(|e| e.id == id)
+    }
+
+    /// Returns the iter of this [`Adjacency<T>`].
+    pub fn iter(&self) -> AdjacencyIter<'_, T, W> {
+        self.into_iter()
+    }
+
     pub fn len(&self) -> usize {
         self.edges.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
     /// Add in end new [`Edge<T>`]
-    fn push(&mut self, edge: Edge<T>) -> Result<()> {
-        self.edges.insert(edge);
+    fn push(&mut self, edge: Edge<T, W>) -> Result<()> {
+        self.edges.push(edge);
         Ok(())
     }
 
-    fn delete(&mut self, edge_index: Index) -> Result<Option<Edge<T>>> {
-        let rm_edge = self
-            .edges
-            .iter()
-            .find(|e| e.node.number == edge_index)
-            .cloned();
-
-        if let Some(rm_e) = rm_edge {
-            self.edges.remove(&rm_e);
-            Ok(Some(rm_e))
+    /// Удаляет первое ребро в вершину edge_index
+    fn delete(&mut self, edge_index: Index) -> Result<Option<Edge<T, W>>> {
+        if let Some(pos) = self.edges.iter().position(|e| e.node.number == edge_index) {
+            Ok(Some(self.edges.remove(pos)))
         } else {
             Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")))
         }
     }
+
+    fn delete_by_id(&mut self, id: EdgeId) -> Option<Edge<T, W>> {
+        let pos = self.edges.iter().position(|e| e.id == id)?;
+        Some(self.edges.remove(pos))
+    }
 }
 
-pub struct AdjacencyIter<'a, T>
+pub struct AdjacencyIter<'a, T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    inner: hash_set::Iter<'a, Edge<T>>,
+    inner: slice::Iter<'a, Edge<T, W>>,
 }
 
-impl<'a, T> Iterator for AdjacencyIter<'a, T>
+impl<'a, T, W> Iterator for AdjacencyIter<'a, T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    type Item = &'a Edge<T>;
+    type Item = &'a Edge<T, W>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
     }
 }
 
-impl<'a, T> IntoIterator for &'a Adjacency<T>
+impl<'a, T, W> IntoIterator for &'a Adjacency<T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    type Item = &'a Edge<T>;
-    type IntoIter = AdjacencyIter<'a, T>;
+    type Item = &'a Edge<T, W>;
+    type IntoIter = AdjacencyIter<'a, T, W>;
 
     fn into_iter(self) -> Self::IntoIter {
         AdjacencyIter {
@@ -316,18 +491,63 @@ where
 // Graph part
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Graph<T>
+#[serde(from = "GraphData<T, W>")]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Graph<T, W = u32>
 where
     T: Clone,
+    W: Weight,
 {
     nodes: HashMap<Index, Node<T>>,
-    adjacency: HashMap<Index, Adjacency<T>>,
+    adjacency: HashMap<Index, Adjacency<T, W>>,
     is_directed: bool,
+    is_multigraph: bool,
+    #[serde(skip)]
+    next_edge_id: u32,
+    /// Компоненты слабой связности, если включено их отслеживание
+    #[serde(skip)]
+    connectivity: Option<UnionFind>,
+}
+
+/// Граф в том виде, в котором он лежит в файле. В старых файлах нет
+/// `is_multigraph` и id ребер, их назначает [`Graph::assign_edge_ids`].
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct GraphData<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    nodes: HashMap<Index, Node<T>>,
+    adjacency: HashMap<Index, Adjacency<T, W>>,
+    is_directed: bool,
+    #[serde(default)]
+    is_multigraph: bool,
+}
+
+impl<T, W> From<GraphData<T, W>> for Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    fn from(data: GraphData<T, W>) -> Self {
+        let mut graph = Self {
+            nodes: data.nodes,
+            adjacency: data.adjacency,
+            is_directed: data.is_directed,
+            is_multigraph: data.is_multigraph,
+            next_edge_id: 0,
+            connectivity: None,
+        };
+        graph.assign_edge_ids();
+        graph
+    }
 }
 
-impl<T> Display for Graph<T>
+impl<T, W> Display for Graph<T, W>
 where
     T: Display + Clone,
+    W: Weight,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut print_format = String::new();
@@ -338,26 +558,141 @@ where
     }
 }
 
-impl<T> Default for Graph<T>
+impl<T, W> Default for Graph<T, W>
 where
     T: Clone,
+    W: Weight,
 {
     fn default() -> Self {
         Self {
             nodes: HashMap::new(),
             adjacency: HashMap::new(),
             is_directed: false,
+            is_multigraph: false,
+            next_edge_id: 1,
+            connectivity: None,
+        }
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Назначает id ребрам без id. Половины неориентированного ребра
+    /// получают общий id.
+    fn assign_edge_ids(&mut self) {
+        let mut next = self
+            .adjacency
+            .values()
+            .flat_map(|adj| adj.edges.iter())
+            .map(|edge| edge.id.0)
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        let mut indices: Vec<Index> = self.adjacency.keys().copied().collect();
+        indices.sort();
+        for from in indices {
+            for pos in 0..self.adjacency[&from].edges.len() {
+                let edge = &mut self.adjacency.get_mut(&from).unwrap().edges[pos];
+                if edge.id != EdgeId::default() {
+                    continue;
+                }
+                let id = EdgeId(next);
+                next += 1;
+                edge.id = id;
+                let (to, weight) = (edge.node.number, edge.weight);
+
+                // Ищем вторую половину неориентированного ребра
+                if !self.is_directed
+                    && to != from
+                    && let Some(adj) = self.adjacency.get_mut(&to)
+                {
+                    let twin = adj
+                        .edges
+                        .iter()
+                        .position(|e| {
+                            e.node.number == from && e.id == EdgeId::default() && e.weight == weight
+                        })
+                        .or_else(|| {
+                            adj.edges
+                                .iter()
+                                .position(|e| e.node.number == from && e.id == EdgeId::default())
+                        });
+                    if let Some(twin) = twin {
+                        adj.edges[twin].id = id;
+                    }
+                }
+            }
+        }
+        self.next_edge_id = next;
+    }
+
+    /// Выдает новый id ребра
+    fn new_edge_id(&mut self) -> EdgeId {
+        let id = EdgeId(self.next_edge_id);
+        self.next_edge_id += 1;
+        id
+    }
+
+    /// Включает отслеживание компонент связности: после этого
+    /// [`Graph::add_node`] и [`Graph::add_edge`] обновляют их за почти
+    /// константное время. Направление ребер не учитывается (слабая
+    /// связность). Удаление вершины или ребра пересчитывает компоненты
+    /// заново за `O(V + E)`.
+    pub fn track_connectivity(&mut self) {
+        let mut sets: UnionFind = self.nodes.keys().copied().collect();
+        for (&from, adjacency) in &self.adjacency {
+            // Ребро орграфа может вести в несуществующую вершину
+            for edge in adjacency {
+                if self.nodes.contains_key(&edge.node.number) {
+                    sets.union(from, edge.node.number);
+                }
+            }
+        }
+        self.connectivity = Some(sets);
+    }
+
+    /// Выключает отслеживание компонент связности
+    pub fn untrack_connectivity(&mut self) {
+        self.connectivity = None;
+    }
+
+    /// Отслеживаемые компоненты связности, если отслеживание включено
+    pub fn connectivity(&self) -> Option<&UnionFind> {
+        self.connectivity.as_ref()
+    }
+
+    /// То же для запросов, сжимающих пути ([`UnionFind::connected`] и
+    /// др.). Менять систему множеств напрямую не стоит: она перестанет
+    /// соответствовать графу
+    pub fn connectivity_mut(&mut self) -> Option<&mut UnionFind> {
+        self.connectivity.as_mut()
+    }
+
+    /// Число компонент слабой связности, если отслеживание включено
+    pub fn component_count(&self) -> Option<usize> {
+        self.connectivity.as_ref().map(UnionFind::component_count)
+    }
+
+    /// Пересчитывает компоненты после удаления, если они отслеживаются
+    fn retrack_connectivity(&mut self) {
+        if self.connectivity.is_some() {
+            self.track_connectivity();
         }
     }
 }
 
-impl<T> Graph<T>
+impl<T, W> Graph<T, W>
 where
     T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
 {
     /// Creates a new [`Graph<T>`]
-    pub fn new(node: Option<Node<T>>, edge_adjacency: Adjacency<T>, is_directed: bool) -> Self {
-        let mut new_edges: HashMap<Index, Adjacency<T>> = HashMap::new();
+    pub fn new(node: Option<Node<T>>, edge_adjacency: Adjacency<T, W>, is_directed: bool) -> Self {
+        let mut new_edges: HashMap<Index, Adjacency<T, W>> = HashMap::new();
         let mut new_nodes: HashMap<Index, Node<T>> = HashMap::new();
 
         if let Some(n) = node {
@@ -373,11 +708,16 @@ where
             new_nodes.insert(n.number, n);
         }
 
-        Self {
+        let mut graph = Self {
             nodes: new_nodes,
             adjacency: new_edges,
             is_directed,
-        }
+            is_multigraph: false,
+            next_edge_id: 0,
+            connectivity: None,
+        };
+        graph.assign_edge_ids();
+        graph
     }
 
     /// Returns the some node index of this [`Graph<T>`].
@@ -402,14 +742,27 @@ where
         self.nodes.len()
     }
 
+    /// Returns true if this [`Graph<T>`] has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
     /// Returns the is directed field of this [`Graph<T>`].
     pub fn get_is_directed(&self) -> bool {
         self.is_directed
     }
 
+    /// Returns the is multigraph field of this [`Graph<T>`].
+    pub fn get_is_multigraph(&self) -> bool {
+        self.is_multigraph
+    }
+
     pub fn add_node(&mut self, node: Node<T>) -> Result<()> {
         if let Entry::Vacant(adj) = self.adjacency.entry(node.number) {
             adj.insert(Adjacency::default());
+            if let Some(sets) = &mut self.connectivity {
+                sets.insert(node.number);
+            }
             self.nodes.insert(node.number, node);
             Ok(())
         } else {
@@ -420,52 +773,115 @@ where
         }
     }
 
-    pub fn add_edge(&mut self, node: &Node<T>, new_edge: &Edge<T>) -> Result<()> {
-        if let Some(edges) = self.adjacency.get_mut(&node.number) {
-            edges.push(new_edge.clone())?;
-        } else {
+    /// Добавляет ребро из node в new_edge.node и возвращает его id
+    /// (id из new_edge не используется).
+    ///
+    /// В простом графе второе ребро между теми же вершинами не
+    /// добавляется: вернется [`GraphKindError::EdgeAlreadyExist`] с id уже
+    /// существующего в описании. В мультиграфе каждый вызов добавляет
+    /// новое ребро.
+    pub fn add_edge(&mut self, node: &Node<T>, new_edge: &Edge<T, W>) -> Result<EdgeId> {
+        let to = new_edge.node.number;
+        let Some(edges) = self.adjacency.get(&node.number) else {
+            return Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")));
+        };
+
+        if !self.is_multigraph
+            && let Some(existing) = edges.iter().find(|e| e.node.number == to)
+        {
+            return Err(Box::new(GraphError::new(
+                GraphKindError::EdgeAlreadyExist,
+                &format!("ребро {} из {} в {to}", existing.id, node.number),
+            )));
+        }
+
+        if !self.is_directed && !self.adjacency.contains_key(&to) {
             return Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")));
         }
 
-        if !self.is_directed {
-            let duplicate_edge = Edge::new(&node.number, new_edge.weight, &node.value);
-            if let Some(edges) = self.adjacency.get_mut(&new_edge.node.number) {
+        let id = self.new_edge_id();
+        if let Some(edges) = self.adjacency.get_mut(&node.number) {
+            let mut edge = new_edge.clone();
+            edge.id = id;
+            edges.push(edge)?;
+        }
+
+        // Петля неориентированного графа хранится один раз
+        if !self.is_directed && to != node.number {
+            let mut duplicate_edge = Edge::new(&node.number, new_edge.weight, &node.value);
+            duplicate_edge.id = id;
+            duplicate_edge.capacity = new_edge.capacity;
+            duplicate_edge.cost = new_edge.cost;
+            if let Some(edges) = self.adjacency.get_mut(&to) {
                 edges.push(duplicate_edge)?;
-            } else {
-                return Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")));
             }
         }
-        Ok(())
+        if let Some(sets) = &mut self.connectivity
+            && self.nodes.contains_key(&to)
+        {
+            sets.union(node.number, to);
+        }
+        Ok(id)
     }
 
-    pub fn delete_edge(&mut self, node: &Node<T>, edge_index: &Index) -> Result<PairEdges<T>> {
+    /// Удаляет одно ребро из node в edge_index
+    pub fn delete_edge(&mut self, node: &Node<T>, edge_index: &Index) -> Result<PairEdges<T, W>> {
         let first = if let Some(adjacency) = self.adjacency.get_mut(&node.number) {
             adjacency.delete(*edge_index)?
         } else {
             return Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")));
         };
 
-        if self.is_directed {
-            Ok((first, None))
-        } else {
-            let second = if first.is_some()
-                && let Some(adjacency) = self.adjacency.get_mut(&first.clone().unwrap().node.number)
-            {
-                adjacency.delete(node.number)?
-            } else {
-                return Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")));
-            };
-            Ok((first, second))
-        }
+        // Вторая половина ребра неориентированного графа --- ребро с тем
+        // же id у другой вершины
+        let second = match &first {
+            Some(edge) if !self.is_directed && edge.node.number != node.number => self
+                .adjacency
+                .get_mut(&edge.node.number)
+                .and_then(|adjacency| adjacency.delete_by_id(edge.id)),
+            _ => None,
+        };
+        // Компоненты пересчитываем, только когда удалены обе половины
+        self.retrack_connectivity();
+        Ok((first, second))
+    }
+
+    /// Удаляет ребро по id (в неориентированном графе --- обе половины)
+    pub fn delete_edge_by_id(&mut self, id: EdgeId) -> Result<PairEdges<T, W>> {
+        let mut removed = self
+            .adjacency
+            .values_mut()
+            .filter_map(|adjacency| adjacency.delete_by_id(id));
+
+        let pair = match removed.next() {
+            Some(first) => (Some(first), removed.next()),
+            None => {
+                return Err(Box::new(GraphError::new(
+                    GraphKindError::EdgeNotFound,
+                    &format!("id {id}"),
+                )));
+            }
+        };
+        self.retrack_connectivity();
+        Ok(pair)
+    }
+
+    /// Returns the edge with given id and the node it goes from.
+    pub fn get_edge(&self, id: EdgeId) -> Option<(Index, &Edge<T, W>)> {
+        self.adjacency
+            .iter()
+            .find_map(|(&from, adjacency)| adjacency.get_by_id(id).map(|edge| (from, edge)))
     }
 
-    pub fn delete_node(&mut self, node: &Node<T>) -> Result<Adjacency<T>> {
+    pub fn delete_node(&mut self, node: &Node<T>) -> Result<Adjacency<T, W>> {
         // Remove edges from other adjacencies
         for (_, adjacency) in self.adjacency.iter_mut() {
             adjacency.edges.retain(|n| n.node.number != node.number);
         }
         self.nodes.remove(&node.number);
-        if let Some(adjacency) = self.adjacency.remove(&node.number) {
+        let removed = self.adjacency.remove(&node.number);
+        self.retrack_connectivity();
+        if let Some(adjacency) = removed {
             return Ok(adjacency);
         }
         Err(Box::new(GraphError::new(GraphKindError::NodeNotFound, "")))
@@ -480,11 +896,11 @@ where
         Ok(())
     }
 
-    pub fn new_from_file(path: &str) -> Result<Graph<T>> {
+    pub fn new_from_file(path: &str) -> Result<Graph<T, W>> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
 
-        let readed: Graph<T> = serde_json::from_reader(reader)?;
+        let readed: Graph<T, W> = serde_json::from_reader(reader)?;
         Ok(readed)
     }
 
@@ -492,6 +908,11 @@ where
         self.is_directed = true;
     }
 
+    /// Разрешает параллельные ребра
+    pub fn to_multigraph_mut(&mut self) {
+        self.is_multigraph = true;
+    }
+
     pub fn has_edge(&self, from_ind: &Index, to_ind: &Index) -> Result<bool> {
         if let Some(adj) = self.get_adjacency(from_ind) {
             for e in adj {
@@ -503,13 +924,14 @@ where
         Ok(false)
     }
 
-    pub fn to_not_directed(&self) -> Result<Graph<T>> {
+    pub fn to_not_directed(&self) -> Result<Graph<T, W>> {
         let mut not_dir_graph = Graph {
             is_directed: false,
+            is_multigraph: self.is_multigraph,
             ..Default::default()
         };
 
-        if self.len() == 0 {
+        if self.is_empty() {
             return Ok(not_dir_graph);
         }
 
@@ -519,8 +941,17 @@ where
             }
         }
 
+        // Половины неориентированного ребра добавляем один раз
+        let mut added: HashSet<EdgeId> = HashSet::new();
         for (ind, adj) in self.iter() {
             for edge in adj {
+                // Встречные дуги простого орграфа становятся одним ребром
+                if !added.insert(edge.id)
+                    || (!not_dir_graph.is_multigraph
+                        && not_dir_graph.has_edge(ind, &edge.node.number)?)
+                {
+                    continue;
+                }
                 not_dir_graph.add_edge(
                     &Node {
                         number: *ind,
@@ -538,7 +969,7 @@ where
         self.nodes.get(index_node)
     }
 
-    pub fn get_adjacency(&self, index_node: &Index) -> Option<&Adjacency<T>> {
+    pub fn get_adjacency(&self, index_node: &Index) -> Option<&Adjacency<T, W>> {
         self.adjacency.get(index_node)
     }
 
@@ -550,8 +981,9 @@ where
     }
 
     pub fn create_subgraph(&self, nodes: Vec<Index>, is_directed: bool) -> Result<Self> {
-        let mut subgraph: Graph<T> = Graph {
+        let mut subgraph: Graph<T, W> = Graph {
             is_directed,
+            is_multigraph: self.is_multigraph,
             ..Default::default()
         };
 
@@ -561,10 +993,17 @@ where
             }
         }
 
+        let mut added: HashSet<EdgeId> = HashSet::new();
         for (ind, adj) in self.iter() {
             if subgraph.get_node(ind).is_some() {
                 for edge in adj {
-                    if subgraph.get_node(&edge.node.number).is_some() {
+                    // Половины неориентированного ребра добавляем один раз, а
+                    // встречные дуги в простом неориентированном подграфе
+                    // становятся одним ребром
+                    if subgraph.get_node(&edge.node.number).is_some()
+                        && (is_directed || added.insert(edge.id))
+                        && (subgraph.is_multigraph || !subgraph.has_edge(ind, &edge.node.number)?)
+                    {
                         subgraph.add_edge(
                             &Node {
                                 number: *ind,
@@ -581,33 +1020,119 @@ where
     }
 
     /// Returns the get all edges of this [`Graph<T>`].
-    pub fn get_all_edges(&self) -> Vec<(Index, Edge<T>)> {
-        let mut edges_list: Vec<(Index, Edge<T>)> = Vec::new();
+    pub fn get_all_edges(&self) -> Vec<(Index, Edge<T, W>)> {
+        let mut edges_list: Vec<(Index, Edge<T, W>)> = Vec::new();
         for (&ind, adj) in self {
             for edge in adj {
                 edges_list.push((ind, edge.clone()));
             }
         }
 
-        edges_list.sort_by(|(_, edge1), (_, edge2)| edge1.weight.cmp(&edge2.weight));
+        edges_list.sort_by_key(|(_, edge)| edge.weight);
         edges_list
     }
 
     /// Returns the iter of this [`Graph<T>`].
-    pub fn iter(&self) -> GraphIter<'_, T> {
+    pub fn iter(&self) -> GraphIter<'_, T, W> {
         GraphIter {
             inner: self.adjacency.iter(),
         }
     }
 
     /// Returns the mut iter of this [`Graph<T>`].
-    pub fn iter_mut(&mut self) -> GraphIterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> GraphIterMut<'_, T, W> {
         GraphIterMut {
             inner: self.adjacency.iter_mut(),
         }
     }
 }
 
+impl<T, W> GraphView for Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    type Weight = W;
+
+    fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.is_directed
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        self.adjacency.contains_key(&index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.adjacency.keys().copied()
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, W)> + '_ {
+        self.adjacency
+            .get(&index)
+            .into_iter()
+            .flat_map(|adj| adj.edges.iter())
+            .map(|edge| (edge.node.number, edge.weight))
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Граф, в котором вес ребра --- его пропускная способность
+    /// ([`Edge::flow_capacity`]); для потоковых алгоритмов
+    pub fn capacities(&self) -> CapacityView<'_, T, W> {
+        CapacityView { graph: self }
+    }
+}
+
+/// Представление [`Graph<T>`] с пропускными способностями вместо весов
+pub struct CapacityView<'a, T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    graph: &'a Graph<T, W>,
+}
+
+impl<T, W> GraphView for CapacityView<'_, T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    type Weight = W;
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        self.graph.contains_node(index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.graph.node_indices()
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, W)> + '_ {
+        self.graph
+            .adjacency
+            .get(&index)
+            .into_iter()
+            .flat_map(|adj| adj.edges.iter())
+            .map(|edge| (edge.node.number, edge.flow_capacity()))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum GraphType {
     Default,
@@ -622,46 +1147,51 @@ pub enum ColorNode {
     Black,
 }
 
-pub struct GraphIter<'a, T>
+pub struct GraphIter<'a, T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    inner: hash_map::Iter<'a, Index, Adjacency<T>>,
+    inner: hash_map::Iter<'a, Index, Adjacency<T, W>>,
 }
 
-impl<'a, T> Iterator for GraphIter<'a, T>
+impl<'a, T, W> Iterator for GraphIter<'a, T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    type Item = (&'a Index, &'a Adjacency<T>);
+    type Item = (&'a Index, &'a Adjacency<T, W>);
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
     }
 }
 
-pub struct GraphIterMut<'a, T>
+pub struct GraphIterMut<'a, T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    inner: hash_map::IterMut<'a, Index, Adjacency<T>>,
+    inner: hash_map::IterMut<'a, Index, Adjacency<T, W>>,
 }
 
-impl<'a, T> Iterator for GraphIterMut<'a, T>
+impl<'a, T, W> Iterator for GraphIterMut<'a, T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    type Item = (&'a Index, &'a mut Adjacency<T>);
+    type Item = (&'a Index, &'a mut Adjacency<T, W>);
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next()
     }
 }
 
-impl<'a, T> IntoIterator for &'a Graph<T>
+impl<'a, T, W> IntoIterator for &'a Graph<T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    type Item = (&'a Index, &'a Adjacency<T>);
-    type IntoIter = GraphIter<'a, T>;
+    type Item = (&'a Index, &'a Adjacency<T, W>);
+    type IntoIter = GraphIter<'a, T, W>;
     fn into_iter(self) -> Self::IntoIter {
         GraphIter {
             inner: self.adjacency.iter(),
@@ -669,12 +1199,13 @@ where
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut Graph<T>
+impl<'a, T, W> IntoIterator for &'a mut Graph<T, W>
 where
     T: Clone,
+    W: Weight,
 {
-    type Item = (&'a Index, &'a mut Adjacency<T>);
-    type IntoIter = GraphIterMut<'a, T>;
+    type Item = (&'a Index, &'a mut Adjacency<T, W>);
+    type IntoIter = GraphIterMut<'a, T, W>;
     fn into_iter(self) -> Self::IntoIter {
         GraphIterMut {
             inner: self.adjacency.iter_mut(),
@@ -0,0 +1,202 @@
+use std::{collections::HashMap, error::Error, fmt::Debug};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::graph::{
+    core::{Adjacency, Edge, Graph, Index, Node},
+    view::GraphView,
+    weight::Weight,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Граф в сжатом построчном формате (CSR).
+///
+/// Вершины получают плотные номера `0..n` в порядке возрастания [`Index`],
+/// значение каждой вершины хранится один раз. Ребра вершины `i` лежат в
+/// `targets[offsets[i]..offsets[i + 1]]` и `weights[...]` того же
+/// диапазона, отсортированные по номеру соседа, поэтому порядок обхода
+/// детерминирован. В неориентированном графе каждое ребро хранится в обе
+/// стороны, как и в [`Graph<T>`]; параллельные ребра мультиграфа хранятся
+/// каждое отдельно; id, пропускные способности и стоимости ребер не
+/// сохраняются.
+#[derive(Debug, Clone)]
+pub struct CsrGraph<T, W = u32> {
+    indices: Vec<Index>,
+    positions: HashMap<Index, u32>,
+    values: Vec<T>,
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    weights: Vec<W>,
+    is_directed: bool,
+    is_multigraph: bool,
+}
+
+impl<T, W> CsrGraph<T, W>
+where
+    W: Weight,
+{
+    /// Возвращает количество хранимых дуг (в неориентированном графе
+    /// каждое ребро считается дважды, петля --- один раз)
+    pub fn arc_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Плотный номер вершины index
+    pub fn position(&self, index: Index) -> Option<usize> {
+        self.positions.get(&index).map(|&pos| pos as usize)
+    }
+
+    /// Исходный номер вершины по плотному номеру pos
+    pub fn index_at(&self, pos: usize) -> Index {
+        self.indices[pos]
+    }
+
+    /// Значение вершины index
+    pub fn get_value(&self, index: Index) -> Option<&T> {
+        self.position(index).map(|pos| &self.values[pos])
+    }
+
+    /// Соседи вершины с плотным номером pos в плотных номерах
+    pub fn dense_neighbors(&self, pos: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let range = self.offsets[pos]..self.offsets[pos + 1];
+        self.targets[range.clone()]
+            .iter()
+            .zip(&self.weights[range])
+            .map(|(&to, &weight)| (to as usize, weight))
+    }
+}
+
+impl<T, W> CsrGraph<T, W>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    /// Строит [`CsrGraph<T>`] по [`Graph<T>`].
+    ///
+    /// Вершины, встречающиеся только как концы ребер, получают значение из
+    /// ребра.
+    pub fn from_graph(graph: &Graph<T, W>) -> Self {
+        let mut values_by_index: HashMap<Index, T> = HashMap::new();
+        for (index, adj) in graph.iter() {
+            let value = graph
+                .get_node(index)
+                .map(|node| node.value.clone())
+                .unwrap_or_default();
+            values_by_index.insert(*index, value);
+            for edge in adj {
+                values_by_index
+                    .entry(edge.node.number)
+                    .or_insert_with(|| edge.node.value.clone());
+            }
+        }
+
+        let mut indices: Vec<Index> = values_by_index.keys().copied().collect();
+        indices.sort();
+        let positions: HashMap<Index, u32> = indices
+            .iter()
+            .enumerate()
+            .map(|(pos, &index)| (index, pos as u32))
+            .collect();
+        let values: Vec<T> = indices
+            .iter()
+            .map(|index| values_by_index.remove(index).unwrap_or_default())
+            .collect();
+
+        let mut offsets = Vec::with_capacity(indices.len() + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+        offsets.push(0);
+        for index in &indices {
+            let mut row: Vec<(u32, W)> = graph
+                .get_adjacency(index)
+                .into_iter()
+                .flatten()
+                .map(|edge| (positions[&edge.node.number], edge.weight))
+                .collect();
+            row.sort_unstable();
+            for (to, weight) in row {
+                targets.push(to);
+                weights.push(weight);
+            }
+            offsets.push(targets.len());
+        }
+
+        Self {
+            indices,
+            positions,
+            values,
+            offsets,
+            targets,
+            weights,
+            is_directed: graph.get_is_directed(),
+            is_multigraph: graph.get_is_multigraph(),
+        }
+    }
+
+    /// Преобразует обратно в [`Graph<T>`]
+    pub fn to_graph(&self) -> Result<Graph<T, W>> {
+        let mut graph: Graph<T, W> = Graph::new(None, Adjacency::default(), self.is_directed);
+        if self.is_multigraph {
+            graph.to_multigraph_mut();
+        }
+
+        for (index, value) in self.indices.iter().zip(&self.values) {
+            graph.add_node(Node::new(*index, value.clone()))?;
+        }
+
+        for from in 0..self.indices.len() {
+            let node = Node::new(self.indices[from], self.values[from].clone());
+            for (to, weight) in self.dense_neighbors(from) {
+                // Неориентированное ребро добавляется в обе стороны сразу
+                if !self.is_directed && to < from {
+                    continue;
+                }
+                let edge = Edge::new(&self.indices[to], weight, &self.values[to]);
+                graph.add_edge(&node, &edge)?;
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+impl<T, W> From<&Graph<T, W>> for CsrGraph<T, W>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+    W: Weight,
+{
+    fn from(graph: &Graph<T, W>) -> Self {
+        Self::from_graph(graph)
+    }
+}
+
+impl<T, W> GraphView for CsrGraph<T, W>
+where
+    W: Weight,
+{
+    type Weight = W;
+
+    fn node_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.is_directed
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        self.positions.contains_key(&index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.indices.iter().copied()
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, W)> + '_ {
+        self.position(index)
+            .into_iter()
+            .flat_map(|pos| self.dense_neighbors(pos))
+            .map(|(to, weight)| (self.indices[to], weight))
+    }
+}
@@ -1,3 +1,5 @@
-pub mod cli;
 pub mod core;
-pub mod gui;
+pub mod csr;
+pub mod union_find;
+pub mod view;
+pub mod weight;
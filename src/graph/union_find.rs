@@ -0,0 +1,147 @@
+//! Система непересекающихся множеств (union–find) на вершинах графа.
+
+use std::collections::HashMap;
+
+use crate::graph::core::Index;
+
+/// Система непересекающихся множеств с ключами [`Index`]: сжатие путей и
+/// объединение по рангу, поэтому операции почти константные. Для каждого
+/// множества хранится его размер, для всей системы --- число множеств.
+///
+/// Поиск представителя сжимает путь, поэтому [`UnionFind::find`] и
+/// запросы на его основе требуют `&mut self`.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    positions: HashMap<Index, usize>,
+    indices: Vec<Index>,
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    // Размер множества, верен только для корней
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавляет элемент отдельным множеством; `false`, если он уже есть
+    pub fn insert(&mut self, index: Index) -> bool {
+        if self.positions.contains_key(&index) {
+            return false;
+        }
+        let position = self.indices.len();
+        self.positions.insert(index, position);
+        self.indices.push(index);
+        self.parent.push(position);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        true
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.positions.contains_key(&index)
+    }
+
+    /// Число элементов
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Число множеств
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    fn root(&mut self, mut position: usize) -> usize {
+        let mut root = position;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        while self.parent[position] != root {
+            let next = self.parent[position];
+            self.parent[position] = root;
+            position = next;
+        }
+        root
+    }
+
+    /// Представитель множества элемента или [`None`], если элемента нет
+    pub fn find(&mut self, index: Index) -> Option<Index> {
+        let position = *self.positions.get(&index)?;
+        let root = self.root(position);
+        Some(self.indices[root])
+    }
+
+    /// Объединяет множества элементов, недостающие элементы добавляются.
+    /// Возвращает `false`, если элементы уже были в одном множестве.
+    pub fn union(&mut self, a: Index, b: Index) -> bool {
+        self.insert(a);
+        self.insert(b);
+        let (a, b) = (self.root(self.positions[&a]), self.root(self.positions[&b]));
+        if a == b {
+            return false;
+        }
+        let (low, high) = if self.rank[a] < self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[low] = high;
+        self.size[high] += self.size[low];
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+        self.components -= 1;
+        true
+    }
+
+    /// Лежат ли элементы в одном множестве; отсутствующий элемент ни с
+    /// чем не связан
+    pub fn connected(&mut self, a: Index, b: Index) -> bool {
+        match (self.find(a), self.find(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Размер множества элемента или [`None`], если элемента нет
+    pub fn size_of(&mut self, index: Index) -> Option<usize> {
+        let position = *self.positions.get(&index)?;
+        let root = self.root(position);
+        Some(self.size[root])
+    }
+
+    /// Множества: элементы каждого по возрастанию, множества по
+    /// наименьшему элементу
+    pub fn components(&mut self) -> Vec<Vec<Index>> {
+        let mut groups: HashMap<usize, Vec<Index>> = HashMap::new();
+        for position in 0..self.indices.len() {
+            let root = self.root(position);
+            groups.entry(root).or_default().push(self.indices[position]);
+        }
+        let mut groups: Vec<Vec<Index>> = groups.into_values().collect();
+        for group in &mut groups {
+            group.sort();
+        }
+        groups.sort();
+        groups
+    }
+}
+
+impl FromIterator<Index> for UnionFind {
+    /// Система из одноэлементных множеств
+    fn from_iter<I: IntoIterator<Item = Index>>(iter: I) -> Self {
+        let mut sets = Self::new();
+        for index in iter {
+            sets.insert(index);
+        }
+        sets
+    }
+}
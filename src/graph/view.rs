@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use crate::graph::{
+    core::{GraphError, GraphKindError, Index},
+    weight::Weight,
+};
+
+/// Доступ к графу только на чтение, не зависящий от способа хранения.
+///
+/// Алгоритмам из `tasks` нужны лишь обход вершин и соседей с весами,
+/// поэтому они принимают любой тип, реализующий [`GraphView`]:
+/// [`Graph<T>`](crate::graph::core::Graph),
+/// [`CsrGraph<T>`](crate::graph::csr::CsrGraph) или одно из представлений
+/// ниже, которые ничего не копируют из исходного графа.
+pub trait GraphView {
+    /// Тип веса ребер
+    type Weight: Weight;
+
+    /// Количество вершин
+    fn node_count(&self) -> usize;
+
+    /// Является ли граф ориентированным
+    fn is_directed(&self) -> bool;
+
+    /// Есть ли в графе вершина с номером index
+    fn contains_node(&self, index: Index) -> bool;
+
+    /// Номера всех вершин графа
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_;
+
+    /// Соседи вершины index вместе с весами ребер до них
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, Self::Weight)> + '_;
+
+    /// Вес ребра (from, to); для нескольких ребер --- минимальный
+    fn weight(&self, from: Index, to: Index) -> Option<Self::Weight> {
+        self.neighbors(from)
+            .filter(|&(neighbor, _)| neighbor == to)
+            .map(|(_, weight)| weight)
+            .min()
+    }
+
+    /// Подграф, порожденный вершинами, для которых keep вернет true
+    fn filtered<F>(&self, keep: F) -> FilteredView<'_, Self, F>
+    where
+        Self: Sized,
+        F: Fn(Index) -> bool,
+    {
+        FilteredView::new(self, keep)
+    }
+
+    /// Граф с развернутыми ребрами
+    fn reversed(&self) -> ReversedView<'_, Self>
+    where
+        Self: Sized,
+    {
+        ReversedView::new(self)
+    }
+
+    /// Граф без учета ориентации ребер
+    fn undirected(&self) -> UndirectedView<'_, Self>
+    where
+        Self: Sized,
+    {
+        UndirectedView::new(self)
+    }
+}
+
+/// Входящие ребра: вершина -> (откуда, вес)
+type Incoming<W> = HashMap<Index, Vec<(Index, W)>>;
+
+/// Ребра, входящие в каждую вершину графа
+fn incoming<G: GraphView>(graph: &G) -> Incoming<G::Weight> {
+    let mut incoming: Incoming<G::Weight> = HashMap::new();
+    for from in graph.node_indices() {
+        for (to, weight) in graph.neighbors(from) {
+            incoming.entry(to).or_default().push((from, weight));
+        }
+    }
+    incoming
+}
+
+/// Подграф, порожденный отобранными вершинами (аналог
+/// [`Graph::create_subgraph`](crate::graph::core::Graph::create_subgraph)
+/// без копирования).
+pub struct FilteredView<'a, G, F> {
+    graph: &'a G,
+    keep: F,
+}
+
+impl<'a, G, F> FilteredView<'a, G, F>
+where
+    G: GraphView,
+    F: Fn(Index) -> bool,
+{
+    /// Creates a new [`FilteredView<G, F>`].
+    pub fn new(graph: &'a G, keep: F) -> Self {
+        Self { graph, keep }
+    }
+}
+
+impl<G, F> GraphView for FilteredView<'_, G, F>
+where
+    G: GraphView,
+    F: Fn(Index) -> bool,
+{
+    type Weight = G::Weight;
+
+    fn node_count(&self) -> usize {
+        self.node_indices().count()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        (self.keep)(index) && self.graph.contains_node(index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.graph
+            .node_indices()
+            .filter(|&index| (self.keep)(index))
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, G::Weight)> + '_ {
+        let from_kept = (self.keep)(index);
+        self.graph
+            .neighbors(index)
+            .filter(move |&(to, _)| from_kept && (self.keep)(to))
+    }
+}
+
+/// Граф с развернутыми ребрами. Хранит только списки входящих ребер;
+/// для неориентированного графа совпадает с исходным.
+pub struct ReversedView<'a, G>
+where
+    G: GraphView,
+{
+    graph: &'a G,
+    incoming: Incoming<G::Weight>,
+}
+
+impl<'a, G> ReversedView<'a, G>
+where
+    G: GraphView,
+{
+    /// Creates a new [`ReversedView<G>`].
+    pub fn new(graph: &'a G) -> Self {
+        Self {
+            graph,
+            incoming: incoming(graph),
+        }
+    }
+}
+
+impl<G> GraphView for ReversedView<'_, G>
+where
+    G: GraphView,
+{
+    type Weight = G::Weight;
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        self.graph.contains_node(index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.graph.node_indices()
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, G::Weight)> + '_ {
+        self.incoming.get(&index).into_iter().flatten().copied()
+    }
+}
+
+/// Граф без учета ориентации (аналог
+/// [`Graph::to_not_directed`](crate::graph::core::Graph::to_not_directed)
+/// без копирования). Ребро, уже имеющее встречное, второй раз не
+/// добавляется.
+pub struct UndirectedView<'a, G>
+where
+    G: GraphView,
+{
+    graph: &'a G,
+    incoming: Incoming<G::Weight>,
+}
+
+impl<'a, G> UndirectedView<'a, G>
+where
+    G: GraphView,
+{
+    /// Creates a new [`UndirectedView<G>`].
+    pub fn new(graph: &'a G) -> Self {
+        let mut incoming = if graph.is_directed() {
+            incoming(graph)
+        } else {
+            HashMap::new()
+        };
+        // Оставляем только ребра, у которых нет встречного
+        for (to, edges) in incoming.iter_mut() {
+            edges.retain(|&(from, _)| graph.weight(*to, from).is_none());
+        }
+        Self { graph, incoming }
+    }
+}
+
+impl<G> GraphView for UndirectedView<'_, G>
+where
+    G: GraphView,
+{
+    type Weight = G::Weight;
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    fn contains_node(&self, index: Index) -> bool {
+        self.graph.contains_node(index)
+    }
+
+    fn node_indices(&self) -> impl Iterator<Item = Index> + '_ {
+        self.graph.node_indices()
+    }
+
+    fn neighbors(&self, index: Index) -> impl Iterator<Item = (Index, G::Weight)> + '_ {
+        self.graph
+            .neighbors(index)
+            .chain(self.incoming.get(&index).into_iter().flatten().copied())
+    }
+}
+
+/// Проверяет, что граф неориентированный; `description` --- какому
+/// алгоритму это нужно
+pub(crate) fn ensure_undirected<G: GraphView>(
+    graph: &G,
+    description: &str,
+) -> Result<(), GraphError> {
+    if graph.is_directed() {
+        return Err(GraphError::new(
+            GraphKindError::GraphMustBeUndirected,
+            description,
+        ));
+    }
+    Ok(())
+}
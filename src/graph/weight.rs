@@ -0,0 +1,172 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::graph::{
+    core::{GraphError, GraphKindError},
+    view::GraphView,
+};
+
+/// Числовой тип веса ребра.
+///
+/// Веса должны быть полностью упорядочены, поэтому для `f64` используется
+/// обертка [`TotalF64`]. Сложение в алгоритмах идет только через
+/// [`Weight::checked_add`]: переполнение --- ошибка, а не «бесконечность».
+pub trait Weight: Copy + Ord + Debug + Display + Serialize + DeserializeOwned {
+    /// Нулевой вес
+    const ZERO: Self;
+
+    /// Сложение; [`None`] при переполнении
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Вычитание; [`None`] при переполнении (для беззнаковых --- при
+    /// результате меньше нуля)
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Умножение; [`None`] при переполнении
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Половина веса; для целых --- с округлением к нулю
+    fn half(self) -> Self;
+
+    /// Сложение с насыщением на границе диапазона
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// Вес из `f64`, округленный вниз (с насыщением на границе диапазона);
+    /// нужен эвристикам, считающим расстояния в `f64`
+    fn from_f64(value: f64) -> Self;
+
+    /// Сложение, переполнение которого --- ошибка
+    /// [`GraphKindError::WeightOverflow`]
+    fn try_add(self, other: Self) -> Result<Self, GraphError> {
+        self.checked_add(other).ok_or_else(|| {
+            GraphError::new(GraphKindError::WeightOverflow, &format!("{self} + {other}"))
+        })
+    }
+}
+
+macro_rules! impl_weight_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn half(self) -> Self {
+                    self / 2
+                }
+
+                fn saturating_add(self, other: Self) -> Self {
+                    <$t>::saturating_add(self, other)
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    // `as` для чисел с плавающей точкой насыщается, NaN -> 0
+                    value.floor() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_int!(u32, u64, i32, i64);
+
+/// `f64` с полным порядком ([`f64::total_cmp`]), чтобы его можно было
+/// использовать как [`Weight`]. Переполнением считается выход за конечные
+/// значения.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TotalF64(pub f64);
+
+impl PartialEq for TotalF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalF64 {}
+
+impl PartialOrd for TotalF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Display for TotalF64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<f64> for TotalF64 {
+    fn from(value: f64) -> Self {
+        TotalF64(value)
+    }
+}
+
+impl Weight for TotalF64 {
+    const ZERO: Self = TotalF64(0.0);
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let sum = self.0 + other.0;
+        sum.is_finite().then_some(TotalF64(sum))
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        let difference = self.0 - other.0;
+        difference.is_finite().then_some(TotalF64(difference))
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        let product = self.0 * other.0;
+        product.is_finite().then_some(TotalF64(product))
+    }
+
+    fn half(self) -> Self {
+        TotalF64(self.0 / 2.0)
+    }
+
+    fn saturating_add(self, other: Self) -> Self {
+        TotalF64((self.0 + other.0).clamp(f64::MIN, f64::MAX))
+    }
+
+    fn from_f64(value: f64) -> Self {
+        TotalF64(value)
+    }
+}
+
+/// Проверяет, что веса всех ребер неотрицательны (нужно Дейкстре и
+/// потоковым алгоритмам)
+pub(crate) fn ensure_non_negative<G: GraphView>(graph: &G) -> Result<(), GraphError> {
+    for from in graph.node_indices() {
+        for (to, weight) in graph.neighbors(from) {
+            if weight < G::Weight::ZERO {
+                return Err(GraphError::new(
+                    GraphKindError::NegativeWeight,
+                    &format!("ребро ({from}, {to}) с весом {weight}"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
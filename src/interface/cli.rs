@@ -1,15 +1,15 @@
 use std::{
-    cmp::Ordering,
     error::Error,
     fmt::{Debug, Display},
 };
 
 use serde::{Serialize, de::DeserializeOwned};
-
-use crate::{
-    graph::core::{Adjacency, Edge, Graph, GraphError, GraphKindError, GraphType, Node},
-    tasks::{
-        task_10::task_10_3, task_11::task_11 , task_2::task_2_4, task_3::task_3_5, task_4::task_4_6, task_5::task_5_18, task_6::task_6_4, task_7::task_7_kraskal, task_8::task_8_1, task_9::task_9_2
+use theory_graph_homework::{
+    Adjacency, Edge, Graph, GraphError, GraphKindError, GraphType, Node,
+    algorithms::{
+        classify, degrees, has_path_within, in_degree, intersection, is_connected, max_flow,
+        min_cost_max_flow, minimum_spanning_tree, node_with_each_distance_within,
+        node_with_total_distance_within,
     },
 };
 
@@ -49,7 +49,7 @@ pub fn cli_interface() -> Result<()> {
 
             8 => choice_8(&graph)?,
 
-            9 => choice_9(&mut graph)?,
+            9 => choice_9(&graph)?,
 
             10 => choice_10(&graph)?,
 
@@ -65,6 +65,8 @@ pub fn cli_interface() -> Result<()> {
 
             16 => choice_16()?,
 
+            17 => choice_17()?,
+
             _ => {
                 break;
             }
@@ -119,7 +121,11 @@ fn print_choices() {
         "16. Решить задачу на нахождение максимального потока любым \
             алгоритмом. (задание 11)"
     );
-    println!("17 и больше. Выйти");
+    println!(
+        "17. Найти максимальный поток минимальной стоимости (стоимость \
+            единицы потока задается в поле cost ребра)"
+    );
+    println!("18 и больше. Выйти");
     println!("===========================================================");
 }
 
@@ -179,16 +185,17 @@ fn choice_2(graph: &mut Graph<u32>) -> Result<()> {
 
     let new_edge = Edge::new(&edge_node_number.into(), edge_node_weight, &edge_node_value);
 
-    if graph.get_is_directed() {
-        graph.add_edge(&Node::new(node_number.into(), 0), &new_edge)?;
+    let id = if graph.get_is_directed() {
+        graph.add_edge(&Node::new(node_number.into(), 0), &new_edge)?
     } else {
         println!("Введите значение вершины из которой будет выходить ребро:");
         input.clear();
         std::io::stdin().read_line(&mut input)?;
         let node_value: u32 = input.trim().parse()?;
 
-        graph.add_edge(&Node::new(node_number.into(), node_value), &new_edge)?;
-    }
+        graph.add_edge(&Node::new(node_number.into(), node_value), &new_edge)?
+    };
+    println!("id ребра: {id}");
     println!("{graph}");
     Ok(())
 }
@@ -265,7 +272,7 @@ fn choice_7<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default>
 
     println!(
         "Полустепень захода вершины {{{node_number}}} = {}",
-        task_2_4(graph, &node_number)?
+        in_degree(graph, node_number.into())?
     );
     Ok(())
 }
@@ -273,7 +280,7 @@ fn choice_7<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default>
 fn choice_8<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default>(
     graph: &Graph<T>,
 ) -> Result<()> {
-    for (index, count) in task_3_5(graph)? {
+    for (index, count) in degrees(graph)? {
         println!("Степень вершины {{{index}}} = {count}")
     }
 
@@ -281,15 +288,15 @@ fn choice_8<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default>
 }
 
 fn choice_9<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &mut Graph<T>,
+    graph: &Graph<T>,
 ) -> Result<()> {
     println!("Введите путь до файла (для второго графа):");
 
     let mut input = String::new();
     std::io::stdin().read_line(&mut input)?;
 
-    let mut other_graph: Graph<T> = Graph::new_from_file(input.trim_end())?;
-    println!("{}", task_4_6(graph, &mut other_graph)?);
+    let other_graph: Graph<T> = Graph::new_from_file(input.trim_end())?;
+    println!("{}", intersection(graph, &other_graph)?);
     Ok(())
 }
 
@@ -298,7 +305,7 @@ fn choice_10<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default
 ) -> Result<()> {
     println!(
         "Данный граф {}",
-        match task_5_18(graph)? {
+        match classify(graph)? {
             GraphType::Tree => "--- дерево",
             GraphType::Forest => "--- лес",
             _ => "не является ни дервом ни лесом",
@@ -312,7 +319,7 @@ fn choice_11<T: Display + Clone + DeserializeOwned + Debug + Serialize + Default
 ) -> Result<()> {
     println!(
         "Данный граф: {}",
-        match task_6_4(graph)? {
+        match is_connected(graph)? {
             true => "связен",
             false => "не связен",
         }
@@ -327,7 +334,7 @@ fn choice_12() -> Result<()> {
     std::io::stdin().read_line(&mut input)?;
 
     let other_graph: Graph<String> = Graph::new_from_file(input.trim_end())?;
-    println!("{}", task_7_kraskal(&other_graph)?);
+    println!("{}", minimum_spanning_tree(&other_graph)?);
     Ok(())
 }
 
@@ -354,7 +361,7 @@ fn choice_13() -> Result<()> {
 
     println!(
         "{}",
-        match task_8_1(&other_graph, start, destination, weight_limit)? {
+        match has_path_within(&other_graph, start.into(), destination.into(), weight_limit)? {
             true => "Существует",
             false => "Не существует",
         }
@@ -373,14 +380,11 @@ fn choice_14() -> Result<()> {
     std::io::stdin().read_line(&mut input)?;
     let weight_limit: u32 = input.trim().parse()?;
 
-    let zero: i32 = 0;
-    let result = task_9_2(&other_graph, &weight_limit)?;
-
     println!(
         "{}",
-        match result.cmp(&zero) {
-            Ordering::Less | Ordering::Equal => "Не найдена".to_string(),
-            Ordering::Greater => format!("Найдена вершина {result}"),
+        match node_with_total_distance_within(&other_graph, weight_limit)? {
+            Some(result) => format!("Найдена вершина {result}"),
+            None => "Не найдена".to_string(),
         }
     );
     Ok(())
@@ -397,14 +401,11 @@ fn choice_15() -> Result<()> {
     std::io::stdin().read_line(&mut input)?;
     let weight_limit: u32 = input.trim().parse()?;
 
-    let zero: i32 = 0;
-    let result = task_10_3(&other_graph, &weight_limit)?;
-
     println!(
         "{}",
-        match result.cmp(&zero) {
-            Ordering::Less | Ordering::Equal => "Не найдена".to_string(),
-            Ordering::Greater => format!("Найдена вершина {result}"),
+        match node_with_each_distance_within(&other_graph, weight_limit)? {
+            Some(result) => format!("Найдена вершина {result}"),
+            None => "Не найдена".to_string(),
         }
     );
     Ok(())
@@ -426,8 +427,38 @@ fn choice_16() -> Result<()> {
     std::io::stdin().read_line(&mut input)?;
     let stock: u32 = input.trim().parse()?;
 
-    println!("Максимальный поток = {}", task_11(&other_graph, source.into(), stock.into())?);
+    println!(
+        "Максимальный поток = {}",
+        max_flow(&other_graph.capacities(), source.into(), stock.into())?
+    );
 
     Ok(())
 }
 
+fn choice_17() -> Result<()> {
+    println!("Введите путь до файла (для временного графа):");
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let other_graph: Graph<String> = Graph::new_from_file(input.trim_end())?;
+
+    println!("Введите номер вершины истока:");
+    input.clear();
+    std::io::stdin().read_line(&mut input)?;
+    let source: u32 = input.trim().parse()?;
+
+    println!("Введите номер вершины стока:");
+    input.clear();
+    std::io::stdin().read_line(&mut input)?;
+    let stock: u32 = input.trim().parse()?;
+
+    let result = min_cost_max_flow(&other_graph, source.into(), stock.into())?;
+    println!("Поток = {}, стоимость = {}", result.value(), result.cost());
+    for edge in result.edges() {
+        println!(
+            "{} -> {} (ребро {}): {}",
+            edge.from, edge.to, edge.id, edge.flow
+        );
+    }
+
+    Ok(())
+}
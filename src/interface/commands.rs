@@ -0,0 +1,1249 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Debug,
+    io,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+use theory_graph_homework::{
+    Adjacency, Edge, EdgeId, Graph, GraphError, GraphKindError, GraphType, GraphView, Index, Node,
+    algorithms::{
+        Bipartition, EulerObstruction, Eulerian, FlowMismatch, GreedyOrder, InvalidColoring,
+        MaxFlowAlgorithm, MaxFlowSolver, NegativeCycle, Objective, SearchResult, ShortestPaths,
+        TopologicalOrder, Tour, astar, bellman_ford, biconnectivity, bipartite_edge_coloring,
+        bipartition, boruvka, check_coloring, check_max_flow, chinese_postman, christofides,
+        classify, condensation, count_paths, critical_path, degrees, dijkstra, dsatur,
+        edge_connectivity, edge_disjoint_paths, euclidean, eulerian, exact_coloring,
+        floyd_warshall, greedy_coloring, hamiltonian_cycle, hamiltonian_path, has_path_within,
+        held_karp, hierholzer, hopcroft_karp, hungarian, in_degree, intersection, is_connected,
+        is_maximum_matching, is_weakly_connected, kosaraju_scc, kruskal, longest_path, manhattan,
+        maximum_matching, maximum_weight_matching, min_cost_flow, min_cost_max_flow,
+        min_cost_transport, minimum_vertex_cover, nearest_neighbor, node_with_each_distance_within,
+        node_with_total_distance_within, prim, shortest_path, sinks, sources, spfa, tarjan_scc,
+        topological_sort_dfs, topological_sort_kahn, two_opt, vertex_connectivity,
+        vertex_disjoint_paths,
+    },
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Граф, значения вершин которого хранятся как произвольный JSON.
+/// Веса знаковые, чтобы можно было задавать отрицательные стоимости.
+type JsonGraph = Graph<Value, i64>;
+
+/// Команда выполнена успешно
+pub const EXIT_SUCCESS: i32 = 0;
+/// Ошибка в графе или в алгоритме
+pub const EXIT_FAILURE: i32 = 1;
+/// Ошибка чтения/записи или разбора файла графа
+/// (код 2 занят clap под неверные аргументы)
+pub const EXIT_IO: i32 = 3;
+
+/// Неинтерактивный режим: каждая команда печатает результат в stdout
+/// одной строкой JSON, а ошибку --- в stderr в виде `{"error": "..."}`.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Печатать JSON с отступами
+    #[arg(long, global = true)]
+    pub pretty: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Создать пустой граф и сохранить его в файл
+    New {
+        #[arg(short, long)]
+        file: String,
+        /// Создать ориентированный граф
+        #[arg(short, long)]
+        directed: bool,
+        /// Разрешить параллельные ребра
+        #[arg(short, long)]
+        multigraph: bool,
+    },
+    /// Загрузить граф из файла и вывести его
+    Load {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Загрузить граф из файла и сохранить его в другой файл
+    Save {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, long)]
+        out: String,
+    },
+    /// Добавить вершину
+    AddNode {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, long)]
+        node: u32,
+        /// Значение вершины (JSON, иначе строка)
+        #[arg(short, long)]
+        value: Option<String>,
+        /// Куда сохранить результат (по умолчанию --- в исходный файл)
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Добавить ребро
+    AddEdge {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long)]
+        from: u32,
+        #[arg(long)]
+        to: u32,
+        #[arg(short, long, default_value_t = 0, allow_negative_numbers = true)]
+        weight: i64,
+        /// Пропускная способность для потоков (по умолчанию --- вес)
+        #[arg(long)]
+        capacity: Option<i64>,
+        /// Стоимость единицы потока
+        #[arg(long, allow_negative_numbers = true)]
+        cost: Option<i64>,
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Удалить вершину
+    DeleteNode {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, long)]
+        node: u32,
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Удалить ребро по концам (первое из параллельных) или по id
+    DeleteEdge {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long, required_unless_present = "id", requires = "to")]
+        from: Option<u32>,
+        #[arg(long, requires = "from")]
+        to: Option<u32>,
+        /// id ребра (вместо --from и --to)
+        #[arg(long, conflicts_with_all = ["from", "to"])]
+        id: Option<u32>,
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Полустепень захода вершины орграфа (задание 2)
+    InDegree {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, long)]
+        node: u32,
+    },
+    /// Степень каждой вершины орграфа (задание 3)
+    Degree {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Пересечение двух графов (задание 4)
+    Intersect {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long)]
+        other: String,
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Является ли граф деревом, лесом или ни тем, ни другим (задание 5)
+    Forest {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Является ли граф связным (задание 6)
+    Connected {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Каркас минимального веса (задание 7): ребра, суммарный вес и число
+    /// деревьев, если граф несвязный
+    Mst {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long, value_enum, default_value_t = SpanningTreeAlgorithm::Kruskal)]
+        algorithm: SpanningTreeAlgorithm,
+        /// Искать каркас максимального веса
+        #[arg(long)]
+        maximum: bool,
+        /// Сохранить каркас в этот файл
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Существует ли путь длиной не более L между s и t (задание 8)
+    PathWithin {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short)]
+        s: u32,
+        #[arg(short)]
+        t: u32,
+        #[arg(short, long)]
+        limit: i64,
+    },
+    /// Вершина, сумма кратчайших путей от которой не превосходит P (задание 9)
+    SumWithin {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, long)]
+        limit: i64,
+    },
+    /// Вершина, каждый кратчайший путь от которой не превосходит N (задание 10)
+    EachWithin {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, long)]
+        limit: i64,
+    },
+    /// Максимальный поток из s в t (задание 11): поток по каждому ребру и
+    /// минимальный разрез
+    Maxflow {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short)]
+        s: u32,
+        #[arg(short)]
+        t: u32,
+        #[arg(long, value_enum, default_value_t = FlowAlgorithm::EdmondsKarp)]
+        algorithm: FlowAlgorithm,
+        /// Проверить, что все алгоритмы находят одинаковый поток
+        #[arg(long)]
+        check: bool,
+    },
+    /// Поток минимальной стоимости: из s в t (весь максимальный или
+    /// `--amount` единиц) либо от вершин `--supply` к вершинам `--demand`
+    MinCostFlow {
+        #[arg(short, long)]
+        file: String,
+        #[arg(
+            short,
+            requires = "t",
+            required_unless_present = "supply",
+            conflicts_with_all = ["supply", "demand"]
+        )]
+        s: Option<u32>,
+        #[arg(short, requires = "s")]
+        t: Option<u32>,
+        /// Сколько единиц отправить из s в t
+        #[arg(long, requires = "s")]
+        amount: Option<i64>,
+        /// Предложение вершин в виде `вершина:количество`
+        #[arg(long, value_delimiter = ',', value_parser = parse_balance, requires = "demand")]
+        supply: Vec<(u32, i64)>,
+        /// Спрос вершин в виде `вершина:количество`
+        #[arg(long, value_delimiter = ',', value_parser = parse_balance, requires = "supply")]
+        demand: Vec<(u32, i64)>,
+    },
+    /// Кратчайшие пути из s с отрицательными весами (Беллман–Форд).
+    /// Отрицательный цикл печатается в ошибке как `negative_cycle`
+    BellmanFord {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short)]
+        s: u32,
+        /// Вывести только путь до t
+        #[arg(short)]
+        t: Option<u32>,
+        /// Использовать SPFA вместо классического алгоритма
+        #[arg(long)]
+        spfa: bool,
+    },
+    /// Кратчайшие пути из s алгоритмом Дейкстры (веса неотрицательные)
+    Dijkstra {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short)]
+        s: u32,
+        /// Вывести только путь до t
+        #[arg(short)]
+        t: Option<u32>,
+    },
+    /// Кратчайшие пути между всеми парами вершин (Флойд–Уоршелл)
+    FloydWarshall {
+        #[arg(short, long)]
+        file: String,
+        /// Вывести только путь из s в t
+        #[arg(short, requires = "t")]
+        s: Option<u32>,
+        #[arg(short, requires = "s")]
+        t: Option<u32>,
+    },
+    /// Кратчайший путь из s в t алгоритмом A* по координатам вершин
+    /// (`{"x": .., "y": ..}` или `[x, y]` в значении вершины)
+    Astar {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short)]
+        s: u32,
+        #[arg(short)]
+        t: u32,
+        #[arg(long, value_enum, default_value_t = Heuristic::Euclidean)]
+        heuristic: Heuristic,
+    },
+    /// Компоненты сильной связности; слабая и сильная связность орграфа
+    Scc {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long, value_enum, default_value_t = SccAlgorithm::Tarjan)]
+        algorithm: SccAlgorithm,
+        /// Сохранить граф конденсации в этот файл
+        #[arg(long)]
+        condensation: Option<String>,
+    },
+    /// Мосты, точки сочленения и компоненты двусвязности
+    /// неориентированного графа
+    Biconnected {
+        #[arg(short, long)]
+        file: String,
+        /// Сохранить каждую компоненту в файл `<prefix><номер>.json`
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+    /// Доли двудольного графа или нечетный цикл. Для неориентированного
+    /// графа --- наибольшее паросочетание (Хопкрофт–Карп) и минимальное
+    /// вершинное покрытие
+    Bipartite {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Наибольшее паросочетание неориентированного графа (алгоритм
+    /// Эдмондса) с проверкой отсутствия увеличивающего пути
+    Matching {
+        #[arg(short, long)]
+        file: String,
+        /// Искать паросочетание максимального веса
+        #[arg(long)]
+        weighted: bool,
+    },
+    /// Задача о назначениях (венгерский алгоритм): каждой вершине меньшей
+    /// доли --- пара из другой доли с минимальной суммой весов
+    Assignment {
+        #[arg(short, long)]
+        file: String,
+        /// Вершины левой доли (по умолчанию доли определяются по графу)
+        #[arg(long, value_delimiter = ',')]
+        left: Option<Vec<u32>>,
+        /// Искать максимальную сумму весов
+        #[arg(long)]
+        maximize: bool,
+    },
+    /// Эйлеров цикл или путь (алгоритм Хирхольцера), а если его нет ---
+    /// причина: несбалансированные степени или несвязность
+    Euler {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Маршрут китайского почтальона: кратчайший замкнутый обход всех
+    /// ребер
+    Postman {
+        #[arg(short, long)]
+        file: String,
+    },
+    /// Кратчайший гамильтонов цикл или путь (метод ветвей и границ)
+    Hamiltonian {
+        #[arg(short, long)]
+        file: String,
+        /// Искать путь, а не цикл
+        #[arg(long)]
+        path: bool,
+    },
+    /// Задача коммивояжера: точный алгоритм или эвристика
+    Tsp {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long, value_enum, default_value_t = TspAlgorithm::HeldKarp)]
+        algorithm: TspAlgorithm,
+        /// Начальная вершина для ближайшего соседа (по умолчанию
+        /// наименьшая)
+        #[arg(long)]
+        start: Option<u32>,
+        /// Улучшить найденный маршрут заменами 2-opt
+        #[arg(long)]
+        two_opt: bool,
+    },
+    /// Раскраска вершин графа; с --edges --- раскраска ребер двудольного
+    /// графа в наибольшую степень цветов
+    Color {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long, value_enum, default_value_t = ColoringAlgorithm::Dsatur)]
+        algorithm: ColoringAlgorithm,
+        /// Красить ребра, а не вершины (только двудольный граф)
+        #[arg(long, conflicts_with = "algorithm")]
+        edges: bool,
+        /// Проверить, что найденная раскраска правильная
+        #[arg(long)]
+        check: bool,
+    },
+    /// Реберная и вершинная связность графа; с s и t --- между ними,
+    /// вместе с непересекающимися путями
+    Connectivity {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, requires = "t")]
+        s: Option<u32>,
+        #[arg(short, requires = "s")]
+        t: Option<u32>,
+    },
+    /// Топологический порядок орграфа или цикл, мешающий его построить;
+    /// истоки и стоки
+    Toposort {
+        #[arg(short, long)]
+        file: String,
+        #[arg(long, value_enum, default_value_t = ToposortAlgorithm::Kahn)]
+        algorithm: ToposortAlgorithm,
+    },
+    /// Критический путь ациклического орграфа: ранние и поздние времена
+    /// событий. С s и t --- самый длинный путь из s в t
+    CriticalPath {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short, requires = "t")]
+        s: Option<u32>,
+        #[arg(short, requires = "s")]
+        t: Option<u32>,
+    },
+    /// Число путей из s в t ациклического орграфа
+    CountPaths {
+        #[arg(short, long)]
+        file: String,
+        #[arg(short)]
+        s: u32,
+        #[arg(short)]
+        t: u32,
+    },
+    /// Запустить визуализацию максимального потока
+    Gui,
+}
+
+/// Алгоритм поиска компонент сильной связности
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SccAlgorithm {
+    Tarjan,
+    Kosaraju,
+}
+
+/// Алгоритм максимального потока
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FlowAlgorithm {
+    EdmondsKarp,
+    Dinic,
+    /// Проталкивание предпотока, активные вершины в очереди
+    PushRelabelFifo,
+    /// Проталкивание предпотока, сначала самая высокая вершина
+    PushRelabelHighestLabel,
+}
+
+impl From<FlowAlgorithm> for MaxFlowAlgorithm {
+    fn from(algorithm: FlowAlgorithm) -> Self {
+        match algorithm {
+            FlowAlgorithm::EdmondsKarp => MaxFlowAlgorithm::EdmondsKarp,
+            FlowAlgorithm::Dinic => MaxFlowAlgorithm::Dinic,
+            FlowAlgorithm::PushRelabelFifo => MaxFlowAlgorithm::PushRelabelFifo,
+            FlowAlgorithm::PushRelabelHighestLabel => MaxFlowAlgorithm::PushRelabelHighestLabel,
+        }
+    }
+}
+
+/// Алгоритм построения остова
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SpanningTreeAlgorithm {
+    Kruskal,
+    Prim,
+    Boruvka,
+}
+
+/// Алгоритм задачи коммивояжера
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum TspAlgorithm {
+    /// Динамика по подмножествам, до 20 вершин
+    HeldKarp,
+    /// Метод ветвей и границ
+    BranchAndBound,
+    /// Ближайший сосед (эвристика)
+    NearestNeighbor,
+    /// Кристофидес для метрического графа (эвристика)
+    Christofides,
+}
+
+/// Алгоритм раскраски вершин
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ColoringAlgorithm {
+    /// Жадная раскраска по возрастанию номера
+    Greedy,
+    /// Жадная раскраска по убыванию степени
+    LargestFirst,
+    /// Жадная раскраска, вершины наименьшей степени в конце
+    SmallestLast,
+    Dsatur,
+    /// Наименьшее число цветов перебором, для небольших графов
+    Exact,
+}
+
+/// Алгоритм топологической сортировки
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ToposortAlgorithm {
+    Kahn,
+    /// Обход в глубину
+    Dfs,
+}
+
+/// Эвристика для A*
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Heuristic {
+    Euclidean,
+    Manhattan,
+    /// Без эвристики, то есть Дейкстра
+    None,
+}
+
+/// Выполняет команду, печатает результат и возвращает код завершения
+pub fn run(cli: Cli) -> i32 {
+    match execute(cli.command) {
+        Ok(output) => {
+            let printed = if cli.pretty {
+                serde_json::to_string_pretty(&output)
+            } else {
+                serde_json::to_string(&output)
+            };
+            match printed {
+                Ok(text) => {
+                    println!("{text}");
+                    EXIT_SUCCESS
+                }
+                Err(err) => report(err.into()),
+            }
+        }
+        Err(err) => report(err),
+    }
+}
+
+/// Печатает ошибку в stderr и подбирает для нее код завершения
+fn report(err: Box<dyn Error>) -> i32 {
+    let mut output = json!({ "error": err.to_string() });
+    if let Some(negative) = err.downcast_ref::<NegativeCycle>() {
+        output["negative_cycle"] = json!(negative.cycle());
+    }
+    if let Some(invalid) = err.downcast_ref::<InvalidColoring>() {
+        output["uncolored"] = json!(invalid.uncolored());
+        output["conflicts"] = json!(invalid.conflicts());
+    }
+    if let Some(mismatch) = err.downcast_ref::<FlowMismatch>() {
+        output["max_flow"] = mismatch
+            .values()
+            .iter()
+            .map(|(name, value)| (name.to_string(), json!(value)))
+            .collect();
+    }
+    eprintln!("{output}");
+    if err.is::<io::Error>() || err.is::<serde_json::Error>() {
+        EXIT_IO
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+fn execute(command: Command) -> Result<Value> {
+    match command {
+        Command::New {
+            file,
+            directed,
+            multigraph,
+        } => {
+            let mut graph: JsonGraph = Graph::new(None, Adjacency::default(), directed);
+            if multigraph {
+                graph.to_multigraph_mut();
+            }
+            save(&graph, &file)
+        }
+
+        Command::Load { file } => Ok(serde_json::to_value(load(&file)?)?),
+
+        Command::Save { file, out } => save(&load(&file)?, &out),
+
+        Command::AddNode {
+            file,
+            node,
+            value,
+            out,
+        } => {
+            let mut graph = load(&file)?;
+            let value = match value {
+                Some(raw) => serde_json::from_str(&raw).unwrap_or(Value::String(raw)),
+                None => Value::Null,
+            };
+            graph.add_node(Node::new(node.into(), value))?;
+            save(&graph, out.as_deref().unwrap_or(&file))
+        }
+
+        Command::AddEdge {
+            file,
+            from,
+            to,
+            weight,
+            capacity,
+            cost,
+            out,
+        } => {
+            let mut graph = load(&file)?;
+            let from_node = find_node(&graph, from)?.clone();
+            let to_node = find_node(&graph, to)?;
+            let mut new_edge = Edge::new(&to_node.number, weight, &to_node.value);
+            new_edge.capacity = capacity;
+            new_edge.cost = cost;
+            let id = graph.add_edge(&from_node, &new_edge)?;
+            let mut output = save(&graph, out.as_deref().unwrap_or(&file))?;
+            output["edge_id"] = json!(id);
+            Ok(output)
+        }
+
+        Command::DeleteNode { file, node, out } => {
+            let mut graph = load(&file)?;
+            let node = find_node(&graph, node)?.clone();
+            graph.delete_node(&node)?;
+            save(&graph, out.as_deref().unwrap_or(&file))
+        }
+
+        Command::DeleteEdge {
+            file,
+            from,
+            to,
+            id,
+            out,
+        } => {
+            let mut graph = load(&file)?;
+            match (id, from, to) {
+                (Some(id), _, _) => {
+                    graph.delete_edge_by_id(EdgeId(id))?;
+                }
+                (None, Some(from), Some(to)) => {
+                    let from_node = find_node(&graph, from)?.clone();
+                    graph.delete_edge(&from_node, &to.into())?;
+                }
+                // clap не пропустит другие сочетания аргументов
+                _ => unreachable!(),
+            }
+            save(&graph, out.as_deref().unwrap_or(&file))
+        }
+
+        Command::InDegree { file, node } => {
+            let graph = load(&file)?;
+            Ok(json!({ "node": node, "in_degree": in_degree(&graph, node.into())? }))
+        }
+
+        Command::Degree { file } => Ok(Value::Array(
+            degrees(&load(&file)?)?
+                .into_iter()
+                .map(|(node, degree)| json!({ "node": node, "degree": degree }))
+                .collect(),
+        )),
+
+        Command::Intersect { file, other, out } => {
+            let result = intersection(&load(&file)?, &load(&other)?)?;
+            match out {
+                Some(path) => save(&result, &path),
+                None => Ok(serde_json::to_value(result)?),
+            }
+        }
+
+        Command::Forest { file } => {
+            let graph_type = match classify(&load(&file)?)? {
+                GraphType::Tree => "tree",
+                GraphType::Forest => "forest",
+                GraphType::Default => "default",
+            };
+            Ok(json!({ "type": graph_type }))
+        }
+
+        Command::Connected { file } => Ok(json!({ "connected": is_connected(&load(&file)?)? })),
+
+        Command::Mst {
+            file,
+            algorithm,
+            maximum,
+            out,
+        } => {
+            let graph = load(&file)?;
+            let objective = if maximum {
+                Objective::Maximize
+            } else {
+                Objective::Minimize
+            };
+            let forest = match algorithm {
+                SpanningTreeAlgorithm::Kruskal => kruskal(&graph, objective)?,
+                SpanningTreeAlgorithm::Prim => prim(&graph, objective)?,
+                SpanningTreeAlgorithm::Boruvka => boruvka(&graph, objective)?,
+            };
+            let edges: Vec<Value> = forest
+                .edges()
+                .iter()
+                .map(|edge| {
+                    json!({ "id": edge.id, "from": edge.from, "to": edge.to, "weight": edge.weight })
+                })
+                .collect();
+            let mut output = json!({
+                "weight": forest.total(),
+                "components": forest.component_count(),
+                "edges": edges,
+            });
+            if let Some(path) = out {
+                output["saved"] = save(&forest.to_graph(&graph)?, &path)?;
+            }
+            Ok(output)
+        }
+
+        Command::PathWithin { file, s, t, limit } => Ok(json!({
+            "exists": has_path_within(&load(&file)?, s.into(), t.into(), limit)?
+        })),
+
+        Command::SumWithin { file, limit } => Ok(json!({
+            "node": node_with_total_distance_within(&load(&file)?, limit)?
+        })),
+
+        Command::EachWithin { file, limit } => Ok(json!({
+            "node": node_with_each_distance_within(&load(&file)?, limit)?
+        })),
+
+        Command::Maxflow {
+            file,
+            s,
+            t,
+            algorithm,
+            check,
+        } => {
+            let graph = load(&file)?;
+            let graph = graph.capacities();
+            if check {
+                check_max_flow(&graph, s.into(), t.into())?;
+            }
+            let network = MaxFlowAlgorithm::from(algorithm).solve(&graph, s.into(), t.into())?;
+            let cut = network.min_cut(s.into())?;
+            let flows: Vec<Value> = network
+                .edge_flows()
+                .into_iter()
+                .map(|(from, to, flow, capacity)| {
+                    json!({ "from": from, "to": to, "flow": flow, "capacity": capacity })
+                })
+                .collect();
+            Ok(json!({
+                "max_flow": network.value(),
+                "flows": flows,
+                "source_side": cut.source_side(),
+                "cut": cut.edges(),
+                "cut_capacity": cut.capacity(),
+            }))
+        }
+
+        Command::MinCostFlow {
+            file,
+            s,
+            t,
+            amount,
+            supply,
+            demand,
+        } => {
+            let graph = load(&file)?;
+            let result = match (s.zip(t), amount) {
+                (Some((s, t)), Some(amount)) => min_cost_flow(&graph, s.into(), t.into(), amount)?,
+                (Some((s, t)), None) => min_cost_max_flow(&graph, s.into(), t.into())?,
+                (None, _) => {
+                    let to_map = |balances: Vec<(u32, i64)>| {
+                        balances
+                            .into_iter()
+                            .map(|(node, amount)| (Index(node), amount))
+                            .collect::<HashMap<_, _>>()
+                    };
+                    min_cost_transport(&graph, &to_map(supply), &to_map(demand))?
+                }
+            };
+            let edges: Vec<Value> = result
+                .edges()
+                .iter()
+                .map(|edge| {
+                    json!({ "id": edge.id, "from": edge.from, "to": edge.to, "flow": edge.flow })
+                })
+                .collect();
+            Ok(json!({
+                "flow": result.value(),
+                "cost": result.cost(),
+                "edges": edges,
+            }))
+        }
+
+        Command::BellmanFord {
+            file,
+            s,
+            t,
+            spfa: use_spfa,
+        } => {
+            let graph = load(&file)?;
+            let paths = if use_spfa {
+                spfa(&graph, s.into())?
+            } else {
+                bellman_ford(&graph, s.into())?
+            };
+            Ok(paths_output(&paths, t))
+        }
+
+        Command::Dijkstra { file, s, t } => {
+            let graph = load(&file)?;
+            match t {
+                Some(t) => {
+                    let found = shortest_path(&graph, s.into(), t.into())?;
+                    let (distance, path) = found.unzip();
+                    Ok(json!({ "distance": distance, "path": path }))
+                }
+                None => Ok(paths_output(&dijkstra(&graph, s.into())?, None)),
+            }
+        }
+
+        Command::FloydWarshall { file, s, t } => {
+            let paths = floyd_warshall(&load(&file)?)?;
+            match (s, t) {
+                (Some(s), Some(t)) => Ok(json!({
+                    "distance": paths.distance(s.into(), t.into()),
+                    "path": paths.path(s.into(), t.into()),
+                })),
+                _ => Ok(json!({
+                    "nodes": paths.indices(),
+                    "distances": paths.distance_matrix(),
+                    "next": paths.next_hop_matrix(),
+                })),
+            }
+        }
+
+        Command::Astar {
+            file,
+            s,
+            t,
+            heuristic,
+        } => {
+            let graph = load(&file)?;
+            let (s, t) = (s.into(), t.into());
+            let SearchResult {
+                distance,
+                path,
+                expanded,
+            } = match heuristic {
+                Heuristic::Euclidean => astar(&graph, s, t, euclidean(&graph, t))?,
+                Heuristic::Manhattan => astar(&graph, s, t, manhattan(&graph, t))?,
+                Heuristic::None => astar(&graph, s, t, |_| 0)?,
+            };
+            Ok(json!({ "distance": distance, "path": path, "expanded": expanded }))
+        }
+
+        Command::Scc {
+            file,
+            algorithm,
+            condensation: out,
+        } => {
+            let graph = load(&file)?;
+            let components = match algorithm {
+                SccAlgorithm::Tarjan => tarjan_scc(&graph),
+                SccAlgorithm::Kosaraju => kosaraju_scc(&graph),
+            };
+            let mut output = json!({
+                "strongly_connected": components.len() <= 1,
+                "weakly_connected": is_weakly_connected(&graph),
+                "components": components.components(),
+            });
+            if let Some(path) = out {
+                output["condensation"] = save(&condensation(&graph, &components)?, &path)?;
+            }
+            Ok(output)
+        }
+
+        Command::Biconnected { file, prefix } => {
+            let graph = load(&file)?;
+            let found = biconnectivity(&graph)?;
+            let mut output = json!({
+                "bridges": found.bridges(),
+                "articulation_points": found.articulation_points(),
+                "components": found.components(),
+            });
+            if let Some(prefix) = prefix {
+                let saved = found
+                    .subgraphs(&graph)?
+                    .iter()
+                    .enumerate()
+                    .map(|(number, subgraph)| save(subgraph, &format!("{prefix}{number}.json")))
+                    .collect::<Result<Vec<Value>>>()?;
+                output["saved"] = json!(saved);
+            }
+            Ok(output)
+        }
+
+        Command::Bipartite { file } => {
+            let graph = load(&file)?;
+            let (left, right) = match bipartition(&graph) {
+                Bipartition::Parts(left, right) => (left, right),
+                Bipartition::OddCycle(cycle) => {
+                    return Ok(json!({ "bipartite": false, "odd_cycle": cycle }));
+                }
+            };
+            let mut output = json!({ "bipartite": true, "parts": [left, right] });
+            if !graph.get_is_directed() {
+                let matching = hopcroft_karp(&graph)?;
+                output["matching"] = json!(matching.pairs());
+                output["matching_size"] = json!(matching.len());
+                output["vertex_cover"] = json!(minimum_vertex_cover(&graph)?);
+            }
+            Ok(output)
+        }
+
+        Command::Matching { file, weighted } => {
+            let graph = load(&file)?;
+            if weighted {
+                let (matching, weight) = maximum_weight_matching(&graph)?;
+                return Ok(json!({
+                    "matching": matching.pairs(),
+                    "size": matching.len(),
+                    "weight": weight,
+                }));
+            }
+            let matching = maximum_matching(&graph)?;
+            Ok(json!({
+                "matching": matching.pairs(),
+                "size": matching.len(),
+                "maximum": is_maximum_matching(&graph, &matching)?,
+            }))
+        }
+
+        Command::Assignment {
+            file,
+            left,
+            maximize,
+        } => {
+            let graph = load(&file)?;
+            let left: Option<Vec<Index>> = left.map(|left| left.into_iter().map(Index).collect());
+            let objective = if maximize {
+                Objective::Maximize
+            } else {
+                Objective::Minimize
+            };
+            let assignment = hungarian(&graph, left.as_deref(), objective)?;
+            let pairs: Vec<Value> = assignment
+                .pairs()
+                .iter()
+                .map(|&(left, right, weight)| {
+                    json!({ "left": left, "right": right, "weight": weight })
+                })
+                .collect();
+            Ok(json!({
+                "pairs": pairs,
+                "total": assignment.total(),
+                "unassigned": assignment.unassigned(),
+            }))
+        }
+
+        Command::Euler { file } => {
+            let graph = load(&file)?;
+            let mut output = match eulerian(&graph) {
+                Eulerian::Circuit => json!({ "kind": "circuit" }),
+                Eulerian::Path { start, end } => {
+                    json!({ "kind": "path", "start": start, "end": end })
+                }
+                Eulerian::NotEulerian(EulerObstruction::DegreeImbalance(nodes)) => {
+                    return Ok(json!({
+                        "kind": "none",
+                        "reason": "degree_imbalance",
+                        "nodes": nodes,
+                    }));
+                }
+                Eulerian::NotEulerian(EulerObstruction::Disconnected(components)) => {
+                    return Ok(json!({
+                        "kind": "none",
+                        "reason": "disconnected",
+                        "components": components,
+                    }));
+                }
+            };
+            let trail = hierholzer(&graph)?;
+            let edges: Vec<Value> = trail
+                .edges()
+                .iter()
+                .map(|edge| {
+                    json!({ "id": edge.id, "from": edge.from, "to": edge.to, "weight": edge.weight })
+                })
+                .collect();
+            output["nodes"] = json!(trail.nodes());
+            output["edges"] = json!(edges);
+            output["weight"] = json!(trail.total());
+            Ok(output)
+        }
+
+        Command::Hamiltonian { file, path } => {
+            let graph = load(&file)?;
+            let found = if path {
+                hamiltonian_path(&graph)?
+            } else {
+                hamiltonian_cycle(&graph)?
+            };
+            Ok(tour_json(found.as_ref()))
+        }
+
+        Command::Tsp {
+            file,
+            algorithm,
+            start,
+            two_opt: improve,
+        } => {
+            let graph = load(&file)?;
+            let mut found = match algorithm {
+                TspAlgorithm::HeldKarp => held_karp(&graph)?,
+                TspAlgorithm::BranchAndBound => hamiltonian_cycle(&graph)?,
+                TspAlgorithm::NearestNeighbor => {
+                    let start = match start {
+                        Some(start) => Index(start),
+                        None => graph.node_indices().min().ok_or_else(|| {
+                            GraphError::new(GraphKindError::NodeNotFound, "граф пустой")
+                        })?,
+                    };
+                    nearest_neighbor(&graph, start)?
+                }
+                TspAlgorithm::Christofides => Some(christofides(&graph)?),
+            };
+            if improve && let Some(tour) = &found {
+                found = Some(two_opt(&graph, tour)?);
+            }
+            Ok(tour_json(found.as_ref()))
+        }
+
+        Command::Postman { file } => {
+            let graph = load(&file)?;
+            let route = chinese_postman(&graph)?;
+            let edges: Vec<Value> = route
+                .edges()
+                .iter()
+                .map(|edge| {
+                    json!({ "id": edge.id, "from": edge.from, "to": edge.to, "weight": edge.weight })
+                })
+                .collect();
+            let distinct: HashSet<EdgeId> = route.edges().iter().map(|edge| edge.id).collect();
+            Ok(json!({
+                "weight": route.total(),
+                "repeated": route.len() - distinct.len(),
+                "nodes": route.nodes(),
+                "edges": edges,
+            }))
+        }
+
+        Command::Color {
+            file,
+            algorithm,
+            edges,
+            check,
+        } => {
+            let graph = load(&file)?;
+            if edges {
+                let coloring = bipartite_edge_coloring(&graph)?;
+                let mut colors: Vec<(EdgeId, usize)> = coloring
+                    .colors()
+                    .iter()
+                    .map(|(&id, &color)| (id, color))
+                    .collect();
+                colors.sort();
+                let colors: Vec<Value> = colors
+                    .into_iter()
+                    .map(|(id, color)| json!({ "id": id, "color": color }))
+                    .collect();
+                return Ok(json!({
+                    "colors": coloring.color_count(),
+                    "classes": coloring.classes(),
+                    "edges": colors,
+                }));
+            }
+            let coloring = match algorithm {
+                ColoringAlgorithm::Greedy => greedy_coloring(&graph, GreedyOrder::Natural),
+                ColoringAlgorithm::LargestFirst => {
+                    greedy_coloring(&graph, GreedyOrder::LargestFirst)
+                }
+                ColoringAlgorithm::SmallestLast => {
+                    greedy_coloring(&graph, GreedyOrder::SmallestLast)
+                }
+                ColoringAlgorithm::Dsatur => dsatur(&graph),
+                ColoringAlgorithm::Exact => exact_coloring(&graph),
+            };
+            if check {
+                check_coloring(&graph, &coloring)?;
+            }
+            let mut colors: Vec<(Index, usize)> = coloring
+                .colors()
+                .iter()
+                .map(|(&index, &color)| (index, color))
+                .collect();
+            colors.sort();
+            let colors: Vec<Value> = colors
+                .into_iter()
+                .map(|(node, color)| json!({ "node": node, "color": color }))
+                .collect();
+            Ok(json!({
+                "colors": coloring.color_count(),
+                "classes": coloring.classes(),
+                "nodes": colors,
+            }))
+        }
+
+        Command::Connectivity { file, s, t } => {
+            let graph = load(&file)?;
+            match (s, t) {
+                (Some(s), Some(t)) => {
+                    let edge_paths = edge_disjoint_paths(&graph, s.into(), t.into())?;
+                    let vertex_paths = vertex_disjoint_paths(&graph, s.into(), t.into())?;
+                    Ok(json!({
+                        "edge_connectivity": edge_paths.len(),
+                        "vertex_connectivity": vertex_paths.len(),
+                        "edge_disjoint_paths": edge_paths,
+                        "vertex_disjoint_paths": vertex_paths,
+                    }))
+                }
+                _ => Ok(json!({
+                    "edge_connectivity": edge_connectivity(&graph)?,
+                    "vertex_connectivity": vertex_connectivity(&graph)?,
+                })),
+            }
+        }
+
+        Command::Toposort { file, algorithm } => {
+            let graph = load(&file)?;
+            let sorted = match algorithm {
+                ToposortAlgorithm::Kahn => topological_sort_kahn(&graph)?,
+                ToposortAlgorithm::Dfs => topological_sort_dfs(&graph)?,
+            };
+            let mut output = json!({ "sources": sources(&graph), "sinks": sinks(&graph) });
+            match sorted {
+                TopologicalOrder::Order(order) => output["order"] = json!(order),
+                TopologicalOrder::Cycle(cycle) => output["cycle"] = json!(cycle),
+            }
+            Ok(output)
+        }
+
+        Command::CriticalPath { file, s, t } => {
+            let graph = load(&file)?;
+            if let (Some(s), Some(t)) = (s, t) {
+                let (length, path) = longest_path(&graph, s.into(), t.into())?.unzip();
+                return Ok(json!({ "length": length, "path": path }));
+            }
+
+            let critical = critical_path(&graph)?;
+            let mut nodes: Vec<Index> = graph.iter().map(|(&index, _)| index).collect();
+            nodes.sort();
+            let events: Vec<Value> = nodes
+                .into_iter()
+                .map(|node| {
+                    json!({
+                        "node": node,
+                        "earliest": critical.earliest(node),
+                        "latest": critical.latest(node),
+                        "slack": critical.slack(node),
+                    })
+                })
+                .collect();
+            Ok(json!({
+                "length": critical.length(),
+                "path": critical.path(),
+                "events": events,
+            }))
+        }
+
+        Command::CountPaths { file, s, t } => {
+            let count = count_paths(&load(&file)?, s.into(), t.into())?;
+            // Больше u64 в JSON-число не помещается
+            let count = u64::try_from(count)
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::String(count.to_string()));
+            Ok(json!({ "count": count }))
+        }
+
+        Command::Gui => Err(Box::new(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "gui запускается из main",
+        ))),
+    }
+}
+
+/// Расстояния и предки из дерева путей или, если задан t, путь до t
+fn paths_output(paths: &ShortestPaths<i64>, t: Option<u32>) -> Value {
+    if let Some(t) = t {
+        return json!({
+            "distance": paths.distance(t.into()),
+            "path": paths.path_to(t.into()),
+        });
+    }
+
+    let mut nodes: Vec<_> = paths.distances().iter().collect();
+    nodes.sort();
+    Value::Array(
+        nodes
+            .into_iter()
+            .map(|(&node, distance)| {
+                json!({
+                    "node": node,
+                    "distance": distance,
+                    "parent": paths.predecessor(node),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Найденный маршрут или `{"found": false}`
+fn tour_json(tour: Option<&Tour<i64>>) -> Value {
+    match tour {
+        Some(tour) => json!({
+            "found": true,
+            "cycle": tour.is_cycle(),
+            "nodes": tour.nodes(),
+            "cost": tour.cost(),
+        }),
+        None => json!({ "found": false }),
+    }
+}
+
+/// Разбирает баланс вершины `вершина:количество`
+fn parse_balance(raw: &str) -> std::result::Result<(u32, i64), String> {
+    let (node, amount) = raw
+        .split_once(':')
+        .ok_or_else(|| format!("ожидалось `вершина:количество`, получено `{raw}`"))?;
+    let node = node
+        .trim()
+        .parse()
+        .map_err(|err| format!("вершина `{node}`: {err}"))?;
+    let amount = amount
+        .trim()
+        .parse()
+        .map_err(|err| format!("количество `{amount}`: {err}"))?;
+    Ok((node, amount))
+}
+
+fn load(path: &str) -> Result<JsonGraph> {
+    Graph::new_from_file(path)
+}
+
+fn save<T>(graph: &Graph<T, i64>, path: &str) -> Result<Value>
+where
+    T: Clone + Serialize + DeserializeOwned + Debug + Default,
+{
+    graph.write_in_file(path)?;
+    Ok(json!({ "saved": path, "nodes": graph.len() }))
+}
+
+fn find_node(graph: &JsonGraph, number: u32) -> Result<&Node<Value>> {
+    graph.get_node(&Index(number)).ok_or_else(|| {
+        Box::new(GraphError::new(
+            GraphKindError::NodeNotFound,
+            &format!("вершина {number}"),
+        )) as Box<dyn Error>
+    })
+}
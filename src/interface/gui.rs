@@ -1,7 +1,23 @@
-use crate::graph::core::{Graph, Index};
 use eframe::egui;
-use std::collections::HashMap;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use theory_graph_homework::{
+    Graph, Index,
+    algorithms::{Coloring, FlowNetwork, MinCut, dsatur, shortest_path},
+};
+
+/// Цвета классов раскраски без красного и зеленого, занятых стоком и
+/// истоком. Если классов больше, цвета повторяются, поэтому номер класса
+/// подписывается у каждой вершины
+const CLASS_COLORS: [egui::Color32; 8] = [
+    egui::Color32::from_rgb(0, 130, 200),
+    egui::Color32::from_rgb(245, 130, 48),
+    egui::Color32::from_rgb(145, 30, 180),
+    egui::Color32::from_rgb(70, 190, 190),
+    egui::Color32::from_rgb(170, 110, 40),
+    egui::Color32::from_rgb(240, 50, 230),
+    egui::Color32::from_rgb(0, 0, 128),
+    egui::Color32::from_rgb(128, 128, 128),
+];
 
 static EXAMPLE_JSON: &str = r#"{
   "nodes": {
@@ -58,13 +74,21 @@ struct MaxFlowVisualizer {
     t_input: String,
 
     // Состояние алгоритма
-    capacity: Option<HashMap<(Index, Index), u32>>,
-    flow: Option<HashMap<(Index, Index), i32>>,
+    network: Option<FlowNetwork>,
     current_path: Option<Vec<Index>>,
-    max_flow: u32,
     step: usize,
     s: Option<Index>,
     t: Option<Index>,
+    error: Option<String>,
+
+    // Кратчайший путь из s в t и его длина
+    shortest_path: Option<(u32, Vec<Index>)>,
+
+    // Минимальный разрез, когда увеличивающих путей больше нет
+    cut: Option<MinCut>,
+
+    // Раскраска вершин (DSatur)
+    coloring: Option<Coloring>,
 }
 
 impl MaxFlowVisualizer {
@@ -78,13 +102,15 @@ impl MaxFlowVisualizer {
             node_positions: HashMap::new(),
             show_graph: false,
             show_about: false,
-            capacity: None,
-            flow: None,
+            network: None,
             current_path: None,
-            max_flow: 0,
             step: 0,
             s: None,
             t: None,
+            error: None,
+            shortest_path: None,
+            cut: None,
+            coloring: None,
         }
     }
 
@@ -109,126 +135,82 @@ impl MaxFlowVisualizer {
     }
 
     fn build_capacity_and_flow(&mut self, graph: &Graph<String>) {
-        let mut capacity: HashMap<(Index, Index), u32> = HashMap::new();
-        let mut flow: HashMap<(Index, Index), i32> = HashMap::new();
-
-        for (&from, adj) in graph.iter() {
-            for edge in adj {
-                *capacity.entry((from, edge.node.number)).or_insert(0) += edge.weight;
-                flow.entry((from, edge.node.number)).or_insert(0);
-                flow.entry((edge.node.number, from)).or_insert(0);
+        match FlowNetwork::new(&graph.capacities()) {
+            Ok(network) => {
+                self.network = Some(network);
+                self.error = None;
+            }
+            Err(err) => {
+                self.network = None;
+                self.error = Some(err.to_string());
             }
         }
-
-        self.capacity = Some(capacity);
-        self.flow = Some(flow);
-        self.max_flow = 0;
         self.step = 0;
         self.current_path = None;
+        self.cut = None;
     }
 
     fn residual(&self, from: Index, to: Index) -> u32 {
-        if let (Some(capacity), Some(flow)) = (&self.capacity, &self.flow) {
-            let c = *capacity.get(&(from, to)).unwrap_or(&0);
-            let f = *flow.get(&(from, to)).unwrap_or(&0);
-            if c == 0 && f > 0 {
-                f as u32
-            } else {
-                c.saturating_sub(f.max(0) as u32)
-            }
-        } else {
-            0
-        }
+        self.network
+            .as_ref()
+            .map_or(0, |network| network.residual(from, to))
+    }
+
+    fn max_flow(&self) -> u32 {
+        self.network.as_ref().map_or(0, FlowNetwork::value)
     }
 
     fn next_step(&mut self) {
-        if self.graph.is_none() || self.capacity.is_none() || self.s.is_none() || self.t.is_none() {
+        let (Some(network), Some(s), Some(t)) = (self.network.as_mut(), self.s, self.t) else {
             return;
-        }
-
-        let graph = self.graph.as_ref().unwrap();
-        let capacity = self.capacity.as_ref().unwrap();
-        let flow = self.flow.as_mut().unwrap();
-        let s = self.s.unwrap();
-        let t = self.t.unwrap();
+        };
 
         // BFS поиск пути
-        let mut parent: HashMap<Index, Option<Index>> = HashMap::new();
-        let mut visited: HashMap<Index, bool> = HashMap::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back(s);
-        parent.insert(s, None);
-        visited.insert(s, true);
-
-        let mut path_found = false;
-        'bfs: while let Some(from_ind) = queue.pop_front() {
-            if let Some(adj) = graph.get_adjacency(&from_ind) {
-                for edge in adj {
-                    let to_ind = edge.node.number;
-                    if !visited.get(&to_ind).copied().unwrap_or(false) && {
-                        let c = *capacity.get(&(from_ind, to_ind)).unwrap_or(&0);
-                        let f = *flow.get(&(from_ind, to_ind)).unwrap_or(&0);
-                        let r = if c == 0 && f > 0 {
-                            f as u32
-                        } else {
-                            c.saturating_sub(f.max(0) as u32)
-                        };
-                        r > 0
-                    } {
-                        parent.insert(to_ind, Some(from_ind));
-                        visited.insert(to_ind, true);
-                        if to_ind == t {
-                            path_found = true;
-                            break 'bfs;
-                        }
-                        queue.push_back(to_ind);
-                    }
+        self.current_path = network.find_augmenting_path(s, t);
+
+        // Обновляем потоки вдоль найденного пути
+        if let Some(path) = &self.current_path {
+            match network.augment(path) {
+                Ok(_) => self.step += 1,
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    self.current_path = None;
                 }
             }
+        } else {
+            // Путей больше нет: поток максимален, строим разрез
+            match network.min_cut(s) {
+                Ok(cut) => self.cut = Some(cut),
+                Err(err) => self.error = Some(err.to_string()),
+            }
         }
+    }
 
-        if !path_found || !parent.contains_key(&t) {
-            self.current_path = None;
+    fn find_shortest_path(&mut self) {
+        let (Some(graph), Some(s), Some(t)) = (self.graph.as_ref(), self.s, self.t) else {
             return;
-        }
-
-        // Восстанавливаем путь
-        let mut path = vec![t];
-        let mut v = t;
-        while let Some(Some(u)) = parent.get(&v) {
-            path.push(*u);
-            v = *u;
-        }
-        path.reverse();
-
-        self.current_path = Some(path.clone());
-
-        // Бутылочное горлышко
-        let mut path_flow: i32 = i32::MAX;
-        for window in path.windows(2) {
-            let from = window[0];
-            let to = window[1];
+        };
 
-            let c = *capacity.get(&(from, to)).unwrap_or(&0);
-            let f = *flow.get(&(from, to)).unwrap_or(&0);
-            let r = if c == 0 && f > 0 {
-                f as u32
-            } else {
-                c.saturating_sub(f.max(0) as u32)
-            };
-
-            path_flow = path_flow.min(r as i32);
+        match shortest_path(graph, s, t) {
+            Ok(Some(found)) => {
+                self.shortest_path = Some(found);
+                self.error = None;
+            }
+            Ok(None) => {
+                self.shortest_path = None;
+                self.error = Some(format!("путь из {s} в {t} не найден"));
+            }
+            Err(err) => {
+                self.shortest_path = None;
+                self.error = Some(err.to_string());
+            }
         }
+    }
 
-        // Обновляем потоки
-        for window in path.windows(2) {
-            *flow.entry((window[0], window[1])).or_insert(0) += path_flow;
-            *flow.entry((window[1], window[0])).or_insert(0) -= path_flow;
+    fn color_graph(&mut self) {
+        if let Some(graph) = &self.graph {
+            self.coloring = Some(dsatur(graph));
         }
-
-        self.max_flow += path_flow as u32;
-        self.step += 1;
     }
 
     fn draw_graph(&self, ui: &mut egui::Ui) {
@@ -242,39 +224,88 @@ impl MaxFlowVisualizer {
             egui::Sense::hover(),
         );
 
+        // Пары вершин, у которых уже есть подпись: сеть хранит поток и
+        // пропускную способность параллельных дуг суммарно
+        let mut labeled: HashSet<(Index, Index)> = HashSet::new();
+
         // Рисуем рёбра
         for (from_idx, adj) in graph.iter() {
             if let Some(&from_pos) = self.node_positions.get(from_idx) {
                 for edge in adj {
                     let to_idx = edge.node.number;
 
-                    if **from_idx > *to_idx {
+                    // Вторая половина неориентированного ребра; дуги орграфа
+                    // рисуются все
+                    if !graph.get_is_directed() && **from_idx > *to_idx {
                         continue;
                     }
 
                     if let Some(&to_pos) = self.node_positions.get(&to_idx) {
+                        // Встречные дуги u→v и v→u разводим по разные стороны
+                        // от прямой между вершинами, иначе они совпадают
+                        let normal = {
+                            let dir = (to_pos - from_pos).normalized();
+                            egui::Vec2::new(-dir.y, dir.x)
+                        };
+                        let antiparallel = graph.get_is_directed()
+                            && *from_idx != to_idx
+                            && graph.has_edge(&to_idx, from_idx).unwrap_or(false);
+                        let (from_pos, to_pos) = if antiparallel {
+                            (from_pos + normal * 8.0, to_pos + normal * 8.0)
+                        } else {
+                            (from_pos, to_pos)
+                        };
+
                         let current_flow = self.residual(*from_idx, to_idx);
                         let is_path_edge = self
                             .current_path
                             .as_ref()
                             .is_some_and(|path| path.windows(2).any(|w| w == [*from_idx, to_idx]));
+                        let is_cut_edge = self
+                            .cut
+                            .as_ref()
+                            .is_some_and(|cut| cut.edges().contains(&(*from_idx, to_idx)));
+                        let is_shortest_edge =
+                            self.shortest_path.as_ref().is_some_and(|(_, path)| {
+                                path.windows(2).any(|w| {
+                                    w == [*from_idx, to_idx]
+                                        || (!graph.get_is_directed() && w == [to_idx, *from_idx])
+                                })
+                            });
 
                         // Цвет и толщина в зависимости от потока и пути
                         let color = if is_path_edge {
                             egui::Color32::GREEN
+                        } else if is_cut_edge {
+                            egui::Color32::from_rgb(200, 0, 200)
+                        } else if is_shortest_edge {
+                            egui::Color32::from_rgb(255, 165, 0)
                         } else if current_flow > 0 {
                             egui::Color32::from_rgb(100, 200, 100)
                         } else {
                             egui::Color32::LIGHT_GRAY
                         };
 
-                        let stroke_width = if is_path_edge { 4.0 } else { 2.0 };
+                        let stroke_width = if is_path_edge || is_cut_edge || is_shortest_edge {
+                            4.0
+                        } else {
+                            2.0
+                        };
 
-                        // Линия ребра
-                        painter.line_segment(
-                            [from_pos, to_pos],
-                            egui::Stroke::new(stroke_width, color),
-                        );
+                        // Линия ребра; ребра разреза --- пунктиром
+                        if is_cut_edge {
+                            painter.extend(egui::Shape::dashed_line(
+                                &[from_pos, to_pos],
+                                egui::Stroke::new(stroke_width, color),
+                                10.0,
+                                6.0,
+                            ));
+                        } else {
+                            painter.line_segment(
+                                [from_pos, to_pos],
+                                egui::Stroke::new(stroke_width, color),
+                            );
+                        }
 
                         // Стрелка
                         let dir = (to_pos - from_pos).normalized();
@@ -294,20 +325,35 @@ impl MaxFlowVisualizer {
                             egui::Stroke::new(stroke_width, color),
                         );
 
-                        // Текст: flow/capacity
+                        // Текст: flow/capacity, одна подпись на пару вершин
+                        if !labeled.insert((*from_idx, to_idx)) {
+                            continue;
+                        }
                         let mid_pos = egui::Pos2::new(
                             (from_pos.x + to_pos.x) * 0.5,
                             (from_pos.y + to_pos.y) * 0.5,
                         );
+                        // Подпись встречной дуги --- со своей стороны
+                        let mid_pos = if antiparallel {
+                            mid_pos + normal * 18.0
+                        } else {
+                            mid_pos
+                        };
 
-                        let flow_str = if let Some(flow_map) = &self.flow {
+                        let flow_str = if let Some(network) = &self.network {
                             format!(
                                 "{}/{}",
-                                flow_map.get(&(*from_idx, to_idx)).unwrap_or(&0),
-                                edge.weight
+                                network.flow(*from_idx, to_idx),
+                                network.capacity(*from_idx, to_idx)
                             )
                         } else {
-                            edge.weight.to_string()
+                            // Веса всех параллельных ребер
+                            let weights: Vec<String> = adj
+                                .iter()
+                                .filter(|other| other.node.number == to_idx)
+                                .map(|other| other.weight.to_string())
+                                .collect();
+                            weights.join(", ")
                         };
 
                         painter.text(
@@ -322,14 +368,33 @@ impl MaxFlowVisualizer {
             }
         }
 
-        // Вершины (подсветка s и t)
+        // Доля истока минимального разреза --- полупрозрачный фон
+        if let Some(cut) = &self.cut {
+            for index in cut.source_side() {
+                if let Some(&pos) = self.node_positions.get(index) {
+                    painter.circle_filled(
+                        pos,
+                        45.0,
+                        egui::Color32::from_rgba_unmultiplied(0, 200, 0, 60),
+                    );
+                }
+            }
+        }
+
+        // Вершины (подсветка s и t, остальные --- по цвету раскраски)
         for (&idx, node) in &graph.get_all_nodes() {
             if let Some(&pos) = self.node_positions.get(&idx) {
                 let radius = 30.0;
+                let class = self
+                    .coloring
+                    .as_ref()
+                    .and_then(|coloring| coloring.color_of(idx));
                 let color = if Some(idx) == self.s {
                     egui::Color32::from_rgb(0, 255, 0) // зелёный для источника
                 } else if Some(idx) == self.t {
                     egui::Color32::from_rgb(255, 0, 0) // красный для стока
+                } else if let Some(class) = class {
+                    CLASS_COLORS[class % CLASS_COLORS.len()]
                 } else {
                     egui::Color32::from_rgb(40, 120, 200)
                 };
@@ -352,6 +417,17 @@ impl MaxFlowVisualizer {
                     egui::FontId::proportional(17.0),
                     egui::Color32::BLACK,
                 );
+
+                // Номер цвета над вершиной
+                if let Some(class) = class {
+                    painter.text(
+                        egui::Pos2::new(pos.x, pos.y - radius - 10.0),
+                        egui::Align2::CENTER_CENTER,
+                        format!("цвет {class}").as_str(),
+                        egui::FontId::proportional(15.0),
+                        egui::Color32::BLACK,
+                    );
+                }
             }
         }
     }
@@ -365,15 +441,39 @@ impl eframe::App for MaxFlowVisualizer {
 
                 ui.vertical(|ui| {
                     ui.label(format!("Шаг: {}", self.step));
-                    ui.label(format!("Поток: {}", self.max_flow));
+                    ui.label(format!("Поток: {}", self.max_flow()));
+                    if let Some((distance, path)) = &self.shortest_path {
+                        let route: Vec<String> = path.iter().map(Index::to_string).collect();
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            format!("Кратчайший путь: {} (длина {distance})", route.join(" → ")),
+                        );
+                    }
+                    if let Some(coloring) = &self.coloring {
+                        ui.label(format!("Раскраска: {} цветов", coloring.color_count()));
+                    }
 
                     // Финальный статус
-                    if self.capacity.is_some() && self.current_path.is_none() {
+                    if let Some(error) = &self.error {
+                        ui.colored_label(egui::Color32::RED, format!("Ошибка: {error}"));
+                    } else if self.network.is_some() && self.current_path.is_none() {
                         ui.colored_label(
                             egui::Color32::from_rgb(0, 255, 0),
-                            format!("✅ МАКСИМАЛЬНЫЙ ПОТОК: {}", self.max_flow),
+                            format!("✅ МАКСИМАЛЬНЫЙ ПОТОК: {}", self.max_flow()),
                         );
-                    } else if self.capacity.is_some() {
+                        if let Some(cut) = &self.cut {
+                            let side: Vec<String> =
+                                cut.source_side().iter().map(Index::to_string).collect();
+                            ui.colored_label(
+                                egui::Color32::from_rgb(200, 0, 200),
+                                format!(
+                                    "Минимальный разрез: {} (S = {{{}}})",
+                                    cut.capacity(),
+                                    side.join(", ")
+                                ),
+                            );
+                        }
+                    } else if self.network.is_some() {
                         ui.label("🔍 Идёт поиск...");
                     }
                 });
@@ -382,16 +482,18 @@ impl eframe::App for MaxFlowVisualizer {
                     if ui.button("About").clicked() {
                         self.show_about = true;
                     }
-                    if self.show_graph && self.capacity.is_some() {
+                    if self.show_graph && self.network.is_some() {
                         if ui.button("▶️ Следующий шаг").clicked() {
                             self.next_step();
                         }
                         if ui.button("🔄 Сброс").clicked() {
-                            self.capacity = None;
-                            self.flow = None;
+                            self.network = None;
                             self.current_path = None;
-                            self.max_flow = 0;
                             self.step = 0;
+                            self.error = None;
+                            self.shortest_path = None;
+                            self.cut = None;
+                            self.coloring = None;
                         }
                     }
                 });
@@ -432,6 +534,8 @@ impl eframe::App for MaxFlowVisualizer {
                             match serde_json::from_str::<Graph<String>>(&self.json_input) {
                                 Ok(graph) => {
                                     self.graph = Some(graph.clone());
+                                    self.shortest_path = None;
+                                    self.coloring = None;
                                     self.node_positions = self.compute_layout(&graph);
                                     self.show_graph = true;
                                     ui.label("Граф загружен!");
@@ -441,10 +545,10 @@ impl eframe::App for MaxFlowVisualizer {
                                 }
                             }
                         }
-                        if ui.button("💾 Сохранить").clicked() {
-                            if let Some(graph) = &self.graph {
-                                let _ = graph.write_in_file(&self.json_output);
-                            }
+                        if ui.button("💾 Сохранить").clicked()
+                            && let Some(graph) = &self.graph
+                        {
+                            let _ = graph.write_in_file(&self.json_output);
                         };
 
                         ui.end_row();
@@ -452,26 +556,34 @@ impl eframe::App for MaxFlowVisualizer {
                         if self.show_graph && self.graph.is_some() {
                             ui.label("Исток (s):");
                             ui.add(egui::TextEdit::singleline(&mut self.s_input));
-                            if ui.button("Set s").clicked() {
-                                if let Ok(val) = self.s_input.parse::<u32>() {
-                                    self.s = Some(Index(val));
-                                }
+                            if ui.button("Set s").clicked()
+                                && let Ok(val) = self.s_input.parse::<u32>()
+                            {
+                                self.s = Some(Index(val));
                             }
                             ui.end_row();
 
                             ui.label("Сток (t):");
                             ui.add(egui::TextEdit::singleline(&mut self.t_input));
-                            if ui.button("Set t").clicked() {
-                                if let Ok(val) = self.t_input.parse::<u32>() {
-                                    self.t = Some(Index(val));
-                                }
+                            if ui.button("Set t").clicked()
+                                && let Ok(val) = self.t_input.parse::<u32>()
+                            {
+                                self.t = Some(Index(val));
                             }
                             ui.end_row();
 
-                            if ui.button("🚀 Запустить алгоритм").clicked() {
-                                if let Some(graph) = &self.graph {
-                                    self.build_capacity_and_flow(&graph.clone());
-                                }
+                            if ui.button("🚀 Запустить алгоритм").clicked()
+                                && let Some(graph) = &self.graph
+                            {
+                                self.build_capacity_and_flow(&graph.clone());
+                            }
+                            if ui.button("📍 Кратчайший путь").clicked() {
+                                self.find_shortest_path();
+                            }
+                            ui.end_row();
+
+                            if ui.button("🎨 Раскраска").clicked() {
+                                self.color_graph();
                             }
                         }
                     });
@@ -533,6 +645,21 @@ impl eframe::App for MaxFlowVisualizer {
                         оставляет загруженный граф, чтобы можно было запустить \
                         визуализацию снова.",
                     );
+                    ui.label(
+                        "7. Кнопка «Кратчайший путь» подсвечивает оранжевым \
+                        кратчайший путь из s в t (алгоритм Дейкстры).",
+                    );
+                    ui.label(
+                        "8. Когда увеличивающих путей не остается, вершины доли \
+                        истока минимального разреза закрашиваются зеленым фоном, \
+                        а ребра разреза рисуются фиолетовым пунктиром.",
+                    );
+                    ui.label(
+                        "9. Кнопка «Раскраска» красит вершины алгоритмом DSatur: \
+                        смежные вершины получают разные цвета, вершины одного \
+                        цвета образуют независимое множество. Номер цвета подписан \
+                        над каждой вершиной.",
+                    );
                     ui.separator();
                     ui.add_space(8.0);
                     ui.label("Пример JSON графа:");
@@ -0,0 +1,17 @@
+//! Библиотека для работы с графами: структура [`Graph<T>`] со списками
+//! смежности и алгоритмы над ней в модуле [`algorithms`].
+
+pub mod algorithms;
+pub mod graph;
+mod tasks;
+
+pub use graph::{
+    core::{
+        Adjacency, CapacityView, ColorNode, Edge, EdgeId, Graph, GraphError, GraphKindError,
+        GraphType, Index, Node,
+    },
+    csr::CsrGraph,
+    union_find::UnionFind,
+    view::{FilteredView, GraphView, ReversedView, UndirectedView},
+    weight::{TotalF64, Weight},
+};
@@ -1,11 +1,25 @@
-mod graph;
-mod tasks;
+mod interface;
 
 use std::process;
 
-use crate::graph::{cli, gui::gui_interface};
+use clap::Parser;
+
+use crate::interface::{
+    cli,
+    commands::{self, Cli, Command},
+    gui::gui_interface,
+};
 
 fn main() -> Result<(), eframe::Error> {
+    // С аргументами работаем в неинтерактивном режиме, иначе --- меню
+    if std::env::args_os().len() > 1 {
+        let args = Cli::parse();
+        if let Command::Gui = args.command {
+            return gui_interface();
+        }
+        process::exit(commands::run(args));
+    }
+
     println!("Выберете вариант:");
     println!("1. 1-11 задания в cli формате");
     println!("2. Визуализация нахождения максимального потока в gui формате");
@@ -1,104 +1,45 @@
-use std::{collections::HashMap, error::Error, fmt::Debug};
+use std::error::Error;
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{Graph, GraphError, GraphKindError, Index};
+use crate::{
+    algorithms::shortest_path::floyd_warshall,
+    graph::{
+        core::{GraphError, GraphKindError},
+        view::GraphView,
+    },
+};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-fn floid_uorshel<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    pos_for_index: &HashMap<Index, usize>,
-) -> Vec<Vec<u32>> {
-    let mut dist = vec![vec![u32::MAX; graph.len()]; graph.len()];
-
-    for (i, dst) in dist.iter_mut().enumerate() {
-        dst[i] = 0;
-    }
-
-    // Заполняем dist минимальным расстоянием из одной вершины в другую
-    for (ind_from, adj) in graph.iter() {
-        let from = pos_for_index[ind_from];
-        for edge in adj {
-            let to = pos_for_index[&edge.node.number];
-            dist[from][to] = dist[from][to].min(edge.weight);
-        }
-    }
-
-    // Проходимся по всем вершинам и находим более короткие пути,
-    // через другие вершины
-    for middle in 0..graph.len() {
-        for from in 0..graph.len() {
-            if dist[from][middle] == u32::MAX {
-                continue;
-            }
-            for to in 0..graph.len() {
-                if dist[middle][to] == u32::MAX {
-                    continue;
-                }
-                // Если через middle путь короче, то обновляем dist[from][to]
-                let through_middle = dist[from][middle] + dist[middle][to];
-                if through_middle < dist[from][to] {
-                    dist[from][to] = through_middle;
-                }
-            }
-        }
-    }
-    dist
-}
-
-/// Найти в [`Graph<T>`] вершину, каждая из минимальных стоимостей пути от
+/// Найти в графе вершину, каждая из минимальных стоимостей пути от
 /// которой до остальных не превосходит limit.
 ///
 /// # Errors
 /// Эта функция вернет ошибку, если граф ориентированный.
 /// Ошибка типа: [`GraphError`]
-pub fn task_10_3<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    limit: &u32,
-) -> Result<i32> {
-    if graph.get_is_directed() {
+pub fn task_10_3<G: GraphView>(graph: &G, limit: &G::Weight) -> Result<i32> {
+    if graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть неориентированный граф",
         )));
     }
 
-    // HashMap для сопостовления индекса вершины с индексом в dist
-    let pos_for_index: HashMap<Index, usize> = graph
-        .iter()
-        .enumerate()
-        .map(|(i, (&ind, _))| (ind, i))
-        .collect();
-
-    let dist = floid_uorshel(graph, &pos_for_index);
+    let paths = floyd_warshall(graph)?;
+    let dist = paths.distance_matrix();
 
     'nodes: for (node_ind, cur_dists) in dist.iter().enumerate() {
         for (edge_ind, weight) in cur_dists.iter().enumerate() {
             if node_ind == edge_ind {
                 continue;
             }
-            // Если путь слишком длинный, то node_ind не подходит
-            if weight > limit {
+            // Если пути нет или он слишком длинный, то node_ind не подходит
+            if weight.is_none_or(|weight| weight > *limit) {
                 continue 'nodes;
             }
         }
 
         // Если вершина подошла
-        return Ok(
-            match pos_for_index
-                .iter()
-                .find_map(|(key, &val)| if val == node_ind { Some(key) } else { None })
-            {
-                Some(&key) => *key as i32,
-                None => {
-                    return Err(Box::new(GraphError::new(
-                        GraphKindError::NodeNotFound,
-                        "что-то пошло не так",
-                    )));
-                }
-            },
-        );
+        return Ok(*paths.indices()[node_ind] as i32);
     }
 
     Ok(-1)
@@ -1,146 +1,299 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     error::Error,
-    fmt::Debug,
 };
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{Graph, GraphError, GraphKindError, Index};
+use crate::graph::{
+    core::{GraphError, GraphKindError, Index},
+    view::GraphView,
+    weight::{Weight, ensure_non_negative},
+};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-struct BuildCapacityAndFlowResult(HashMap<(Index, Index), u32>, HashMap<(Index, Index), i32>);
+/// Минимальный разрез сети, найденный по максимальному потоку.
+///
+/// Доля истока --- вершины, достижимые из истока по ребрам с ненулевой
+/// остаточной пропускной способностью. Ребра разреза идут из доли истока
+/// в остальные вершины; все они насыщены, и их суммарная пропускная
+/// способность равна величине максимального потока.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut<W = u32> {
+    source_side: Vec<Index>,
+    edges: Vec<(Index, Index)>,
+    capacity: W,
+}
 
-/// Создаем HashMap-ы для пропускных способностей и потоков
-fn build_capacity_and_flow<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-) -> BuildCapacityAndFlowResult {
-    let mut capacity: HashMap<(Index, Index), u32> = HashMap::new();
+impl<W: Weight> MinCut<W> {
+    /// Вершины доли истока по возрастанию номера
+    pub fn source_side(&self) -> &[Index] {
+        &self.source_side
+    }
 
-    let mut flow: HashMap<(Index, Index), i32> = HashMap::new();
+    /// Лежит ли вершина в доле истока
+    pub fn contains(&self, index: Index) -> bool {
+        self.source_side.binary_search(&index).is_ok()
+    }
 
-    for (&from, adj) in graph.iter() {
-        for edge in adj {
-            *capacity.entry((from, edge.node.number)).or_insert(0) += edge.weight;
-            // Обнуляем поток по всем ребрам
-            flow.entry((from, edge.node.number)).or_insert(0);
-            flow.entry((edge.node.number, from)).or_insert(0);
-        }
+    /// Ребра разреза `(from, to)` по возрастанию
+    pub fn edges(&self) -> &[(Index, Index)] {
+        &self.edges
     }
 
-    BuildCapacityAndFlowResult(capacity, flow)
+    /// Суммарная пропускная способность ребер разреза
+    pub fn capacity(&self) -> W {
+        self.capacity
+    }
 }
 
-/// Остаточная пропускная способность от (from, to)
-fn residual(
-    capacity: &HashMap<(Index, Index), u32>,
-    flow: &HashMap<(Index, Index), i32>,
-    from: Index,
-    to: Index,
-) -> u32 {
-    let c = *capacity.get(&(from, to)).unwrap_or(&0);
-    let f = *flow.get(&(from, to)).unwrap_or(&0);
-    if c == 0 && f > 0 {
-        // чисто обратное ребро: остаточная = f
-        f as u32
-    } else {
-        // прямое ребро: остаточная = c - f
-        c.saturating_sub(f.max(0) as u32)
-    }
+/// Состояние сети для алгоритма Эдмондса–Карпа: пропускные способности,
+/// текущие потоки и соседи в остаточной сети.
+///
+/// Поток хранится по каждой дуге отдельно и неотрицателен; встречные
+/// потоки взаимно сокращаются, поэтому из `flow(u, v)` и `flow(v, u)`
+/// хотя бы один нулевой. Остаточная пропускная способность ---
+/// `c(u, v) - f(u, v) + f(v, u)`.
+#[derive(Debug, Clone)]
+pub struct FlowNetwork<W = u32> {
+    capacity: HashMap<(Index, Index), W>,
+    flow: HashMap<(Index, Index), W>,
+    neighbors: HashMap<Index, HashSet<Index>>,
+    value: W,
 }
 
-/// Поиск увеличивающего пути BFS-ом.
-fn bfs_find_path<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    capacity: &HashMap<(Index, Index), u32>,
-    flow: &HashMap<(Index, Index), i32>,
-    start: Index,
-    end: Index,
-) -> (bool, HashMap<Index, Option<Index>>) {
-    let mut parent: HashMap<Index, Option<Index>> = HashMap::new();
-    let mut visited: HashMap<Index, bool> = HashMap::new();
-
-    let mut queue = VecDeque::<Index>::new();
-
-    queue.push_back(start);
-    parent.insert(start, None);
-    visited.insert(start, true);
-
-    'bfs: while let Some(from_ind) = queue.pop_front() {
-        // Прямые рёбра
-        if let Some(adj) = graph.get_adjacency(&from_ind) {
-            for edge in adj {
-                let to_ind = edge.node.number;
-                if !visited.get(&to_ind).copied().unwrap_or(false)
-                    && residual(capacity, flow, from_ind, to_ind) > 0
-                {
-                    parent.insert(to_ind, Some(from_ind));
-                    visited.insert(to_ind, true);
-                    if to_ind == end {
-                        break 'bfs;
-                    }
-                    queue.push_back(to_ind);
-                }
+impl<W> FlowNetwork<W>
+where
+    W: Weight,
+{
+    /// Создает сеть с нулевым потоком по ребрам графа. Пропускные
+    /// способности параллельных ребер складываются.
+    ///
+    /// # Errors
+    /// Вернет [`GraphError`], если у ребра отрицательный вес или сумма
+    /// параллельных ребер переполнилась.
+    pub fn new<G: GraphView<Weight = W>>(graph: &G) -> Result<Self> {
+        ensure_non_negative(graph)?;
+
+        let mut capacity: HashMap<(Index, Index), W> = HashMap::new();
+        let mut neighbors: HashMap<Index, HashSet<Index>> = HashMap::new();
+
+        for from in graph.node_indices() {
+            for (to, weight) in graph.neighbors(from) {
+                let total = capacity.entry((from, to)).or_insert(W::ZERO);
+                *total = total.try_add(weight)?;
+                // В остаточной сети ребро может идти в обе стороны
+                neighbors.entry(from).or_default().insert(to);
+                neighbors.entry(to).or_default().insert(from);
             }
-            // Обратные ребра
-            for (&(from, to), f_from_to) in flow.iter() {
-                if to == from_ind && *f_from_to > 0 && !visited.get(&from).copied().unwrap_or(false)
-                {
-                    parent.insert(from, Some(from_ind));
-                    visited.insert(from, true);
-                    if to == end {
-                        break 'bfs;
-                    }
+        }
+
+        Ok(Self {
+            capacity,
+            flow: HashMap::new(),
+            neighbors,
+            value: W::ZERO,
+        })
+    }
 
+    /// Пропускная способность ребра (from, to)
+    pub fn capacity(&self, from: Index, to: Index) -> W {
+        *self.capacity.get(&(from, to)).unwrap_or(&W::ZERO)
+    }
+
+    /// Текущий поток по ребру (from, to)
+    pub fn flow(&self, from: Index, to: Index) -> W {
+        *self.flow.get(&(from, to)).unwrap_or(&W::ZERO)
+    }
+
+    /// Суммарный поток, найденный на данный момент
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Остаточная пропускная способность от (from, to)
+    pub fn residual(&self, from: Index, to: Index) -> W {
+        // Поток по дуге не превосходит ее пропускной способности
+        let forward = self
+            .capacity(from, to)
+            .checked_sub(self.flow(from, to))
+            .unwrap_or(W::ZERO);
+        forward.saturating_add(self.flow(to, from))
+    }
+
+    /// Поток по каждому ребру сети: `(from, to, поток, пропускная
+    /// способность)`, по возрастанию концов. Параллельные ребра идут одной
+    /// записью.
+    pub fn edge_flows(&self) -> Vec<(Index, Index, W, W)> {
+        let mut edges: Vec<(Index, Index, W, W)> = self
+            .capacity
+            .iter()
+            .map(|(&(from, to), &capacity)| (from, to, self.flow(from, to), capacity))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    /// Минимальный разрез, отделяющий `source`. Имеет смысл после того,
+    /// как увеличивающих путей не осталось: иначе это лишь разрез по
+    /// текущему потоку.
+    ///
+    /// # Errors
+    /// Вернет [`GraphError`], если сумма пропускных способностей
+    /// переполнилась.
+    pub fn min_cut(&self, source: Index) -> Result<MinCut<W>> {
+        let mut reached: HashSet<Index> = HashSet::from([source]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(from) = queue.pop_front() {
+            for &to in self.neighbors.get(&from).into_iter().flatten() {
+                if self.residual(from, to) > W::ZERO && reached.insert(to) {
                     queue.push_back(to);
                 }
             }
         }
+
+        let mut edges: Vec<(Index, Index)> = self
+            .capacity
+            .keys()
+            .filter(|(from, to)| reached.contains(from) && !reached.contains(to))
+            .copied()
+            .collect();
+        edges.sort();
+        let mut capacity = W::ZERO;
+        for &(from, to) in &edges {
+            capacity = capacity.try_add(self.capacity(from, to))?;
+        }
+
+        let mut source_side: Vec<Index> = reached.into_iter().collect();
+        source_side.sort();
+        Ok(MinCut {
+            source_side,
+            edges,
+            capacity,
+        })
+    }
+
+    /// Поиск кратчайшего увеличивающего пути BFS-ом.
+    /// Возвращает путь от start до end включительно.
+    pub fn find_augmenting_path(&self, start: Index, end: Index) -> Option<Vec<Index>> {
+        let mut parent: HashMap<Index, Index> = HashMap::new();
+        let mut visited: HashSet<Index> = HashSet::new();
+        let mut queue = VecDeque::<Index>::new();
+
+        queue.push_back(start);
+        visited.insert(start);
+
+        'bfs: while let Some(from_ind) = queue.pop_front() {
+            if let Some(neighbors) = self.neighbors.get(&from_ind) {
+                for &to_ind in neighbors {
+                    if !visited.contains(&to_ind) && self.residual(from_ind, to_ind) > W::ZERO {
+                        parent.insert(to_ind, from_ind);
+                        visited.insert(to_ind);
+                        if to_ind == end {
+                            break 'bfs;
+                        }
+                        queue.push_back(to_ind);
+                    }
+                }
+            }
+        }
+
+        if start == end || !parent.contains_key(&end) {
+            return None;
+        }
+
+        // Восстанавливаем путь
+        let mut path = vec![end];
+        let mut v = end;
+        while let Some(&u) = parent.get(&v) {
+            path.push(u);
+            v = u;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Пускает по пути поток, равный бутылочному горлышку, и возвращает его
+    ///
+    /// # Errors
+    /// Вернет [`GraphError`], если суммарный поток переполнился.
+    pub fn augment(&mut self, path: &[Index]) -> Result<W> {
+        // Находим бутылочное горлышко
+        let path_flow = path
+            .windows(2)
+            .map(|w| self.residual(w[0], w[1]))
+            .min()
+            .unwrap_or(W::ZERO);
+
+        // Обновляем потоки вдоль пути
+        for w in path.windows(2) {
+            self.push(w[0], w[1], path_flow)?;
+        }
+
+        self.value = self.value.try_add(path_flow)?;
+        Ok(path_flow)
+    }
+
+    /// Пускает amount по дуге (from, to): сначала сокращаем встречный
+    /// поток, остаток пускаем по самой дуге
+    fn push(&mut self, from: Index, to: Index, amount: W) -> Result<()> {
+        let back = self.flow(to, from).min(amount);
+        let rest = amount.checked_sub(back).unwrap_or(W::ZERO);
+        let forward = self.flow(from, to).try_add(rest)?;
+        self.flow.insert(
+            (to, from),
+            self.flow(to, from).checked_sub(back).unwrap_or(W::ZERO),
+        );
+        self.flow.insert((from, to), forward);
+        Ok(())
+    }
+
+    /// Сеть с потоком, найденным другим алгоритмом. Потоки по
+    /// параллельным ребрам складываются; величина потока --- сколько
+    /// вытекает из `source`.
+    ///
+    /// # Errors
+    /// Вернет [`GraphError`], если поток переполнился.
+    pub(crate) fn with_flows(
+        mut self,
+        flows: impl IntoIterator<Item = (Index, Index, W)>,
+        source: Index,
+    ) -> Result<Self> {
+        for (from, to, amount) in flows {
+            self.push(from, to, amount)?;
+        }
+
+        let (mut outgoing, mut incoming) = (W::ZERO, W::ZERO);
+        for &other in self.neighbors.get(&source).into_iter().flatten() {
+            outgoing = outgoing.try_add(self.flow(source, other))?;
+            incoming = incoming.try_add(self.flow(other, source))?;
+        }
+        self.value = outgoing.checked_sub(incoming).unwrap_or(W::ZERO);
+        Ok(self)
     }
-    (parent.contains_key(&end), parent)
 }
 
 /// Нахождение максимального потока
-pub fn task_11<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
+pub fn task_11<G: GraphView>(graph: &G, start: Index, end: Index) -> Result<G::Weight> {
+    Ok(task_11_network(graph, start, end)?.value())
+}
+
+/// Сеть с максимальным потоком: поток по ребрам и минимальный разрез
+pub fn task_11_network<G: GraphView>(
+    graph: &G,
     start: Index,
     end: Index,
-) -> Result<u32> {
-    if !graph.get_is_directed() {
+) -> Result<FlowNetwork<G::Weight>> {
+    if !graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть сеть (орграф)",
         )));
     }
-    let BuildCapacityAndFlowResult(capacity, mut flow) = build_capacity_and_flow(graph);
-    let mut max_flow: i32 = 0;
-
-    loop {
-        let (found, parent) = bfs_find_path(graph, &capacity, &flow, start, end);
-        if !found {
-            break;
-        }
-
-        // Находим бутылочное горлышко
-        let mut path_flow: i32 = i32::MAX;
-        let mut v = end;
-        while let Some(Some(u)) = parent.get(&v) {
-            let r = residual(&capacity, &flow, *u, v);
-            path_flow = path_flow.min(r as i32);
-            v = *u;
-        }
-
-        // Обновляем потоки вдоль пути
-        let mut v2 = end;
-        while let Some(Some(u)) = parent.get(&v2) {
-            *flow.entry((*u, v2)).or_insert(0) += path_flow;
-            *flow.entry((v2, *u)).or_insert(0) -= path_flow;
-            v2 = *u;
-        }
 
-        max_flow += path_flow;
+    let mut network = FlowNetwork::new(graph)?;
+    while let Some(path) = network.find_augmenting_path(start, end) {
+        network.augment(&path)?;
     }
 
-    Ok(max_flow as u32)
+    Ok(network)
 }
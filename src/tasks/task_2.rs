@@ -1,15 +1,13 @@
-use std::{error::Error, fmt::Debug};
+use std::error::Error;
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{Graph, GraphError, GraphKindError};
+use crate::graph::{
+    core::{GraphError, GraphKindError},
+    view::GraphView,
+};
 
 /// Получить полустепень захода данной вершины орграфа
-pub fn task_2_4<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    node_index: &u32,
-) -> Result<u32, Box<dyn Error>> {
-    if !graph.get_is_directed() {
+pub fn task_2_4<G: GraphView>(graph: &G, node_index: &u32) -> Result<u32, Box<dyn Error>> {
+    if !graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть орграф",
@@ -18,12 +16,11 @@ pub fn task_2_4<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
     // Полустепень вершины
     let mut half_step: u32 = 0;
     // Проходимся по всем вершинам и считаем сколько раз встречается
-    // эта вершина в ребрах
-    for (_, adjacency) in graph {
-        for edg in adjacency {
-            if edg.node.number == *node_index {
+    // эта вершина в ребрах (параллельные ребра считаются каждое)
+    for index in graph.node_indices() {
+        for (neighbor, _) in graph.neighbors(index) {
+            if neighbor == *node_index {
                 half_step += 1;
-                break;
             }
         }
     }
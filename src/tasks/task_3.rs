@@ -1,14 +1,13 @@
-use std::{error::Error, fmt::Debug};
+use std::error::Error;
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{Graph, GraphError, GraphKindError};
+use crate::graph::{
+    core::{GraphError, GraphKindError},
+    view::GraphView,
+};
 
 /// Получить степень каждой вершины орграфа
-pub fn task_3_5<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-) -> Result<Vec<(u32, u32)>, Box<dyn Error>> {
-    if !graph.get_is_directed() {
+pub fn task_3_5<G: GraphView>(graph: &G) -> Result<Vec<(u32, u32)>, Box<dyn Error>> {
+    if !graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть орграф",
@@ -16,9 +15,9 @@ pub fn task_3_5<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
     }
     // Вектор пар (вершина, степень вершины)
     let mut result: Vec<(u32, u32)> = Vec::new();
-    for (&node_index, adjacency) in graph {
+    for node_index in graph.node_indices() {
         // заполняем вектор парами (вершина, количество ребер)
-        result.push((*node_index, adjacency.len().try_into().unwrap()));
+        result.push((*node_index, graph.neighbors(node_index).count().try_into()?));
     }
     Ok(result)
 }
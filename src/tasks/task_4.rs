@@ -2,19 +2,23 @@ use std::{error::Error, fmt::Debug};
 
 use serde::{Serialize, de::DeserializeOwned};
 
-use crate::graph::core::{Adjacency, Graph};
+use crate::graph::{
+    core::{Adjacency, Graph},
+    weight::Weight,
+};
 
-pub fn task_4_6<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph_1: &mut Graph<T>,
-    graph_2: &mut Graph<T>,
-) -> Result<Graph<T>, Box<dyn Error>> {
+pub fn task_4_6<T: Clone + DeserializeOwned + Debug + Serialize + Default, W: Weight>(
+    graph_1: &mut Graph<T, W>,
+    graph_2: &mut Graph<T, W>,
+) -> Result<Graph<T, W>, Box<dyn Error>> {
     // Приводим к графы к ориентированным графам, если у них разная ориентация
     if graph_1.get_is_directed() != graph_2.get_is_directed() {
         graph_1.to_directed_mut();
         graph_2.to_directed_mut();
     }
 
-    let mut res_graph: Graph<T> = Graph::new(None, Adjacency::default(), graph_1.get_is_directed());
+    let mut res_graph: Graph<T, W> =
+        Graph::new(None, Adjacency::default(), graph_1.get_is_directed());
 
     // Находим и добавляем все общие вершины в граф вместе с общими ребрами
     for (index, adjacency_s) in graph_2.iter() {
@@ -32,7 +36,11 @@ pub fn task_4_6<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
                         if res_graph.get_node(index).is_none() {
                             res_graph.add_node(node_s.clone())?;
                         }
-                        res_graph.add_edge(node, edge)?
+                        // Вторая половина неориентированного ребра уже
+                        // добавлена вместе с первой
+                        if !res_graph.has_edge(index, &edge.node.number)? {
+                            res_graph.add_edge(node, edge)?;
+                        }
                     }
                 }
             }
@@ -1,58 +1,35 @@
 use std::{
     collections::{HashMap, HashSet},
     error::Error,
-    fmt::Debug,
 };
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{ColorNode, Graph, GraphError, GraphKindError, GraphType, Index};
+use crate::graph::{
+    core::{ColorNode, GraphError, GraphKindError, GraphType, Index},
+    union_find::UnionFind,
+    view::GraphView,
+};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-fn dfs<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    start: &u32,
-    visited: &mut HashSet<u32>,
-    components: &mut Vec<Index>,
-) {
-    if !visited.contains(start) {
-        // Добавялем вершину в список компонентов связности
-        components.push((*start).into());
-    }
-
-    // Отмечаем вершину как посещенную
-    visited.insert(*start);
-
-    // Получаем ребра данной вершины
-    if let Some(adjacency) = graph.get_adjacency(&(*start).into()) {
-        for neighbor in adjacency {
-            if visited.get(&neighbor.node.number).is_none() {
-                dfs(graph, &neighbor.node.number, visited, components);
-            }
-        }
-    }
-}
-
 /// Проверка на лес
-fn is_a_forest<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-) -> Result<bool> {
-    let mut visited: HashSet<u32> = HashSet::new();
-    let mut subgraphs: Vec<Graph<T>> = Vec::new();
-
-    // Разбиваем граф на подграфы по связности
-    for (ind, _) in graph {
-        if !visited.contains(ind) {
-            let mut compnents = Vec::new();
-            dfs(graph, ind, &mut visited, &mut compnents);
-            subgraphs.push(graph.create_subgraph(compnents, true)?);
+fn is_a_forest<G: GraphView>(graph: &G) -> Result<bool> {
+    // Разбиваем граф на подграфы по слабой связности
+    let mut sets: UnionFind = graph.node_indices().collect();
+    for from in graph.node_indices() {
+        for (to, _) in graph.neighbors(from) {
+            sets.union(from, to);
         }
     }
+    let subgraphs: Vec<HashSet<Index>> = sets
+        .components()
+        .into_iter()
+        .map(|nodes| nodes.into_iter().collect())
+        .collect();
 
     // Проверяем, что все из них деревья
     let mut is_forest = true;
-    for subgraph in subgraphs {
+    for nodes in subgraphs {
+        let subgraph = graph.filtered(|index| nodes.contains(&index));
         is_forest = is_a_tree(&subgraph)?;
         if !is_forest {
             break;
@@ -63,8 +40,8 @@ fn is_a_forest<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
 }
 
 /// Проверяет есть ли цикл в графе из вершины start
-fn graph_have_cycle<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
+fn graph_have_cycle<G: GraphView>(
+    graph: &G,
     start: &u32,
     visited: &mut HashMap<Index, ColorNode>,
 ) -> Result<bool> {
@@ -83,18 +60,16 @@ fn graph_have_cycle<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
         )));
     };
 
-    if let Some(adjacency) = graph.get_adjacency(&(*start).into()) {
-        // Проходимся по всем смежным вершинам
-        for neighbor in adjacency {
-            if let Some(color) = visited.get(&neighbor.node.number) {
-                // Если цвет не серый
-                if *color != ColorNode::Gray {
-                    if graph_have_cycle(graph, &neighbor.node.number, visited)? {
-                        return Ok(true);
-                    }
-                } else {
+    // Проходимся по всем смежным вершинам
+    for (neighbor, _) in graph.neighbors((*start).into()) {
+        if let Some(color) = visited.get(&neighbor) {
+            // Если цвет не серый
+            if *color != ColorNode::Gray {
+                if graph_have_cycle(graph, &neighbor, visited)? {
                     return Ok(true);
                 }
+            } else {
+                return Ok(true);
             }
         }
     }
@@ -108,38 +83,37 @@ fn graph_have_cycle<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
 }
 
 /// Проверка является ли граф деревом
-fn is_a_tree<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-) -> Result<bool> {
+fn is_a_tree<G: GraphView>(graph: &G) -> Result<bool> {
     let mut count = 0;
     let mut cycle = false;
     let mut is_connected = false;
 
-    let mut visited = graph.get_nodes_with_color();
-    for (index, adjacency) in graph {
-        count += adjacency.len();
+    let mut visited: HashMap<Index, ColorNode> = graph
+        .node_indices()
+        .map(|index| (index, ColorNode::White))
+        .collect();
+    for index in graph.node_indices() {
+        count += graph.neighbors(index).count();
         if !cycle {
-            cycle = graph_have_cycle(graph, index, &mut visited)?;
+            cycle = graph_have_cycle(graph, &index, &mut visited)?;
             if !is_connected {
                 is_connected = visited.values().all(|color| *color == ColorNode::Black);
             }
         }
     }
-    Ok((graph.len() as i64 - count as i64) == 1 && !cycle && is_connected)
+    Ok((graph.node_count() as i64 - count as i64) == 1 && !cycle && is_connected)
 }
 
 /// Проверка является ли граф деревом, лесом или обычным
-pub fn task_5_18<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-) -> Result<GraphType> {
-    if !graph.get_is_directed() {
+pub fn task_5_18<G: GraphView>(graph: &G) -> Result<GraphType> {
+    if !graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть орграф",
         )));
     }
 
-    if graph.len() == 0 || is_a_tree(graph)? {
+    if graph.node_count() == 0 || is_a_tree(graph)? {
         return Ok(GraphType::Tree);
     }
 
@@ -1,56 +1,46 @@
 use std::{
     collections::{HashSet, VecDeque},
     error::Error,
-    fmt::Debug,
 };
 
-use serde::{Serialize, de::DeserializeOwned};
-
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
-use crate::graph::core::{Graph, GraphError, GraphKindError};
+use crate::graph::{
+    core::{GraphError, GraphKindError, Index},
+    view::GraphView,
+};
 
-fn bfs<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    start: &u32,
-    visited: &mut HashSet<u32>,
-) {
+fn bfs<G: GraphView>(graph: &G, start: Index, visited: &mut HashSet<Index>) {
     // Создали очередь со стартовой вершиной
-    let mut queue: VecDeque<u32> = VecDeque::new();
-    queue.push_back(*start);
-    visited.insert(*start);
+    let mut queue: VecDeque<Index> = VecDeque::new();
+    queue.push_back(start);
+    visited.insert(start);
 
     // Пока в очереди есть вершины
     while let Some(current) = queue.pop_front() {
-        if let Some(adjacency) = graph.get_adjacency(&current.into()) {
-            for neighbor in adjacency {
-                let neighbor_index = neighbor.node.number;
-                // Если смежная вершина не посещена, то добавляем в очередь
-                // и отмечаем как посещенную
-                if !visited.contains(&neighbor_index) {
-                    visited.insert(*neighbor_index);
-                    queue.push_back(*neighbor_index);
-                }
+        for (neighbor_index, _) in graph.neighbors(current) {
+            // Если смежная вершина не посещена, то добавляем в очередь
+            // и отмечаем как посещенную
+            if visited.insert(neighbor_index) {
+                queue.push_back(neighbor_index);
             }
         }
     }
 }
 
 /// Выясняет, является ли граф связным.
-pub fn task_6_4<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-) -> Result<bool> {
-    if graph.get_is_directed() {
+pub fn task_6_4<G: GraphView>(graph: &G) -> Result<bool> {
+    if graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть неориентированный граф",
         )));
     }
 
-    let mut visited: HashSet<u32> = HashSet::new();
+    let mut visited: HashSet<Index> = HashSet::new();
 
-    if let Some(node_ind) = graph.get_some_node_index() {
+    if let Some(node_ind) = graph.node_indices().next() {
         bfs(graph, node_ind, &mut visited);
     }
 
-    Ok(visited.len() == graph.len())
+    Ok(visited.len() == graph.node_count())
 }
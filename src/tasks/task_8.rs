@@ -1,34 +1,34 @@
 use std::{
+    cmp::Reverse,
     collections::{BinaryHeap, HashMap},
     error::Error,
-    fmt::Debug,
 };
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{Graph, GraphError, GraphKindError, Index};
+use crate::graph::{
+    core::{GraphError, GraphKindError, Index},
+    view::GraphView,
+    weight::{Weight, ensure_non_negative},
+};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-fn deikstra<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
+fn deikstra<G: GraphView>(
+    graph: &G,
     start: Index,
     destination: Index,
-    weight_limit: u32,
-) -> bool {
-    let mut dist = HashMap::new();
+    weight_limit: G::Weight,
+) -> Result<bool> {
+    // Вершины, которых нет в dist, еще не достигнуты
+    let mut dist: HashMap<Index, G::Weight> = HashMap::new();
     let mut heap = BinaryHeap::new();
 
-    // Для всех вершин кроме начальной выставляем максимум
-    for (index, _) in graph.iter() {
-        dist.insert(*index, u32::MAX);
-    }
-
     // Ставим 0 для начальной вершины
-    dist.insert(start, 0);
-    heap.push((0u32, start));
+    dist.insert(start, G::Weight::ZERO);
+    // BinaryHeap --- max-куча, поэтому кладем Reverse, чтобы первой
+    // доставать вершину с минимальным расстоянием
+    heap.push(Reverse((G::Weight::ZERO, start)));
 
-    while let Some((weight, cur_node)) = heap.pop() {
+    while let Some(Reverse((weight, cur_node))) = heap.pop() {
         // Если длина уже больше weight_limit, то нет смысла считать дальше
         if weight > weight_limit {
             break;
@@ -36,43 +36,42 @@ fn deikstra<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
 
         // Если дошли до нужной вершины
         if cur_node == destination {
-            return true;
+            return Ok(true);
+        }
+
+        // Устаревшая запись в куче
+        if weight > dist[&cur_node] {
+            continue;
         }
 
-        if let Some(adj) = graph.get_adjacency(&cur_node) {
-            for edge in adj {
-                let node = edge.node.number;
-                let new_weight = weight + edge.weight;
+        for (node, edge_weight) in graph.neighbors(cur_node) {
+            let new_weight = weight.try_add(edge_weight)?;
 
-                // Если сосдед не посящен
-                if new_weight < dist[&node] && new_weight <= weight_limit {
-                    dist.insert(node, new_weight);
-                    heap.push((new_weight, node));
-                }
+            // Если сосдед не посящен
+            if dist.get(&node).is_none_or(|&old| new_weight < old) && new_weight <= weight_limit {
+                dist.insert(node, new_weight);
+                heap.push(Reverse((new_weight, node)));
             }
         }
     }
 
-    false
+    Ok(false)
 }
 
-pub fn task_8_1<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
+pub fn task_8_1<G: GraphView>(
+    graph: &G,
     start: u32,
     destination: u32,
-    weight_limit: u32,
+    weight_limit: G::Weight,
 ) -> Result<bool> {
-    if graph.get_is_directed() {
+    if graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть неориентированный граф",
         )));
     }
 
-    Ok(deikstra(
-        graph,
-        start.into(),
-        destination.into(),
-        weight_limit,
-    ))
+    ensure_non_negative(graph)?;
+
+    deikstra(graph, start.into(), destination.into(), weight_limit)
 }
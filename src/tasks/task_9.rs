@@ -1,71 +1,51 @@
-use std::{collections::HashMap, error::Error, fmt::Debug};
+use std::error::Error;
 
-use serde::{Serialize, de::DeserializeOwned};
-
-use crate::graph::core::{Graph, GraphError, GraphKindError, Index};
+use crate::{
+    algorithms::shortest_path,
+    graph::{
+        core::{GraphError, GraphKindError, Index},
+        view::GraphView,
+        weight::Weight,
+    },
+};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
 /// Находим сумму минимальных путей до всех вершин достижимых вершин из
 /// вершины start
-fn bellman_ford<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    start: &Index,
-) -> u32 {
-    // Создаем HashMap из индекса вершины и длины пути до нее
-    // (изначально максимум для u32)
-    let mut dist = HashMap::new();
-    for (index, _) in graph.iter() {
-        dist.insert(*index, u32::MAX);
-    }
-
-    // Для вершины start длина пути 0
-    dist.insert(*start, 0);
-
-    // Перебираем все ребра
-    for _ in 0..graph.len() - 1 {
-        for (ind, adj) in graph.iter() {
-            for edge in adj {
-                let edge_node_num = edge.node.number;
-                // Если вес ребра короче чем чем текущее значение в dist
-                if dist[&edge_node_num] != u32::MAX
-                    && dist[ind] > dist[&edge_node_num] + edge.weight
-                {
-                    // Обновляем значение веса для данной вершины
-                    dist.insert(*ind, dist[&edge_node_num] + edge.weight);
-                }
-            }
-        }
-    }
-
-    dist.iter()
-        .filter(|&(ind, _)| ind != start) // не учитываем расстояние от start
-        .map(|(_, weight)| *weight)
-        .filter(|&v| v != u32::MAX) // не учитываем недостижимые
-        .sum::<u32>()
+fn bellman_ford<G: GraphView>(graph: &G, start: &Index) -> Result<G::Weight> {
+    let paths = shortest_path::bellman_ford(graph, *start)?;
+
+    // Не учитываем расстояние от start; недостижимых в дереве путей нет
+    paths
+        .distances()
+        .iter()
+        .filter(|&(ind, _)| ind != start)
+        .try_fold(
+            G::Weight::ZERO,
+            |sum, (_, &weight)| Ok(sum.try_add(weight)?),
+        )
 }
 
-/// Найти в [`Graph<T>`] вершину, минимальные стоимости путей от которой до
+/// Найти в графе вершину, минимальные стоимости путей от которой до
 /// остальных в сумме не превосходят limit
 ///
 /// # Errors
 /// Эта функция вернет ошибку, если граф ориентированный.
-/// Ошибка типа: [`GraphError`]
-pub fn task_9_2<T: Clone + DeserializeOwned + Debug + Serialize + Default>(
-    graph: &Graph<T>,
-    limit: &u32,
-) -> Result<i32> {
-    if graph.get_is_directed() {
+/// Ошибка типа: [`GraphError`]; для ребра отрицательного веса ---
+/// [`NegativeCycle`](crate::algorithms::NegativeCycle).
+pub fn task_9_2<G: GraphView>(graph: &G, limit: &G::Weight) -> Result<i32> {
+    if graph.is_directed() {
         return Err(Box::new(GraphError::new(
             GraphKindError::GraphMustBeDirected,
             "по условию должен быть неориентированный граф",
         )));
     }
 
-    for (start, _) in graph {
-        let res = bellman_ford(graph, start);
-        if res > 0 && res <= *limit {
-            return Ok((**start) as i32);
+    for start in graph.node_indices() {
+        let res = bellman_ford(graph, &start)?;
+        if res > G::Weight::ZERO && res <= *limit {
+            return Ok((*start) as i32);
         }
     }
 